@@ -0,0 +1,22 @@
+// This shader averages the luminance of the camera's output into a single pixel.
+// It samples a grid of points instead of every pixel, which is close enough for most uses.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+
+const SAMPLES: u32 = 32u;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    var total = 0.0;
+    for (var y = 0u; y < SAMPLES; y += 1u) {
+        for (var x = 0u; x < SAMPLES; x += 1u) {
+            let uv = (vec2<f32>(f32(x), f32(y)) + 0.5) / f32(SAMPLES);
+            let color = textureSampleLevel(screen_texture, texture_sampler, uv, 0.0).rgb;
+            total += dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+        }
+    }
+    return vec4<f32>(total / f32(SAMPLES * SAMPLES), 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,20 @@
+// A post process material that desaturates and tints the output of a camera.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var screen_sampler: sampler;
+
+struct ColorGrading {
+    tint: vec4<f32>,
+    saturation: f32,
+}
+@group(1) @binding(0) var<uniform> grading: ColorGrading;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(screen_texture, screen_sampler, in.uv);
+    let luminance = dot(color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let graded = mix(vec3<f32>(luminance), color.rgb, grading.saturation) * grading.tint.rgb;
+    return vec4<f32>(graded, color.a);
+}
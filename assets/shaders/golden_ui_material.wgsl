@@ -0,0 +1,10 @@
+// This shader colors the left and the right half of a node differently
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+@group(1) @binding(0) var<uniform> left: vec4<f32>;
+@group(1) @binding(1) var<uniform> right: vec4<f32>;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    return select(right, left, in.uv.x < 0.5);
+}
@@ -0,0 +1,323 @@
+use std::{fmt, hash::Hash};
+
+use bevy_ecs::system::{Res, SystemParam};
+use bevy_input::{
+    gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, ButtonInput,
+};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+
+/// An action of a [`CameraController`](crate::CameraController) that can be bound to inputs in
+/// its [`CameraBindings`].
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CameraAction {
+    /// Fly forward.
+    Forward,
+    /// Fly backward.
+    Back,
+    /// Fly to the left.
+    Left,
+    /// Fly to the right.
+    Right,
+    /// Fly up.
+    Up,
+    /// Fly down.
+    Down,
+    /// Fly at the run speed while held.
+    Run,
+    /// Grab the cursor and look around while held.
+    GrabCursor,
+    /// Toggle grabbing the cursor.
+    ToggleCursorGrab,
+    /// Rotate around the focus while held, in orbit mode.
+    Orbit,
+    /// Move the focus while held, in orbit mode.
+    Pan,
+    /// Store a bookmark when a number key is pressed while held, see
+    /// [`CameraBookmarks`](crate::CameraBookmarks).
+    StoreBookmark,
+    /// Add the current viewpoint to the [`CameraPath`](crate::CameraPath) of the camera.
+    RecordWaypoint,
+    /// Start or stop flying along the [`CameraPath`](crate::CameraPath) of the camera.
+    TogglePath,
+}
+
+/// A button that can be bound to a [`CameraAction`].
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CameraInput {
+    /// A key on the keyboard.
+    Key(KeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+    /// A button on any connected gamepad.
+    Gamepad(GamepadButtonType),
+}
+
+impl From<KeyCode> for CameraInput {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for CameraInput {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl From<GamepadButtonType> for CameraInput {
+    fn from(button: GamepadButtonType) -> Self {
+        Self::Gamepad(button)
+    }
+}
+
+impl fmt::Display for CameraInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key:?}"),
+            Self::Mouse(button) => write!(f, "Mouse {button:?}"),
+            Self::Gamepad(button) => write!(f, "Gamepad {button:?}"),
+        }
+    }
+}
+
+/// The inputs bound to each [`CameraAction`] of a [`CameraController`](crate::CameraController).
+///
+/// An action is active when any of its inputs is. Bindings can be changed at runtime, for
+/// example from an input settings screen.
+///
+/// # Example
+/// ```
+/// # use bevy_camera_controller::{CameraAction, CameraController};
+/// # use bevy_input::{gamepad::GamepadButtonType, keyboard::KeyCode};
+/// let mut controller = CameraController::default();
+/// controller
+///     .bindings
+///     .rebind(CameraAction::Forward, KeyCode::ArrowUp)
+///     .bind(CameraAction::Run, GamepadButtonType::LeftThumb);
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct CameraBindings {
+    bindings: HashMap<CameraAction, Vec<CameraInput>>,
+}
+
+impl Default for CameraBindings {
+    fn default() -> Self {
+        Self::empty()
+            .with(CameraAction::Forward, KeyCode::KeyW)
+            .with(CameraAction::Back, KeyCode::KeyS)
+            .with(CameraAction::Left, KeyCode::KeyA)
+            .with(CameraAction::Right, KeyCode::KeyD)
+            .with(CameraAction::Up, KeyCode::KeyE)
+            .with(CameraAction::Down, KeyCode::KeyQ)
+            .with(CameraAction::Run, KeyCode::ShiftLeft)
+            .with(CameraAction::GrabCursor, MouseButton::Left)
+            .with(CameraAction::ToggleCursorGrab, KeyCode::KeyM)
+            .with(CameraAction::Orbit, MouseButton::Left)
+            .with(CameraAction::Pan, MouseButton::Middle)
+            .with(CameraAction::StoreBookmark, KeyCode::ControlLeft)
+            .with(CameraAction::StoreBookmark, KeyCode::ControlRight)
+            .with(CameraAction::RecordWaypoint, KeyCode::KeyR)
+            .with(CameraAction::TogglePath, KeyCode::KeyP)
+    }
+}
+
+impl CameraBindings {
+    /// Creates bindings without any inputs.
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::default(),
+        }
+    }
+
+    /// Builder-style version of [`bind`](Self::bind).
+    pub fn with(mut self, action: CameraAction, input: impl Into<CameraInput>) -> Self {
+        self.bind(action, input);
+        self
+    }
+
+    /// Adds an input to the inputs bound to an action.
+    pub fn bind(&mut self, action: CameraAction, input: impl Into<CameraInput>) -> &mut Self {
+        let input = input.into();
+        let inputs = self.bindings.entry(action).or_default();
+        if !inputs.contains(&input) {
+            inputs.push(input);
+        }
+        self
+    }
+
+    /// Replaces all inputs bound to an action with a single input.
+    pub fn rebind(&mut self, action: CameraAction, input: impl Into<CameraInput>) -> &mut Self {
+        self.bindings.insert(action, vec![input.into()]);
+        self
+    }
+
+    /// Removes an input from the inputs bound to an action.
+    pub fn unbind(&mut self, action: CameraAction, input: impl Into<CameraInput>) -> &mut Self {
+        let input = input.into();
+        if let Some(inputs) = self.bindings.get_mut(&action) {
+            inputs.retain(|bound| *bound != input);
+        }
+        self
+    }
+
+    /// Removes all inputs bound to an action.
+    pub fn clear(&mut self, action: CameraAction) -> &mut Self {
+        self.bindings.remove(&action);
+        self
+    }
+
+    /// Returns the inputs bound to an action.
+    pub fn inputs(&self, action: CameraAction) -> &[CameraInput] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Formats the inputs bound to an action for display, such as `KeyW / ArrowUp`.
+    pub fn describe(&self, action: CameraAction) -> String {
+        let inputs = self.inputs(action);
+        if inputs.is_empty() {
+            return "Unbound".to_string();
+        }
+        inputs
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+/// The button states [`CameraBindings`] are evaluated against.
+#[derive(SystemParam)]
+pub(crate) struct CameraInputs<'w> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    gamepad_button_axes: Res<'w, Axis<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+    gamepads: Res<'w, Gamepads>,
+}
+
+impl CameraInputs<'_> {
+    /// Returns `true` if any input bound to `action` is pressed.
+    pub(crate) fn pressed(&self, bindings: &CameraBindings, action: CameraAction) -> bool {
+        self.any(bindings, action, ButtonState::Pressed)
+    }
+
+    /// Returns `true` if any input bound to `action` was pressed this frame.
+    pub(crate) fn just_pressed(&self, bindings: &CameraBindings, action: CameraAction) -> bool {
+        self.any(bindings, action, ButtonState::JustPressed)
+    }
+
+    /// Returns `true` if any input bound to `action` was released this frame.
+    pub(crate) fn just_released(&self, bindings: &CameraBindings, action: CameraAction) -> bool {
+        self.any(bindings, action, ButtonState::JustReleased)
+    }
+
+    /// Returns the position of a stick, taken from the connected gamepad that moves it furthest.
+    pub(crate) fn gamepad_stick(&self, x: GamepadAxisType, y: GamepadAxisType) -> Vec2 {
+        self.gamepads
+            .iter()
+            .map(|gamepad| {
+                let axis = |axis_type| {
+                    self.gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis_type))
+                        .unwrap_or(0.0)
+                };
+                Vec2::new(axis(x), axis(y))
+            })
+            .fold(Vec2::ZERO, |furthest, stick| {
+                if stick.length_squared() > furthest.length_squared() {
+                    stick
+                } else {
+                    furthest
+                }
+            })
+    }
+
+    /// Returns how far an analog button, such as a trigger, is pressed on any connected gamepad.
+    pub(crate) fn gamepad_trigger(&self, button_type: GamepadButtonType) -> f32 {
+        self.gamepads
+            .iter()
+            .filter_map(|gamepad| {
+                self.gamepad_button_axes
+                    .get(GamepadButton::new(gamepad, button_type))
+            })
+            .fold(0.0, f32::max)
+    }
+
+    fn any(&self, bindings: &CameraBindings, action: CameraAction, state: ButtonState) -> bool {
+        bindings.inputs(action).iter().any(|&input| match input {
+            CameraInput::Key(key) => state.of(&self.keys, key),
+            CameraInput::Mouse(button) => state.of(&self.mouse_buttons, button),
+            CameraInput::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                state.of(
+                    &self.gamepad_buttons,
+                    GamepadButton::new(gamepad, button_type),
+                )
+            }),
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ButtonState {
+    Pressed,
+    JustPressed,
+    JustReleased,
+}
+
+impl ButtonState {
+    fn of<T>(self, input: &ButtonInput<T>, button: T) -> bool
+    where
+        T: Copy + Eq + Hash + Send + Sync + 'static,
+    {
+        match self {
+            Self::Pressed => input.pressed(button),
+            Self::JustPressed => input.just_pressed(button),
+            Self::JustReleased => input.just_released(button),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_adds_each_input_once() {
+        let mut bindings = CameraBindings::empty();
+        bindings
+            .bind(CameraAction::Forward, KeyCode::KeyW)
+            .bind(CameraAction::Forward, KeyCode::ArrowUp)
+            .bind(CameraAction::Forward, KeyCode::KeyW);
+        assert_eq!(
+            bindings.inputs(CameraAction::Forward),
+            [KeyCode::KeyW.into(), KeyCode::ArrowUp.into()]
+        );
+        assert_eq!(bindings.describe(CameraAction::Forward), "KeyW / ArrowUp");
+    }
+
+    #[test]
+    fn rebind_unbind_and_clear() {
+        let mut bindings = CameraBindings::default();
+        bindings.rebind(CameraAction::Run, GamepadButtonType::LeftThumb);
+        assert_eq!(
+            bindings.inputs(CameraAction::Run),
+            [GamepadButtonType::LeftThumb.into()]
+        );
+
+        bindings.unbind(CameraAction::StoreBookmark, KeyCode::ControlLeft);
+        assert_eq!(
+            bindings.inputs(CameraAction::StoreBookmark),
+            [KeyCode::ControlRight.into()]
+        );
+
+        bindings.clear(CameraAction::Pan);
+        assert!(bindings.inputs(CameraAction::Pan).is_empty());
+        assert_eq!(bindings.describe(CameraAction::Pan), "Unbound");
+    }
+}
@@ -0,0 +1,187 @@
+use bevy_ecs::{
+    reflect::ReflectResource,
+    system::{Local, Query, Res, ResMut, Resource},
+};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_log::info;
+use bevy_math::{Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+use crate::{CameraAction, CameraController, CameraControllerMode, CameraInputs};
+
+/// The number keys selecting the bookmark slots, in slot order.
+const SLOT_KEYS: [KeyCode; CameraBookmarks::SLOTS] = [
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// A viewpoint stored in [`CameraBookmarks`].
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(Default)]
+pub struct CameraBookmark {
+    /// The transform of the camera.
+    pub transform: Transform,
+    /// The mode of the [`CameraController`].
+    pub mode: CameraControllerMode,
+    /// The yaw of the [`CameraController`], in radians.
+    pub yaw: f32,
+    /// The pitch of the [`CameraController`], in radians.
+    pub pitch: f32,
+}
+
+impl Default for CameraBookmark {
+    fn default() -> Self {
+        Self {
+            transform: Transform::IDENTITY,
+            mode: CameraControllerMode::Fly,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+}
+
+impl CameraBookmark {
+    /// Creates a bookmark of the current viewpoint of a camera.
+    pub fn new(transform: &Transform, controller: &CameraController) -> Self {
+        Self {
+            transform: *transform,
+            mode: controller.mode,
+            yaw: controller.yaw,
+            pitch: controller.pitch,
+        }
+    }
+}
+
+/// Numbered viewpoint slots of the camera with a [`CameraController`].
+///
+/// Holding [`CameraAction::StoreBookmark`] and pressing a number key stores the current viewpoint
+/// in the slot of that number. Pressing the number key alone moves the camera back to it over
+/// [`CameraController::bookmark_transition_time`].
+///
+/// The slots can also be filled or read directly, for example to persist them between runs.
+#[derive(Resource, Reflect, Debug, Clone, Default)]
+#[reflect(Resource, Default)]
+pub struct CameraBookmarks {
+    /// The bookmarks, indexed by slot.
+    pub slots: [Option<CameraBookmark>; CameraBookmarks::SLOTS],
+}
+
+impl CameraBookmarks {
+    /// The number of bookmark slots, one for each number key.
+    pub const SLOTS: usize = 10;
+
+    /// Returns the bookmark in `slot`, if any.
+    pub fn get(&self, slot: usize) -> Option<&CameraBookmark> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// Stores `bookmark` in `slot`, returning the bookmark it replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slot` is not less than [`CameraBookmarks::SLOTS`].
+    pub fn set(&mut self, slot: usize, bookmark: CameraBookmark) -> Option<CameraBookmark> {
+        self.slots[slot].replace(bookmark)
+    }
+}
+
+/// A camera moving to a recalled [`CameraBookmark`].
+pub(crate) struct BookmarkTransition {
+    from: Transform,
+    to: CameraBookmark,
+    elapsed: f32,
+}
+
+pub(crate) fn run_camera_bookmarks(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    inputs: CameraInputs,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut transition: Local<Option<BookmarkTransition>>,
+    mut query: Query<(&mut Transform, &mut CameraController)>,
+) {
+    let Ok((mut transform, mut controller)) = query.get_single_mut() else {
+        return;
+    };
+
+    if controller.enabled {
+        let store = inputs.pressed(&controller.bindings, CameraAction::StoreBookmark);
+        for (slot, &key) in SLOT_KEYS.iter().enumerate() {
+            if !keys.just_pressed(key) {
+                continue;
+            }
+            if store {
+                bookmarks.set(slot, CameraBookmark::new(&transform, &controller));
+                info!("Stored camera bookmark {slot}");
+            } else if let Some(bookmark) = bookmarks.get(slot) {
+                *transition = Some(BookmarkTransition {
+                    from: *transform,
+                    to: bookmark.clone(),
+                    elapsed: 0.0,
+                });
+            }
+        }
+    }
+
+    let Some(BookmarkTransition { from, to, elapsed }) = transition.as_mut() else {
+        return;
+    };
+
+    *elapsed += time.delta_seconds();
+    let t = if controller.bookmark_transition_time > 0.0 {
+        (*elapsed / controller.bookmark_transition_time).min(1.0)
+    } else {
+        1.0
+    };
+    // Ease in and out
+    let t = t * t * (3.0 - 2.0 * t);
+
+    transform.translation = from.translation.lerp(to.transform.translation, t);
+    transform.rotation = from.rotation.slerp(to.transform.rotation, t);
+    transform.scale = from.scale.lerp(to.transform.scale, t);
+
+    if t >= 1.0 {
+        controller.mode = to.mode;
+        controller.yaw = to.yaw;
+        controller.pitch = to.pitch;
+        controller.smoothed_yaw = to.yaw;
+        controller.smoothed_pitch = to.pitch;
+        controller.rotation_velocity = Vec2::ZERO;
+        controller.velocity = Vec3::ZERO;
+        *transition = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_replaces_the_bookmark_in_a_slot() {
+        let mut bookmarks = CameraBookmarks::default();
+        assert_eq!(bookmarks.get(3), None);
+
+        let first = CameraBookmark::default();
+        let second = CameraBookmark {
+            mode: CameraControllerMode::Orbit {
+                focus: Vec3::ZERO,
+                distance: 5.0,
+            },
+            ..Default::default()
+        };
+        assert_eq!(bookmarks.set(3, first.clone()), None);
+        assert_eq!(bookmarks.set(3, second.clone()), Some(first));
+        assert_eq!(bookmarks.get(3), Some(&second));
+        assert_eq!(bookmarks.get(CameraBookmarks::SLOTS), None);
+    }
+}
@@ -0,0 +1,576 @@
+use std::{
+    f32::consts::{FRAC_PI_2, PI, TAU},
+    fmt,
+};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    reflect::ReflectComponent,
+    system::{Local, Query, Res},
+};
+use bevy_input::{
+    gamepad::{GamepadAxisType, GamepadButtonType},
+    mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+};
+use bevy_log::info;
+use bevy_math::{EulerRot, Quat, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::{Camera, Projection};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_window::{CursorGrabMode, Window};
+
+use crate::{CameraAction, CameraBindings, CameraControllerSettings, CameraInputs};
+
+/// The rotation applied per unit of mouse motion, at a
+/// [`CameraControllerSettings::mouse_sensitivity`] of `1.0`.
+///
+/// Based on Valorant's default sensitivity, not entirely sure why it is exactly 1.0 / 180.0,
+/// but I'm guessing it is a misunderstanding between degrees/radians and then sticking with
+/// it because it felt nice.
+pub const RADIANS_PER_DOT: f32 = 1.0 / 180.0;
+
+/// How a [`CameraController`] moves its camera.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+#[reflect(Default)]
+pub enum CameraControllerMode {
+    /// Fly around freely, moving with the keyboard and looking around with the mouse.
+    #[default]
+    Fly,
+    /// Orbit around a pivot point, rotating and panning by dragging the mouse and zooming by
+    /// scrolling.
+    Orbit {
+        /// The point the camera orbits around and looks at.
+        focus: Vec3,
+        /// The distance from the camera to [`focus`](CameraControllerMode::Orbit::focus).
+        distance: f32,
+    },
+    /// Follow an entity, staying at an offset from it and turning to look at it.
+    ///
+    /// How closely the camera follows is set by [`CameraController::follow_smoothing`], and how
+    /// quickly it turns by [`CameraController::rotation_smooth_time`].
+    Follow {
+        /// The entity to follow. It needs a [`GlobalTransform`].
+        target: Entity,
+        /// The position of the camera relative to the target, in world space.
+        offset: Vec3,
+        /// Keeps the current orientation of the camera instead of looking at the target.
+        lock_rotation: bool,
+    },
+}
+
+/// A camera controller, with a freecam-style [fly mode](CameraControllerMode::Fly), an
+/// [orbit mode](CameraControllerMode::Orbit) and a [follow mode](CameraControllerMode::Follow).
+///
+/// Add this [`Component`] to an entity with a [`Camera`] to move it around with the keyboard and
+/// mouse. This requires the [`CameraControllerPlugin`](crate::CameraControllerPlugin).
+///
+/// The mode can be switched at runtime by changing [`mode`](Self::mode). When switching to orbit
+/// mode, the camera is moved so that it looks at the focus from its current orientation.
+///
+/// The [`Display`](fmt::Display) implementation lists the controls, and is logged when the
+/// controller is first used. Preferences such as the mouse sensitivity are shared by all
+/// controllers through the [`CameraControllerSettings`] resource.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct CameraController {
+    /// Whether the controller responds to input.
+    pub enabled: bool,
+    /// How the controller moves the camera.
+    pub mode: CameraControllerMode,
+    /// Set once [`pitch`](Self::pitch) and [`yaw`](Self::yaw) were read from the transform of
+    /// the camera.
+    pub initialized: bool,
+    /// The inputs bound to each [`CameraAction`].
+    pub bindings: CameraBindings,
+    /// The speed when flying normally, in units per second.
+    ///
+    /// While [`CameraAction::Run`] is held, this is multiplied by
+    /// [`CameraControllerSettings::run_speed_multiplier`].
+    pub walk_speed: f32,
+    /// How much scrolling a line changes the speed in fly mode, or the distance to the focus in
+    /// orbit mode, relative to its current value.
+    pub scroll_factor: f32,
+    /// How quickly the velocity approaches the speed of a pressed movement key, per second.
+    ///
+    /// The velocity closes `1 - exp(-acceleration)` of the remaining difference each second, so
+    /// larger values feel snappier. [`f32::INFINITY`] reaches the speed immediately.
+    pub acceleration: f32,
+    /// How quickly the camera comes to a stop when no movement key is pressed, per second.
+    ///
+    /// This works like [`acceleration`](Self::acceleration), with a target velocity of zero.
+    pub deceleration: f32,
+    /// The time in seconds it takes the orientation of the camera to catch up with the mouse,
+    /// using a critically damped spring.
+    ///
+    /// A value of `0.0` disables rotational smoothing.
+    pub rotation_smooth_time: f32,
+    /// The target pitch of the camera, in radians.
+    pub pitch: f32,
+    /// The target yaw of the camera, in radians.
+    pub yaw: f32,
+    /// The pitch currently applied to the camera, which follows [`pitch`](Self::pitch) according
+    /// to [`rotation_smooth_time`](Self::rotation_smooth_time).
+    pub smoothed_pitch: f32,
+    /// The yaw currently applied to the camera, which follows [`yaw`](Self::yaw) according to
+    /// [`rotation_smooth_time`](Self::rotation_smooth_time).
+    pub smoothed_yaw: f32,
+    /// The current rate of change of the smoothed yaw (`x`) and pitch (`y`), in radians per second.
+    pub rotation_velocity: Vec2,
+    /// The current velocity of the camera, relative to its orientation.
+    pub velocity: Vec3,
+    /// How far a gamepad stick or trigger has to be moved before it has an effect, between `0.0`
+    /// and `1.0`.
+    pub gamepad_dead_zone: f32,
+    /// The exponent applied to the gamepad input outside of the dead zone.
+    ///
+    /// Values above `1.0` allow finer control near the center of the sticks, `1.0` is linear.
+    pub gamepad_response_curve: f32,
+    /// The rotation speed of the camera with the right stick fully tilted, in radians per second.
+    pub gamepad_look_speed: f32,
+    /// How quickly the camera catches up with its target in follow mode, per second.
+    ///
+    /// This works like [`acceleration`](Self::acceleration). [`f32::INFINITY`] keeps the camera
+    /// rigidly attached to the target.
+    pub follow_smoothing: f32,
+    /// The time in seconds it takes to move to a recalled bookmark, see
+    /// [`CameraBookmarks`](crate::CameraBookmarks).
+    pub bookmark_transition_time: f32,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mode: CameraControllerMode::Fly,
+            initialized: false,
+            bindings: CameraBindings::default(),
+            walk_speed: 5.0,
+            scroll_factor: 0.1,
+            acceleration: f32::INFINITY,
+            deceleration: 40.0,
+            rotation_smooth_time: 0.0,
+            pitch: 0.0,
+            yaw: 0.0,
+            smoothed_pitch: 0.0,
+            smoothed_yaw: 0.0,
+            rotation_velocity: Vec2::ZERO,
+            velocity: Vec3::ZERO,
+            gamepad_dead_zone: 0.15,
+            gamepad_response_curve: 2.0,
+            gamepad_look_speed: 2.5,
+            follow_smoothing: 8.0,
+            bookmark_transition_time: 0.5,
+        }
+    }
+}
+
+impl fmt::Display for CameraController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let CameraControllerMode::Follow { .. } = self.mode {
+            return write!(
+                f,
+                "
+Follow Controls:
+    {} + 0-9\t- Store bookmark
+    0-9\t- Recall bookmark",
+                self.bindings.describe(CameraAction::StoreBookmark),
+            );
+        }
+        if let CameraControllerMode::Orbit { .. } = self.mode {
+            return write!(
+                f,
+                "
+Orbit Controls:
+    {}\t- Hold and move to orbit around the focus
+    {}\t- Hold and move to pan the focus
+    Scroll\t- Zoom towards the focus
+    Right stick\t- Orbit around the focus
+    Triggers\t- Zoom in & out
+    {} + 0-9\t- Store bookmark
+    0-9\t- Recall bookmark",
+                self.bindings.describe(CameraAction::Orbit),
+                self.bindings.describe(CameraAction::Pan),
+                self.bindings.describe(CameraAction::StoreBookmark),
+            );
+        }
+
+        write!(
+            f,
+            "
+Freecam Controls:
+    Mouse\t- Move camera orientation
+    Scroll\t- Adjust movement speed
+    {}\t- Hold to grab cursor
+    {}\t- Toggle cursor grab
+    {} & {}\t- Fly forward & backwards
+    {} & {}\t- Fly sideways left & right
+    {} & {}\t- Fly up & down
+    {}\t- Fly faster while held
+    Left stick\t- Fly around
+    Right stick\t- Move camera orientation
+    Triggers\t- Fly up & down
+    {} + 0-9\t- Store bookmark
+    0-9\t- Recall bookmark
+    {}\t- Record path waypoint
+    {}\t- Play or stop path",
+            self.bindings.describe(CameraAction::GrabCursor),
+            self.bindings.describe(CameraAction::ToggleCursorGrab),
+            self.bindings.describe(CameraAction::Forward),
+            self.bindings.describe(CameraAction::Back),
+            self.bindings.describe(CameraAction::Left),
+            self.bindings.describe(CameraAction::Right),
+            self.bindings.describe(CameraAction::Up),
+            self.bindings.describe(CameraAction::Down),
+            self.bindings.describe(CameraAction::Run),
+            self.bindings.describe(CameraAction::StoreBookmark),
+            self.bindings.describe(CameraAction::RecordWaypoint),
+            self.bindings.describe(CameraAction::TogglePath),
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_camera_controller(
+    time: Res<Time>,
+    mut windows: Query<&mut Window>,
+    mut mouse_events: EventReader<MouseMotion>,
+    mut scroll_events: EventReader<MouseWheel>,
+    inputs: CameraInputs,
+    settings: Res<CameraControllerSettings>,
+    mut toggle_cursor_grab: Local<bool>,
+    mut mouse_cursor_grab: Local<bool>,
+    mut query: Query<(
+        &Camera,
+        Option<&Projection>,
+        &mut Transform,
+        &mut CameraController,
+    )>,
+    targets: Query<&GlobalTransform>,
+) {
+    let dt = time.delta_seconds();
+
+    if let Ok((camera, projection, mut transform, mut controller)) = query.get_single_mut() {
+        if !controller.initialized {
+            let (yaw, pitch, _roll) = transform.rotation.to_euler(EulerRot::YXZ);
+            controller.yaw = yaw;
+            controller.pitch = pitch;
+            controller.smoothed_yaw = yaw;
+            controller.smoothed_pitch = pitch;
+            controller.initialized = true;
+            info!("{}", *controller);
+        }
+        if !controller.enabled {
+            mouse_events.clear();
+            return;
+        }
+
+        let mut scroll = 0.0;
+        for scroll_event in scroll_events.read() {
+            let amount = match scroll_event.unit {
+                MouseScrollUnit::Line => scroll_event.y,
+                MouseScrollUnit::Pixel => scroll_event.y / 16.0,
+            };
+            scroll += amount;
+        }
+
+        match controller.mode {
+            CameraControllerMode::Fly => {
+                controller.walk_speed += scroll * controller.scroll_factor * controller.walk_speed;
+
+                // Handle key input
+                let mut axis_input = Vec3::ZERO;
+                if inputs.pressed(&controller.bindings, CameraAction::Forward) {
+                    axis_input.z += 1.0;
+                }
+                if inputs.pressed(&controller.bindings, CameraAction::Back) {
+                    axis_input.z -= 1.0;
+                }
+                if inputs.pressed(&controller.bindings, CameraAction::Right) {
+                    axis_input.x += 1.0;
+                }
+                if inputs.pressed(&controller.bindings, CameraAction::Left) {
+                    axis_input.x -= 1.0;
+                }
+                if inputs.pressed(&controller.bindings, CameraAction::Up) {
+                    axis_input.y += 1.0;
+                }
+                if inputs.pressed(&controller.bindings, CameraAction::Down) {
+                    axis_input.y -= 1.0;
+                }
+                let (move_stick, look_stick, triggers) = gamepad_input(&inputs, &controller);
+                axis_input += Vec3::new(move_stick.x, triggers, move_stick.y);
+
+                let mut cursor_grab_change = false;
+                if inputs.just_pressed(&controller.bindings, CameraAction::ToggleCursorGrab) {
+                    *toggle_cursor_grab = !*toggle_cursor_grab;
+                    cursor_grab_change = true;
+                }
+                if inputs.just_pressed(&controller.bindings, CameraAction::GrabCursor) {
+                    *mouse_cursor_grab = true;
+                    cursor_grab_change = true;
+                }
+                if inputs.just_released(&controller.bindings, CameraAction::GrabCursor) {
+                    *mouse_cursor_grab = false;
+                    cursor_grab_change = true;
+                }
+                let cursor_grab = *mouse_cursor_grab || *toggle_cursor_grab;
+
+                // Apply movement update
+                if axis_input != Vec3::ZERO {
+                    let max_speed = if inputs.pressed(&controller.bindings, CameraAction::Run) {
+                        controller.walk_speed * settings.run_speed_multiplier
+                    } else {
+                        controller.walk_speed
+                    };
+                    controller.velocity = approach(
+                        controller.velocity,
+                        axis_input.clamp_length_max(1.0) * max_speed,
+                        controller.acceleration,
+                        dt,
+                    );
+                } else {
+                    controller.velocity =
+                        approach(controller.velocity, Vec3::ZERO, controller.deceleration, dt);
+                    if controller.velocity.length_squared() < 1e-6 {
+                        controller.velocity = Vec3::ZERO;
+                    }
+                }
+                let forward = *transform.forward();
+                let right = *transform.right();
+                transform.translation += controller.velocity.x * dt * right
+                    + controller.velocity.y * dt * Vec3::Y
+                    + controller.velocity.z * dt * forward;
+
+                // Handle cursor grab
+                if cursor_grab_change {
+                    set_cursor_grab(&mut windows, cursor_grab, settings.cursor_grab_mode);
+                }
+
+                // Handle mouse input
+                let mut mouse_delta = Vec2::ZERO;
+                if cursor_grab {
+                    for mouse_event in mouse_events.read() {
+                        mouse_delta += mouse_event.delta;
+                    }
+                } else {
+                    mouse_events.clear();
+                }
+
+                // Apply look update
+                turn(&mut controller, &settings, mouse_delta * RADIANS_PER_DOT);
+                look(&mut controller, &settings, look_stick, dt);
+                if update_smoothed_rotation(&mut controller, dt) {
+                    transform.rotation = Quat::from_euler(
+                        EulerRot::ZYX,
+                        0.0,
+                        controller.smoothed_yaw,
+                        controller.smoothed_pitch,
+                    );
+                }
+            }
+            CameraControllerMode::Orbit {
+                mut focus,
+                mut distance,
+            } => {
+                // The cursor stays visible while orbiting
+                if *mouse_cursor_grab || *toggle_cursor_grab {
+                    *mouse_cursor_grab = false;
+                    *toggle_cursor_grab = false;
+                    set_cursor_grab(&mut windows, false, settings.cursor_grab_mode);
+                }
+                controller.velocity = Vec3::ZERO;
+
+                let mut mouse_delta = Vec2::ZERO;
+                for mouse_event in mouse_events.read() {
+                    mouse_delta += mouse_event.delta;
+                }
+
+                if inputs.pressed(&controller.bindings, CameraAction::Orbit) {
+                    turn(&mut controller, &settings, mouse_delta * RADIANS_PER_DOT);
+                } else if inputs.pressed(&controller.bindings, CameraAction::Pan) {
+                    // Move the focus so that it follows the cursor
+                    let world_per_pixel = match (projection, camera.logical_viewport_size()) {
+                        (Some(Projection::Perspective(perspective)), Some(viewport)) => {
+                            2.0 * distance * (perspective.fov / 2.0).tan() / viewport.y
+                        }
+                        (Some(Projection::Orthographic(orthographic)), Some(viewport)) => {
+                            orthographic.area.height() / viewport.y
+                        }
+                        _ => distance * RADIANS_PER_DOT,
+                    };
+                    let right = *transform.right();
+                    let up = *transform.up();
+                    focus += (up * mouse_delta.y - right * mouse_delta.x) * world_per_pixel;
+                }
+
+                let (_, look_stick, triggers) = gamepad_input(&inputs, &controller);
+                look(&mut controller, &settings, look_stick, dt);
+
+                // The right trigger zooms in, like scrolling up
+                let zoom = scroll * controller.scroll_factor + triggers * dt;
+                distance = (distance * (1.0 - zoom)).max(1e-3);
+
+                update_smoothed_rotation(&mut controller, dt);
+                transform.rotation = Quat::from_euler(
+                    EulerRot::ZYX,
+                    0.0,
+                    controller.smoothed_yaw,
+                    controller.smoothed_pitch,
+                );
+                transform.translation = focus + transform.rotation * Vec3::Z * distance;
+                controller.mode = CameraControllerMode::Orbit { focus, distance };
+            }
+            CameraControllerMode::Follow {
+                target,
+                offset,
+                lock_rotation,
+            } => {
+                mouse_events.clear();
+                controller.velocity = Vec3::ZERO;
+
+                let Ok(target) = targets.get(target) else {
+                    return;
+                };
+                let target = target.translation();
+
+                transform.translation = approach(
+                    transform.translation,
+                    target + offset,
+                    controller.follow_smoothing,
+                    dt,
+                );
+
+                let direction = target - transform.translation;
+                if !lock_rotation && direction.length_squared() > 1e-6 {
+                    let direction = direction.normalize();
+                    let yaw = (-direction.x).atan2(-direction.z);
+                    // Turn the short way around
+                    let turn = (yaw - controller.smoothed_yaw + PI).rem_euclid(TAU) - PI;
+                    controller.yaw = controller.smoothed_yaw + turn;
+                    controller.pitch = direction.y.clamp(-1.0, 1.0).asin();
+                }
+                if update_smoothed_rotation(&mut controller, dt) {
+                    transform.rotation = Quat::from_euler(
+                        EulerRot::ZYX,
+                        0.0,
+                        controller.smoothed_yaw,
+                        controller.smoothed_pitch,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Returns the shaped positions of the left and right sticks, and the right trigger minus the
+/// left trigger.
+fn gamepad_input(inputs: &CameraInputs, controller: &CameraController) -> (Vec2, Vec2, f32) {
+    let shape = |input: Vec2| {
+        let length = input.length();
+        let dead_zone = controller.gamepad_dead_zone.clamp(0.0, 0.99);
+        if length <= dead_zone {
+            return Vec2::ZERO;
+        }
+        let shaped = ((length - dead_zone) / (1.0 - dead_zone))
+            .min(1.0)
+            .powf(controller.gamepad_response_curve);
+        input * (shaped / length)
+    };
+    let move_stick =
+        shape(inputs.gamepad_stick(GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY));
+    let look_stick =
+        shape(inputs.gamepad_stick(GamepadAxisType::RightStickX, GamepadAxisType::RightStickY));
+    let triggers = shape(Vec2::new(
+        inputs.gamepad_trigger(GamepadButtonType::RightTrigger2)
+            - inputs.gamepad_trigger(GamepadButtonType::LeftTrigger2),
+        0.0,
+    ))
+    .x;
+    (move_stick, look_stick, triggers)
+}
+
+/// Turns the target orientation of the camera with a gamepad stick.
+fn look(
+    controller: &mut CameraController,
+    settings: &CameraControllerSettings,
+    stick: Vec2,
+    dt: f32,
+) {
+    // Pushing the stick up looks up, while moving the mouse up has a negative delta
+    let delta = Vec2::new(stick.x, -stick.y) * controller.gamepad_look_speed * dt;
+    turn(controller, settings, delta);
+}
+
+/// Turns the target orientation of the camera by `delta` radians, scaled by the mouse
+/// sensitivity, where positive values turn to the right and down.
+fn turn(controller: &mut CameraController, settings: &CameraControllerSettings, delta: Vec2) {
+    if delta == Vec2::ZERO {
+        return;
+    }
+    let mut delta = delta * settings.mouse_sensitivity;
+    if settings.invert_y {
+        delta.y = -delta.y;
+    }
+    controller.yaw -= delta.x;
+    controller.pitch = (controller.pitch - delta.y).clamp(-FRAC_PI_2, FRAC_PI_2);
+}
+
+/// Moves `current` towards `target`, closing `1 - exp(-rate * dt)` of the difference.
+fn approach(current: Vec3, target: Vec3, rate: f32, dt: f32) -> Vec3 {
+    if rate.is_infinite() {
+        return target;
+    }
+    current.lerp(target, 1.0 - (-rate * dt).exp())
+}
+
+/// Moves the smoothed yaw and pitch of the controller towards their targets with a critically
+/// damped spring, returning whether they changed.
+fn update_smoothed_rotation(controller: &mut CameraController, dt: f32) -> bool {
+    let target = Vec2::new(controller.yaw, controller.pitch);
+    let current = Vec2::new(controller.smoothed_yaw, controller.smoothed_pitch);
+    if current == target {
+        controller.rotation_velocity = Vec2::ZERO;
+        return false;
+    }
+
+    let (mut smoothed, mut velocity) = (target, Vec2::ZERO);
+    if controller.rotation_smooth_time > 0.0 {
+        // See "Critically Damped Ease-In/Ease-Out Smoothing" in Game Programming Gems 4
+        let omega = 2.0 / controller.rotation_smooth_time;
+        let x = omega * dt;
+        let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+        let change = current - target;
+        let temp = (controller.rotation_velocity + omega * change) * dt;
+        velocity = (controller.rotation_velocity - omega * temp) * decay;
+        smoothed = target + (change + temp) * decay;
+
+        // Snap to the target once the remaining motion is imperceptible
+        if smoothed.distance_squared(target) < 1e-10 && velocity.length_squared() < 1e-8 {
+            (smoothed, velocity) = (target, Vec2::ZERO);
+        }
+    }
+
+    controller.smoothed_yaw = smoothed.x;
+    controller.smoothed_pitch = smoothed.y;
+    controller.rotation_velocity = velocity;
+    true
+}
+
+fn set_cursor_grab(windows: &mut Query<&mut Window>, grab: bool, grab_mode: CursorGrabMode) {
+    for mut window in windows {
+        if grab {
+            if !window.focused {
+                continue;
+            }
+
+            window.cursor.grab_mode = grab_mode;
+            window.cursor.visible = false;
+        } else {
+            window.cursor.grab_mode = CursorGrabMode::None;
+            window.cursor.visible = true;
+        }
+    }
+}
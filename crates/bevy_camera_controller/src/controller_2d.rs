@@ -0,0 +1,157 @@
+use bevy_ecs::{
+    component::Component,
+    event::EventReader,
+    query::With,
+    reflect::ReflectComponent,
+    system::{Local, Query, Res},
+};
+use bevy_input::{
+    mouse::{MouseButton, MouseScrollUnit, MouseWheel},
+    ButtonInput,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::{Camera, OrthographicProjection};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_window::{PrimaryWindow, Window};
+
+/// A pan and zoom camera controller for 2D cameras.
+///
+/// Add this [`Component`] to an entity with a [`Camera`] and an [`OrthographicProjection`], such
+/// as a `Camera2dBundle`, to pan the view by dragging the mouse and to zoom towards the cursor by
+/// scrolling. This requires the [`CameraControllerPlugin`](crate::CameraControllerPlugin).
+///
+/// The cursor is read from the primary window.
+///
+/// # Example
+/// ```
+/// # use bevy_camera_controller::CameraController2d;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_render::{camera::OrthographicProjection, prelude::*};
+/// # use bevy_transform::prelude::*;
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         Camera::default(),
+///         OrthographicProjection::default(),
+///         TransformBundle::default(),
+///         CameraController2d {
+///             // Keep the view on a 4000 by 3000 map
+///             bounds: Some(Rect::new(0., 0., 4000., 3000.)),
+///             ..Default::default()
+///         },
+///     ));
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct CameraController2d {
+    /// Whether the controller responds to input.
+    pub enabled: bool,
+    /// The mouse button to hold to drag the view around.
+    pub mouse_key_pan: MouseButton,
+    /// How much scrolling a line changes the [`scale`](OrthographicProjection::scale) of the
+    /// projection, relative to its current value.
+    pub zoom_factor: f32,
+    /// The smallest [`scale`](OrthographicProjection::scale) the projection can be zoomed in to.
+    pub min_scale: f32,
+    /// The largest [`scale`](OrthographicProjection::scale) the projection can be zoomed out to.
+    pub max_scale: f32,
+    /// The area of the world the view is kept in, if any.
+    ///
+    /// When the view is larger than the bounds along an axis, it is centered on them instead.
+    pub bounds: Option<Rect>,
+}
+
+impl Default for CameraController2d {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mouse_key_pan: MouseButton::Left,
+            zoom_factor: 0.1,
+            min_scale: 0.05,
+            max_scale: 20.0,
+            bounds: None,
+        }
+    }
+}
+
+pub(crate) fn run_camera_controller_2d(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    mut last_cursor_position: Local<Option<Vec2>>,
+    mut query: Query<(
+        &Camera,
+        &GlobalTransform,
+        &mut Transform,
+        &mut OrthographicProjection,
+        &CameraController2d,
+    )>,
+) {
+    let cursor_position = windows.get_single().ok().and_then(Window::cursor_position);
+    let last_cursor_position = std::mem::replace(&mut *last_cursor_position, cursor_position);
+
+    let Ok((camera, global_transform, mut transform, mut projection, controller)) =
+        query.get_single_mut()
+    else {
+        return;
+    };
+    if !controller.enabled {
+        scroll_events.clear();
+        return;
+    }
+
+    let mut scroll = 0.0;
+    for scroll_event in scroll_events.read() {
+        let amount = match scroll_event.unit {
+            MouseScrollUnit::Line => scroll_event.y,
+            MouseScrollUnit::Pixel => scroll_event.y / 16.0,
+        };
+        scroll += amount;
+    }
+
+    let to_world = |position| camera.viewport_to_world_2d(global_transform, position);
+    let camera_position = global_transform.translation().truncate();
+
+    // Keep the point under the cursor where it was when the drag started
+    if mouse_button_input.pressed(controller.mouse_key_pan) {
+        if let (Some(last), Some(current)) = (
+            last_cursor_position.and_then(to_world),
+            cursor_position.and_then(to_world),
+        ) {
+            transform.translation += (last - current).extend(0.0);
+        }
+    }
+
+    // Scale the view around the point under the cursor, or around the center without one
+    let old_scale = projection.scale;
+    let new_scale = (old_scale * (1.0 - scroll * controller.zoom_factor))
+        .clamp(controller.min_scale, controller.max_scale);
+    let zoom = new_scale / old_scale;
+    if zoom != 1.0 {
+        let offset = cursor_position
+            .and_then(to_world)
+            .map_or(Vec2::ZERO, |cursor| cursor - camera_position);
+        transform.translation += (offset * (1.0 - zoom)).extend(0.0);
+        projection.scale = new_scale;
+    }
+
+    if let Some(bounds) = controller.bounds {
+        // The area is only updated for the new scale when the camera is updated
+        let area = Rect {
+            min: projection.area.min * zoom,
+            max: projection.area.max * zoom,
+        };
+        let lower = bounds.min - area.min;
+        let upper = bounds.max - area.max;
+        let translation = transform.translation.truncate();
+        let clamped = Vec2::select(
+            lower.cmple(upper),
+            translation.clamp(lower, upper.max(lower)),
+            (lower + upper) / 2.0,
+        );
+        transform.translation = clamped.extend(transform.translation.z);
+    }
+}
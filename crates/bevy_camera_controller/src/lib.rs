@@ -0,0 +1,96 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! This crate provides camera controllers for the [Bevy game engine](https://bevyengine.org),
+//! to navigate scenes while prototyping, debugging or viewing assets.
+//!
+//! Add the [`CameraControllerPlugin`] to your app, and a [`CameraController`] to an entity with a
+//! [`Camera`](bevy_render::camera::Camera). For 2D cameras, use a [`CameraController2d`] instead.
+//!
+//! ```
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_camera_controller::prelude::*;
+//! # use bevy_render::prelude::*;
+//! # use bevy_transform::prelude::*;
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((
+//!         Camera::default(),
+//!         TransformBundle::default(),
+//!         CameraController::default(),
+//!     ));
+//! }
+//! # bevy_ecs::system::assert_is_system(setup);
+//! ```
+
+mod bindings;
+mod bookmarks;
+mod controller;
+mod controller_2d;
+mod path;
+mod settings;
+
+pub(crate) use bindings::CameraInputs;
+pub use bindings::{CameraAction, CameraBindings, CameraInput};
+pub use bookmarks::{CameraBookmark, CameraBookmarks};
+pub use controller::*;
+pub use controller_2d::*;
+pub use path::{CameraPath, CameraPathEasing, CameraPathWaypointReached};
+pub use settings::CameraControllerSettings;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::schedule::{IntoSystemConfigs, SystemSet};
+
+/// The camera controller prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        CameraAction, CameraController, CameraController2d, CameraControllerMode,
+        CameraControllerPlugin, CameraControllerSettings,
+    };
+}
+
+/// A [`Plugin`] that moves cameras with a [`CameraController`] or [`CameraController2d`]
+/// component based on user input.
+pub struct CameraControllerPlugin;
+
+impl Plugin for CameraControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CameraController>()
+            .register_type::<CameraController2d>()
+            .register_type::<CameraBookmarks>()
+            .register_type::<CameraPath>()
+            .register_type::<CameraControllerSettings>()
+            .init_resource::<CameraBookmarks>()
+            .init_resource::<CameraControllerSettings>()
+            .add_event::<CameraPathWaypointReached>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        controller::run_camera_controller,
+                        // Transitions to bookmarks and camera paths override the controller
+                        bookmarks::run_camera_bookmarks,
+                        path::run_camera_path,
+                    )
+                        .chain(),
+                    controller_2d::run_camera_controller_2d,
+                )
+                    .in_set(CameraControllerSystem),
+            );
+    }
+}
+
+/// The [`SystemSet`] in which cameras with a [`CameraController`] or [`CameraController2d`] are
+/// moved.
+///
+/// Systems that need to read the camera transform after it was updated for the frame should be
+/// ordered after this set.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct CameraControllerSystem;
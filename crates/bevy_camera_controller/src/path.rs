@@ -0,0 +1,400 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventWriter},
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_log::info;
+use bevy_math::{
+    cubic_splines::{CubicCardinalSpline, CubicGenerator},
+    EulerRot, Vec2, Vec3,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+use crate::{CameraAction, CameraController, CameraControllerMode, CameraInputs};
+
+/// How the progress along a [`CameraPath`] changes over time.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Default)]
+pub enum CameraPathEasing {
+    /// Move at a constant pace.
+    Linear,
+    /// Start slowly.
+    EaseIn,
+    /// End slowly.
+    EaseOut,
+    /// Start and end slowly.
+    #[default]
+    EaseInOut,
+}
+
+impl CameraPathEasing {
+    /// Maps the fraction of the duration that passed to the fraction of the path that was covered.
+    pub fn ease(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A path of waypoints a camera with a [`CameraController`] can fly along, for cinematic shots,
+/// captures and automated demo recordings.
+///
+/// The camera moves along a Catmull-Rom spline through the waypoints, and turns from the rotation
+/// of one waypoint to the next. While playing, the path overrides the controller. When playback
+/// ends, the controller continues in fly mode from the end of the path.
+///
+/// Waypoints can be recorded from the current viewpoint with [`CameraAction::RecordWaypoint`],
+/// which adds this component if needed, and playback can be toggled with
+/// [`CameraAction::TogglePath`]. A [`CameraPathWaypointReached`] event is sent whenever the
+/// camera passes a waypoint.
+///
+/// # Example
+/// ```
+/// # use bevy_camera_controller::{CameraController, CameraPath};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_render::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         Camera::default(),
+///         TransformBundle::default(),
+///         CameraController::default(),
+///         CameraPath {
+///             waypoints: vec![
+///                 Transform::from_xyz(0., 2., 10.).looking_at(Vec3::ZERO, Vec3::Y),
+///                 Transform::from_xyz(10., 4., 0.).looking_at(Vec3::ZERO, Vec3::Y),
+///                 Transform::from_xyz(0., 6., -10.).looking_at(Vec3::ZERO, Vec3::Y),
+///             ],
+///             duration: 8.,
+///             ..Default::default()
+///         }
+///         .playing(),
+///     ));
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct CameraPath {
+    /// The viewpoints the camera passes through, in order.
+    pub waypoints: Vec<Transform>,
+    /// The time in seconds it takes to fly along the whole path.
+    pub duration: f32,
+    /// How the progress along the path changes over time.
+    pub easing: CameraPathEasing,
+    /// Whether to start over from the first waypoint when the end of the path is reached.
+    pub looping: bool,
+    /// Whether the camera is currently flying along the path.
+    pub playing: bool,
+    /// The time in seconds since playback started.
+    pub elapsed: f32,
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        Self {
+            waypoints: Vec::new(),
+            duration: 10.0,
+            easing: CameraPathEasing::EaseInOut,
+            looping: false,
+            playing: false,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl CameraPath {
+    /// Starts playback from the first waypoint.
+    pub fn play(&mut self) {
+        self.playing = true;
+        self.elapsed = 0.0;
+    }
+
+    /// Builder-style version of [`play`](Self::play).
+    pub fn playing(mut self) -> Self {
+        self.play();
+        self
+    }
+
+    /// Returns the transform of the camera after `elapsed` seconds of playback, or `None` if
+    /// the path has fewer than two waypoints.
+    pub fn sample(&self, elapsed: f32) -> Option<Transform> {
+        let segments = self.waypoints.len().checked_sub(1).filter(|&n| n > 0)?;
+        let progress = self.progress(elapsed);
+
+        let positions: Vec<Vec3> = self.waypoints.iter().map(|w| w.translation).collect();
+        let curve = CubicCardinalSpline::new_catmull_rom(positions).to_curve();
+
+        let segment = (progress as usize).min(segments - 1);
+        let (from, to) = (&self.waypoints[segment], &self.waypoints[segment + 1]);
+        let t = progress - segment as f32;
+
+        Some(Transform {
+            translation: curve.position(progress),
+            rotation: from.rotation.slerp(to.rotation, t),
+            scale: from.scale.lerp(to.scale, t),
+        })
+    }
+
+    /// Returns the position along the path after `elapsed` seconds, where `n` is waypoint `n`.
+    fn progress(&self, elapsed: f32) -> f32 {
+        let segments = self.waypoints.len().saturating_sub(1) as f32;
+        let t = if self.duration > 0.0 {
+            (elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.easing.ease(t) * segments
+    }
+
+    /// Advances playback by `delta` seconds, and returns whether it wrapped around to the start.
+    ///
+    /// Playback stops at the end of the path, unless the path is looping.
+    fn advance(&mut self, delta: f32) -> bool {
+        let mut elapsed = self.elapsed + delta;
+        let mut wrapped = false;
+        if elapsed >= self.duration {
+            if self.looping && self.duration > 0.0 {
+                elapsed %= self.duration;
+                wrapped = true;
+            } else {
+                elapsed = self.duration;
+                self.playing = false;
+            }
+        }
+        self.elapsed = elapsed;
+        wrapped
+    }
+
+    /// Returns the waypoints passed when playback advanced from `previous` to `elapsed` seconds.
+    fn passed_waypoints(&self, previous: f32, elapsed: f32, wrapped: bool) -> Vec<usize> {
+        let Some(last) = self.waypoints.len().checked_sub(1) else {
+            return Vec::new();
+        };
+        let from = self.progress(previous);
+        let to = (self.progress(elapsed).floor() as usize).min(last);
+
+        let mut passed = Vec::new();
+        if previous == 0.0 {
+            passed.push(0);
+        }
+        let first = from.floor() as usize + 1;
+        if wrapped {
+            passed.extend(first..=last);
+            passed.extend(0..=to);
+        } else {
+            passed.extend(first..=to);
+        }
+        passed
+    }
+}
+
+/// An [`Event`] sent when a camera flying along a [`CameraPath`] passes a waypoint.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CameraPathWaypointReached {
+    /// The camera entity.
+    pub camera: Entity,
+    /// The index of the waypoint in [`CameraPath::waypoints`].
+    pub waypoint: usize,
+}
+
+pub(crate) fn run_camera_path(
+    mut commands: Commands,
+    time: Res<Time>,
+    inputs: CameraInputs,
+    mut waypoint_events: EventWriter<CameraPathWaypointReached>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut CameraController,
+        Option<&mut CameraPath>,
+    )>,
+) {
+    let Ok((camera, mut transform, mut controller, path)) = query.get_single_mut() else {
+        return;
+    };
+
+    let record = controller.enabled
+        && inputs.just_pressed(&controller.bindings, CameraAction::RecordWaypoint);
+    let toggle =
+        controller.enabled && inputs.just_pressed(&controller.bindings, CameraAction::TogglePath);
+
+    let Some(mut path) = path else {
+        if record {
+            commands.entity(camera).insert(CameraPath {
+                waypoints: vec![*transform],
+                ..Default::default()
+            });
+            info!("Recorded camera path waypoint 0");
+        }
+        return;
+    };
+
+    if record {
+        info!("Recorded camera path waypoint {}", path.waypoints.len());
+        path.waypoints.push(*transform);
+    }
+    if toggle {
+        if path.playing {
+            path.playing = false;
+            continue_with_controller(&transform, &mut controller);
+        } else if path.waypoints.len() >= 2 {
+            path.play();
+        }
+    }
+
+    if !path.playing {
+        return;
+    }
+    if path.waypoints.len() < 2 {
+        path.playing = false;
+        return;
+    }
+
+    let previous = path.elapsed;
+    let wrapped = path.advance(time.delta_seconds());
+    let elapsed = path.elapsed;
+
+    // Send events for the waypoints passed since the last frame
+    for waypoint in path.passed_waypoints(previous, elapsed, wrapped) {
+        waypoint_events.send(CameraPathWaypointReached { camera, waypoint });
+    }
+
+    if let Some(sampled) = path.sample(elapsed) {
+        *transform = sampled;
+    }
+
+    if !path.playing {
+        continue_with_controller(&transform, &mut controller);
+    }
+}
+
+/// Lets the controller continue in fly mode from where playback stopped.
+fn continue_with_controller(transform: &Transform, controller: &mut CameraController) {
+    let (yaw, pitch, _roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    controller.mode = CameraControllerMode::Fly;
+    controller.yaw = yaw;
+    controller.pitch = pitch;
+    controller.smoothed_yaw = yaw;
+    controller.smoothed_pitch = pitch;
+    controller.rotation_velocity = Vec2::ZERO;
+    controller.velocity = Vec3::ZERO;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Quat;
+
+    fn linear_path(waypoints: &[Vec3]) -> CameraPath {
+        CameraPath {
+            waypoints: waypoints
+                .iter()
+                .map(|&translation| Transform::from_translation(translation))
+                .collect(),
+            duration: 4.0,
+            easing: CameraPathEasing::Linear,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sample_needs_two_waypoints() {
+        assert_eq!(linear_path(&[]).sample(1.0), None);
+        assert_eq!(linear_path(&[Vec3::X]).sample(1.0), None);
+        assert!(linear_path(&[Vec3::ZERO, Vec3::X]).sample(1.0).is_some());
+    }
+
+    #[test]
+    fn sample_interpolates_between_waypoints() {
+        let mut path = linear_path(&[Vec3::ZERO, Vec3::X, Vec3::new(2.0, 0.0, 0.0)]);
+        path.waypoints[1].rotation = Quat::from_rotation_y(1.0);
+        path.waypoints[1].scale = Vec3::splat(3.0);
+
+        // Each of the two segments takes 2 seconds
+        for (elapsed, expected) in [(0.0, 0), (2.0, 1), (4.0, 2)] {
+            let sampled = path.sample(elapsed).unwrap();
+            assert!(sampled
+                .translation
+                .abs_diff_eq(path.waypoints[expected].translation, 1e-5));
+        }
+        let sampled = path.sample(1.0).unwrap();
+        assert!(sampled
+            .translation
+            .abs_diff_eq(Vec3::new(0.5, 0.0, 0.0), 1e-5));
+        assert!(sampled
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(0.5), 1e-5));
+        assert!(sampled.scale.abs_diff_eq(Vec3::splat(2.0), 1e-5));
+
+        // Sampling past either end stays at that end
+        assert_eq!(path.sample(-1.0), path.sample(0.0));
+        assert_eq!(path.sample(10.0), path.sample(4.0));
+
+        // A path without a duration is always at its end
+        path.duration = 0.0;
+        let sampled = path.sample(0.0).unwrap();
+        assert!(sampled
+            .translation
+            .abs_diff_eq(path.waypoints[2].translation, 1e-5));
+        assert!(sampled.rotation.abs_diff_eq(Quat::IDENTITY, 1e-5));
+    }
+
+    #[test]
+    fn easing_changes_progress() {
+        let mut path = linear_path(&[Vec3::ZERO, Vec3::X, Vec3::new(2.0, 0.0, 0.0)]);
+        path.easing = CameraPathEasing::EaseIn;
+        assert_eq!(path.progress(2.0), 0.5);
+        path.easing = CameraPathEasing::EaseOut;
+        assert_eq!(path.progress(2.0), 1.5);
+        path.easing = CameraPathEasing::EaseInOut;
+        assert_eq!(path.progress(2.0), 1.0);
+        assert_eq!(path.progress(4.0), 2.0);
+    }
+
+    #[test]
+    fn playback_stops_at_the_end() {
+        let mut path = linear_path(&[Vec3::ZERO, Vec3::X, Vec3::Y]).playing();
+
+        assert!(!path.advance(3.0));
+        assert!(path.playing);
+        assert!(!path.advance(3.0));
+        assert!(!path.playing);
+        assert_eq!(path.elapsed, 4.0);
+        assert_eq!(path.passed_waypoints(3.0, 4.0, false), [2]);
+    }
+
+    #[test]
+    fn looping_playback_wraps_around() {
+        let mut path = linear_path(&[Vec3::ZERO, Vec3::X, Vec3::Y]).playing();
+        path.looping = true;
+
+        path.advance(3.0);
+        assert!(path.advance(2.0));
+        assert!(path.playing);
+        assert_eq!(path.elapsed, 1.0);
+        // The end of the path and the first waypoint are passed when wrapping around
+        assert_eq!(path.passed_waypoints(3.0, 1.0, true), [2, 0]);
+    }
+
+    #[test]
+    fn waypoints_are_passed_in_order() {
+        let path = linear_path(&[Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z, Vec3::ONE]);
+
+        // Each segment takes 1 second, and the first waypoint is passed when playback starts
+        assert_eq!(path.passed_waypoints(0.0, 0.5, false), [0]);
+        assert!(path.passed_waypoints(0.5, 0.9, false).is_empty());
+        assert_eq!(path.passed_waypoints(0.9, 3.5, false), [1, 2, 3]);
+        assert!(linear_path(&[])
+            .passed_waypoints(0.0, 1.0, false)
+            .is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+use bevy_ecs::{reflect::ReflectResource, system::Resource};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_window::CursorGrabMode;
+
+/// User preferences shared by all cameras with a [`CameraController`](crate::CameraController).
+///
+/// These are the settings players usually expect in an options menu, so they are kept in a
+/// [`Resource`] instead of on the controller itself. The keys that grab and release the cursor are
+/// configured through the [`CameraBindings`](crate::CameraBindings) of the controller.
+///
+/// # Example
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_camera_controller::{CameraControllerPlugin, CameraControllerSettings};
+/// App::new()
+///     .add_plugins(CameraControllerPlugin)
+///     .insert_resource(CameraControllerSettings {
+///         mouse_sensitivity: 0.5,
+///         invert_y: true,
+///         ..Default::default()
+///     });
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct CameraControllerSettings {
+    /// A multiplier for the rotation caused by mouse motion and the right gamepad stick, see
+    /// [`RADIANS_PER_DOT`](crate::RADIANS_PER_DOT).
+    pub mouse_sensitivity: f32,
+    /// Whether moving the mouse or the right stick up makes the camera look down.
+    pub invert_y: bool,
+    /// The speed while [`CameraAction::Run`](crate::CameraAction::Run) is held, relative to the
+    /// [`walk_speed`](crate::CameraController::walk_speed).
+    pub run_speed_multiplier: f32,
+    /// How the cursor is held in place while it is grabbed.
+    ///
+    /// [`CursorGrabMode::Locked`] is not supported on every platform, in which case
+    /// [`CursorGrabMode::Confined`] can be used instead.
+    pub cursor_grab_mode: CursorGrabMode,
+}
+
+impl Default for CameraControllerSettings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 1.0,
+            invert_y: false,
+            run_speed_multiplier: 3.0,
+            cursor_grab_mode: CursorGrabMode::Locked,
+        }
+    }
+}
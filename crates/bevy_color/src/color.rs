@@ -1,6 +1,6 @@
 use crate::{
-    color_difference::EuclideanDistance, Alpha, Hsla, Hsva, Hue, Hwba, Laba, Lcha, LinearRgba,
-    Luminance, Mix, Oklaba, Oklcha, Srgba, StandardColor, Xyza,
+    color_difference::EuclideanDistance, Alpha, HexColorError, Hsla, Hsva, Hue, Hwba, Laba, Lcha,
+    LinearRgba, Luminance, Mix, Oklaba, Oklcha, Srgba, StandardColor, Xyza,
 };
 use bevy_reflect::prelude::*;
 
@@ -77,6 +77,69 @@ impl Color {
         (*self).into()
     }
 
+    /// Return the color as an sRGB color with alpha.
+    pub fn to_srgba(&self) -> Srgba {
+        (*self).into()
+    }
+
+    /// Return the color as an HSL color with alpha.
+    pub fn to_hsla(&self) -> Hsla {
+        (*self).into()
+    }
+
+    /// Return the color as an HSV color with alpha.
+    pub fn to_hsva(&self) -> Hsva {
+        (*self).into()
+    }
+
+    /// Return the color as an HWB color with alpha.
+    pub fn to_hwba(&self) -> Hwba {
+        (*self).into()
+    }
+
+    /// Return the color as a LAB color with alpha.
+    pub fn to_laba(&self) -> Laba {
+        (*self).into()
+    }
+
+    /// Return the color as an LCH color with alpha.
+    pub fn to_lcha(&self) -> Lcha {
+        (*self).into()
+    }
+
+    /// Return the color as an Oklab color with alpha.
+    pub fn to_oklaba(&self) -> Oklaba {
+        (*self).into()
+    }
+
+    /// Return the color as an Oklch color with alpha.
+    pub fn to_oklcha(&self) -> Oklcha {
+        (*self).into()
+    }
+
+    /// Return the color as an XYZ color with alpha.
+    pub fn to_xyza(&self) -> Xyza {
+        (*self).into()
+    }
+
+    /// Creates a new [`Color`] in the sRGB color space from a CSS-style hexadecimal string, see
+    /// [`Srgba::hex`].
+    ///
+    /// ```
+    /// # use bevy_color::Color;
+    /// let fuchsia = Color::hex("#FF00FF").unwrap();
+    /// assert_eq!(fuchsia.to_hex(), "#FF00FF");
+    /// ```
+    pub fn hex<T: AsRef<str>>(hex: T) -> Result<Self, HexColorError> {
+        Srgba::hex(hex).map(Self::Srgba)
+    }
+
+    /// Converts the color to sRGB, and returns it in CSS-style hexadecimal notation, see
+    /// [`Srgba::to_hex`].
+    pub fn to_hex(&self) -> String {
+        self.to_srgba().to_hex()
+    }
+
     #[deprecated = "Use `Color::srgba` instead"]
     /// Creates a new [`Color`] object storing a [`Srgba`] color.
     pub const fn rgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
@@ -804,3 +867,85 @@ impl EuclideanDistance for Color {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn test_conversions() {
+        for color in TEST_COLORS.iter() {
+            let srgba = Color::from(color.rgb);
+            let hsla = srgba.to_hsla();
+            assert_approx_eq!(hsla.saturation, color.hsl.saturation, 0.001);
+            assert_approx_eq!(hsla.lightness, color.hsl.lightness, 0.001);
+            assert!(
+                srgba.to_oklaba().distance(&color.oklab) < 0.0001,
+                "{}: {:?} != {:?}",
+                color.name,
+                srgba.to_oklaba(),
+                color.oklab
+            );
+            assert!(
+                Color::from(color.linear_rgb)
+                    .to_srgba()
+                    .distance(&color.rgb)
+                    < 0.0001,
+                "{}: {:?} != {:?}",
+                color.name,
+                Color::from(color.linear_rgb).to_srgba(),
+                color.rgb
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for color in TEST_COLORS.iter() {
+            let srgba = Color::from(color.rgb);
+            let round_trips = [
+                Color::from(srgba.linear()),
+                Color::from(srgba.to_hsla()),
+                Color::from(srgba.to_hsva()),
+                Color::from(srgba.to_hwba()),
+                Color::from(srgba.to_laba()),
+                Color::from(srgba.to_lcha()),
+                Color::from(srgba.to_oklaba()),
+                Color::from(srgba.to_oklcha()),
+                Color::from(srgba.to_xyza()),
+            ];
+            for round_trip in round_trips {
+                // Converting there and back accumulates the error of both conversions
+                assert!(
+                    round_trip.to_srgba().distance(&color.rgb) < 0.001,
+                    "{}: {:?} != {:?}",
+                    color.name,
+                    round_trip,
+                    color.rgb
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Color::hex("#FF00FF"), Ok(Color::srgb(1.0, 0.0, 1.0)));
+        assert_eq!(Color::hex("FF00FF7F").unwrap().alpha(), 127.0 / 255.0);
+        assert_eq!(Color::hex("#ff"), Err(HexColorError::Length));
+
+        for color in TEST_COLORS.iter() {
+            let hex = Color::from(color.rgb).to_hex();
+            let parsed = Color::hex(&hex).unwrap();
+            // Hex colors only have 8 bits per channel
+            assert!(
+                parsed.to_srgba().distance(&color.rgb) < 0.01,
+                "{}: {hex} != {:?}",
+                color.name,
+                color.rgb
+            );
+            assert_eq!(parsed.to_hex(), hex);
+        }
+        assert_eq!(Color::hsl(0.0, 1.0, 0.5).to_hex(), "#FF0000");
+    }
+}
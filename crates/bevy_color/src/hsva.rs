@@ -156,7 +156,12 @@ impl From<Hwba> for Hsva {
     ) -> Self {
         // Based on https://en.wikipedia.org/wiki/HWB_color_model#Conversion
         let value = 1. - blackness;
-        let saturation = 1. - (whiteness / value);
+        // Black has no saturation, and would divide by zero
+        let saturation = if value == 0. {
+            0.
+        } else {
+            1. - (whiteness / value)
+        };
 
         Hsva::new(hue, saturation, value, alpha)
     }
@@ -22,6 +22,7 @@ pub mod graph {
         Fxaa,
         Upscaling,
         ContrastAdaptiveSharpening,
+        FullscreenReadback,
         EndMainPassPostProcessing,
     }
 }
@@ -34,6 +34,7 @@ pub mod graph {
         Fxaa,
         Upscaling,
         ContrastAdaptiveSharpening,
+        FullscreenReadback,
         EndMainPassPostProcessing,
     }
 }
@@ -92,7 +93,7 @@ use bevy_render::{
     view::{ExtractedView, ViewDepthTexture, ViewTarget},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_utils::{tracing::warn, HashMap};
+use bevy_utils::{tracing::warn, HashMap, HashSet};
 
 use crate::{
     core_3d::main_transmissive_pass_3d_node::MainTransmissivePass3dNode,
@@ -103,8 +104,10 @@ use crate::{
     },
     dof::DepthOfFieldNode,
     prepass::{
-        node::PrepassNode, AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, MotionVectorPrepass,
-        NormalPrepass, Opaque3dPrepass, OpaqueNoLightmap3dBinKey, ViewPrepassTextures,
+        node::PrepassNode,
+        readback::{PrepassReadbackPlugin, ReadbackDepthPrepass, ReadbackNormalPrepass},
+        AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass,
+        Opaque3dPrepass, OpaqueNoLightmap3dBinKey, ViewPrepassTextures,
         MOTION_VECTOR_PREPASS_FORMAT, NORMAL_PREPASS_FORMAT,
     },
     skybox::SkyboxPlugin,
@@ -120,7 +123,11 @@ impl Plugin for Core3dPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera3d>()
             .register_type::<ScreenSpaceTransmissionQuality>()
-            .add_plugins((SkyboxPlugin, ExtractComponentPlugin::<Camera3d>::default()))
+            .add_plugins((
+                SkyboxPlugin,
+                PrepassReadbackPlugin,
+                ExtractComponentPlugin::<Camera3d>::default(),
+            ))
             .add_systems(PostUpdate, check_msaa);
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
@@ -800,15 +807,37 @@ pub fn prepare_prepass_textures(
         Has<NormalPrepass>,
         Has<MotionVectorPrepass>,
         Has<DeferredPrepass>,
+        Has<ReadbackDepthPrepass>,
+        Has<ReadbackNormalPrepass>,
     )>,
 ) {
+    // The textures are shared by the cameras of a target, so they can be read back if any of
+    // these cameras reads them back
+    let mut depth_readback_targets = HashSet::default();
+    let mut normal_readback_targets = HashSet::default();
+    for (_, camera, .., readback_depth, readback_normal) in &views_3d {
+        if readback_depth {
+            depth_readback_targets.insert(camera.target.clone());
+        }
+        if readback_normal {
+            normal_readback_targets.insert(camera.target.clone());
+        }
+    }
+
     let mut depth_textures = HashMap::default();
     let mut normal_textures = HashMap::default();
     let mut deferred_textures = HashMap::default();
     let mut deferred_lighting_id_textures = HashMap::default();
     let mut motion_vectors_textures = HashMap::default();
-    for (entity, camera, depth_prepass, normal_prepass, motion_vector_prepass, deferred_prepass) in
-        &views_3d
+    for (
+        entity,
+        camera,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        deferred_prepass,
+        ..,
+    ) in &views_3d
     {
         if !opaque_3d_prepass_phases.contains_key(&entity)
             && !alpha_mask_3d_prepass_phases.contains_key(&entity)
@@ -832,6 +861,13 @@ pub fn prepare_prepass_textures(
             depth_textures
                 .entry(camera.target.clone())
                 .or_insert_with(|| {
+                    let mut usage = TextureUsages::COPY_DST
+                        | TextureUsages::RENDER_ATTACHMENT
+                        | TextureUsages::TEXTURE_BINDING;
+                    if depth_readback_targets.contains(&camera.target) {
+                        // Required to read the depth back to the CPU
+                        usage |= TextureUsages::COPY_SRC;
+                    }
                     let descriptor = TextureDescriptor {
                         label: Some("prepass_depth_texture"),
                         size,
@@ -839,9 +875,7 @@ pub fn prepare_prepass_textures(
                         sample_count: msaa.samples(),
                         dimension: TextureDimension::D2,
                         format: CORE_3D_DEPTH_FORMAT,
-                        usage: TextureUsages::COPY_DST
-                            | TextureUsages::RENDER_ATTACHMENT
-                            | TextureUsages::TEXTURE_BINDING,
+                        usage,
                         view_formats: &[],
                     };
                     texture_cache.get(&render_device, descriptor)
@@ -853,6 +887,12 @@ pub fn prepare_prepass_textures(
             normal_textures
                 .entry(camera.target.clone())
                 .or_insert_with(|| {
+                    let mut usage =
+                        TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+                    if normal_readback_targets.contains(&camera.target) {
+                        // Required to read the normals back to the CPU
+                        usage |= TextureUsages::COPY_SRC;
+                    }
                     texture_cache.get(
                         &render_device,
                         TextureDescriptor {
@@ -862,8 +902,7 @@ pub fn prepare_prepass_textures(
                             sample_count: msaa.samples(),
                             dimension: TextureDimension::D2,
                             format: NORMAL_PREPASS_FORMAT,
-                            usage: TextureUsages::RENDER_ATTACHMENT
-                                | TextureUsages::TEXTURE_BINDING,
+                            usage,
                             view_formats: &[],
                         },
                     )
@@ -0,0 +1,330 @@
+//! Run a fullscreen shader over the output of a camera and read the result back to the CPU.
+//!
+//! This is a building block for effects that need to compute something from the rendered image,
+//! such as the average luminance of the scene or a histogram of its pixels, without writing a
+//! render graph node. The shader's result is written to a small texture, which is read back each
+//! frame and sent to the main world as a [`FullscreenReadbackComplete`] event.
+
+mod node;
+
+pub use node::FullscreenReadbackNode;
+
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, First, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_math::{URect, UVec2};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    gpu_readback::GpuReadbacks,
+    render_graph::{RenderGraphApp, ViewNodeRunner},
+    render_resource::{
+        binding_types::{sampler, texture_2d},
+        *,
+    },
+    renderer::RenderDevice,
+    texture::{CachedTexture, TextureCache},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::{default, warn_once};
+
+use crate::{
+    core_2d::graph::{Core2d, Node2d},
+    core_3d::graph::{Core3d, Node3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+
+/// Adds support for [`FullscreenReadback`].
+pub struct FullscreenReadbackPlugin;
+
+impl Plugin for FullscreenReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.add_plugins(ExtractComponentPlugin::<FullscreenReadback>::default())
+            .add_event::<FullscreenReadbackComplete>()
+            .insert_resource(FullscreenReadbackReceiver(rx))
+            .add_systems(First, send_fullscreen_readback_events);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(FullscreenReadbackSender(tx))
+            .init_resource::<SpecializedRenderPipelines<FullscreenReadbackPipeline>>()
+            .add_systems(
+                Render,
+                prepare_fullscreen_readbacks.in_set(RenderSet::PrepareResources),
+            )
+            .add_render_graph_node::<ViewNodeRunner<FullscreenReadbackNode>>(
+                Core3d,
+                Node3d::FullscreenReadback,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::EndMainPassPostProcessing,
+                    Node3d::FullscreenReadback,
+                    Node3d::Upscaling,
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<FullscreenReadbackNode>>(
+                Core2d,
+                Node2d::FullscreenReadback,
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    Node2d::EndMainPassPostProcessing,
+                    Node2d::FullscreenReadback,
+                    Node2d::Upscaling,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<FullscreenReadbackPipeline>();
+    }
+}
+
+/// Runs a fullscreen fragment shader over the output of a camera each frame, and reads the
+/// texture it renders to back to the CPU, which is sent to the main world as a
+/// [`FullscreenReadbackComplete`] event.
+///
+/// The shader runs after post processing, and before the output is upscaled to the render
+/// target. Its `fragment` entry point is called for each pixel of an
+/// [`output_size`](Self::output_size) texture, with the camera's output bound like this:
+///
+/// ```wgsl
+/// #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+///
+/// @group(0) @binding(0) var screen_texture: texture_2d<f32>;
+/// @group(0) @binding(1) var texture_sampler: sampler;
+///
+/// @fragment
+/// fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+///     return textureSample(screen_texture, texture_sampler, in.uv);
+/// }
+/// ```
+///
+/// The output texture is usually much smaller than the camera's output, so the shader reads
+/// several pixels of `screen_texture` for each pixel it writes, for example to average them.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::UVec2;
+/// # use bevy_render::{camera::Camera, render_resource::{Shader, TextureFormat}};
+/// # use bevy_core_pipeline::fullscreen_readback::*;
+/// #[derive(Resource)]
+/// struct LuminanceShader(Handle<Shader>);
+///
+/// fn read_back_luminance(
+///     mut commands: Commands,
+///     camera: Query<Entity, With<Camera>>,
+///     shader: Res<LuminanceShader>,
+/// ) {
+///     commands.entity(camera.single()).insert(FullscreenReadback {
+///         shader: shader.0.clone(),
+///         output_size: UVec2::ONE,
+///         output_format: TextureFormat::R32Float,
+///     });
+/// }
+///
+/// fn print_luminance(mut readbacks: EventReader<FullscreenReadbackComplete>) {
+///     for readback in readbacks.read() {
+///         let luminance = f32::from_ne_bytes(readback.data[0..4].try_into().unwrap());
+///         println!("The average luminance is {luminance}");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(read_back_luminance);
+/// # bevy_ecs::system::assert_is_system(print_luminance);
+/// ```
+#[derive(Component, ExtractComponent, Clone, Debug)]
+pub struct FullscreenReadback {
+    /// The shader run over the camera's output, which needs a `fragment` entry point.
+    pub shader: Handle<Shader>,
+    /// The size of the texture the shader renders to, in pixels.
+    pub output_size: UVec2,
+    /// The format of the texture the shader renders to.
+    ///
+    /// This needs to be a color format that can be rendered to and read back, such as
+    /// [`TextureFormat::R32Float`] or [`TextureFormat::Rgba8Unorm`].
+    pub output_format: TextureFormat,
+}
+
+/// Sent when the output of the shader of a camera with [`FullscreenReadback`] has been read back.
+#[derive(Event, Clone, Debug)]
+pub struct FullscreenReadbackComplete {
+    /// The camera the shader ran on.
+    pub entity: Entity,
+    /// The size of the texture the shader rendered to, in pixels.
+    pub size: UVec2,
+    /// The pixels of the texture, row by row, in the
+    /// [`output_format`](FullscreenReadback::output_format) of the camera's [`FullscreenReadback`].
+    pub data: Vec<u8>,
+}
+
+/// Receives the data read back in the render world.
+#[derive(Resource)]
+struct FullscreenReadbackReceiver(Receiver<FullscreenReadbackComplete>);
+
+/// Sends the data read back to the main world.
+#[derive(Resource)]
+struct FullscreenReadbackSender(Sender<FullscreenReadbackComplete>);
+
+fn send_fullscreen_readback_events(
+    receiver: Res<FullscreenReadbackReceiver>,
+    mut events: EventWriter<FullscreenReadbackComplete>,
+) {
+    while let Ok(readback) = receiver.0.try_recv() {
+        events.send(readback);
+    }
+}
+
+#[derive(Resource)]
+pub struct FullscreenReadbackPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for FullscreenReadbackPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "fullscreen_readback_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mipmap_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        FullscreenReadbackPipeline { layout, sampler }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct FullscreenReadbackPipelineKey {
+    shader: Handle<Shader>,
+    texture_format: TextureFormat,
+}
+
+impl SpecializedRenderPipeline for FullscreenReadbackPipeline {
+    type Key = FullscreenReadbackPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("fullscreen_readback".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: key.shader,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+/// The pipeline and output texture of a camera with [`FullscreenReadback`].
+#[derive(Component)]
+pub struct ViewFullscreenReadback {
+    pub pipeline_id: CachedRenderPipelineId,
+    pub output: CachedTexture,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_fullscreen_readbacks(
+    mut commands: Commands,
+    device: Res<RenderDevice>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<FullscreenReadbackPipeline>>,
+    fullscreen_readback_pipeline: Res<FullscreenReadbackPipeline>,
+    mut texture_cache: ResMut<TextureCache>,
+    mut readbacks: ResMut<GpuReadbacks>,
+    sender: Res<FullscreenReadbackSender>,
+    views: Query<(Entity, &FullscreenReadback)>,
+) {
+    for (entity, readback) in &views {
+        let size = readback.output_size.max(UVec2::ONE);
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &fullscreen_readback_pipeline,
+            FullscreenReadbackPipelineKey {
+                shader: readback.shader.clone(),
+                texture_format: readback.output_format,
+            },
+        );
+        let output = texture_cache.get(
+            &device,
+            TextureDescriptor {
+                label: Some("fullscreen_readback_output"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: readback.output_format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+                view_formats: &[],
+            },
+        );
+
+        // The texture is only rendered to once the shader has been compiled
+        if pipeline_cache.get_render_pipeline(pipeline_id).is_some() {
+            let sender = sender.0.clone();
+            let result = readbacks.for_entity(entity, |readbacks| {
+                readbacks.read_texture(
+                    &device,
+                    &output.texture,
+                    URect::from_corners(UVec2::ZERO, size),
+                    TextureAspect::All,
+                    move |result| match result {
+                        Ok(data) => {
+                            // The main world may have been dropped during shutdown
+                            let _ =
+                                sender.try_send(FullscreenReadbackComplete { entity, size, data });
+                        }
+                        Err(err) => {
+                            warn_once!(
+                                "Can't read back the fullscreen readback of {entity:?}: {err}"
+                            );
+                        }
+                    },
+                )
+            });
+            if let Err(err) = result {
+                warn_once!("Can't read back the fullscreen readback of {entity:?}: {err}");
+            }
+        }
+
+        commands.entity(entity).insert(ViewFullscreenReadback {
+            pipeline_id,
+            output,
+        });
+    }
+}
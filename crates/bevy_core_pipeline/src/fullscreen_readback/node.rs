@@ -0,0 +1,83 @@
+use std::sync::Mutex;
+
+use crate::fullscreen_readback::{FullscreenReadbackPipeline, ViewFullscreenReadback};
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryItem;
+use bevy_render::{
+    diagnostic::RecordDiagnostics,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        BindGroup, BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment,
+        RenderPassDescriptor, TextureViewId,
+    },
+    renderer::RenderContext,
+    view::ViewTarget,
+};
+
+/// Renders the shader of a [`FullscreenReadback`](super::FullscreenReadback) to its output
+/// texture, which is then read back.
+#[derive(Default)]
+pub struct FullscreenReadbackNode {
+    cached_texture_bind_group: Mutex<Option<(TextureViewId, BindGroup)>>,
+}
+
+impl ViewNode for FullscreenReadbackNode {
+    type ViewQuery = (&'static ViewTarget, &'static ViewFullscreenReadback);
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, readback): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let fullscreen_readback_pipeline = world.resource::<FullscreenReadbackPipeline>();
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(readback.pipeline_id) else {
+            return Ok(());
+        };
+
+        let source = target.main_texture_view();
+        let mut cached_bind_group = self.cached_texture_bind_group.lock().unwrap();
+        let bind_group = match &mut *cached_bind_group {
+            Some((id, bind_group)) if source.id() == *id => bind_group,
+            cached_bind_group => {
+                let bind_group = render_context.render_device().create_bind_group(
+                    None,
+                    &fullscreen_readback_pipeline.layout,
+                    &BindGroupEntries::sequential((source, &fullscreen_readback_pipeline.sampler)),
+                );
+
+                let (_, bind_group) = cached_bind_group.insert((source.id(), bind_group));
+                bind_group
+            }
+        };
+
+        let diagnostics = render_context.diagnostic_recorder();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("fullscreen_readback_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &readback.output.default_view,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        };
+
+        let mut render_pass = render_context
+            .command_encoder()
+            .begin_render_pass(&pass_descriptor);
+        let pass_span = diagnostics.pass_span(&mut render_pass, "fullscreen_readback");
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        pass_span.end(&mut render_pass);
+
+        Ok(())
+    }
+}
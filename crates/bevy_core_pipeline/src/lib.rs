@@ -15,10 +15,12 @@ pub mod core_2d;
 pub mod core_3d;
 pub mod deferred;
 pub mod dof;
+pub mod fullscreen_readback;
 pub mod fullscreen_vertex_shader;
 pub mod fxaa;
 pub mod motion_blur;
 pub mod msaa_writeback;
+pub mod post_process_material;
 pub mod prepass;
 mod skybox;
 mod taa;
@@ -55,6 +57,7 @@ use crate::{
     core_3d::Core3dPlugin,
     deferred::copy_lighting_id::CopyDeferredLightingIdPlugin,
     dof::DepthOfFieldPlugin,
+    fullscreen_readback::FullscreenReadbackPlugin,
     fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE,
     fxaa::FxaaPlugin,
     motion_blur::MotionBlurPlugin,
@@ -96,6 +99,7 @@ impl Plugin for CorePipelinePlugin {
                 CASPlugin,
                 MotionBlurPlugin,
                 DepthOfFieldPlugin,
+                FullscreenReadbackPlugin,
             ));
     }
 }
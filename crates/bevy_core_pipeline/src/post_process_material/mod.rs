@@ -0,0 +1,368 @@
+//! Post processing effects defined as materials.
+//!
+//! A [`PostProcessMaterial`] runs a fragment shader over the output of each camera with a
+//! `Handle` to the material, after tonemapping and before the UI is rendered. It is declared like
+//! a UI material, so simple effects such as color grading or stylization don't need their own
+//! render graph node.
+
+mod node;
+
+pub use node::PostProcessMaterialNode;
+
+use std::{any::TypeId, hash::Hash, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{Asset, AssetApp, AssetServer, Handle};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::SRes, SystemParamItem},
+};
+use bevy_render::{
+    extract_component::ExtractComponentPlugin,
+    render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
+    render_graph::{RenderGraphApp, RenderLabel, ViewNodeRunner},
+    render_resource::{
+        binding_types::{sampler, texture_2d},
+        *,
+    },
+    renderer::RenderDevice,
+    texture::{BevyDefault, FallbackImage, GpuImage},
+    view::{ExtractedView, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::default;
+
+use crate::{
+    core_2d::graph::{Core2d, Node2d},
+    core_3d::graph::{Core3d, Node3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+
+/// A fragment shader that runs over the output of a camera, with its bindings declared by
+/// [`AsBindGroup`].
+///
+/// Add a [`PostProcessMaterialPlugin<M>`] to the app, and a `Handle<M>` to the cameras the effect
+/// applies to. The shader runs after tonemapping, so it works with the final colors of the camera,
+/// and before the UI is rendered. The output of the camera is bound to group 0, and the material
+/// to group 1:
+///
+/// ```wgsl
+/// #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+///
+/// @group(0) @binding(0) var screen_texture: texture_2d<f32>;
+/// @group(0) @binding(1) var screen_sampler: sampler;
+///
+/// @group(1) @binding(0) var<uniform> tint: vec4<f32>;
+///
+/// @fragment
+/// fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+///     return textureSample(screen_texture, screen_sampler, in.uv) * tint;
+/// }
+/// ```
+///
+/// ```
+/// # use bevy_asset::Asset;
+/// # use bevy_color::LinearRgba;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_render::render_resource::{AsBindGroup, ShaderRef};
+/// # use bevy_core_pipeline::post_process_material::PostProcessMaterial;
+/// #[derive(Asset, TypePath, AsBindGroup, Clone)]
+/// struct Tint {
+///     #[uniform(0)]
+///     color: LinearRgba,
+/// }
+///
+/// impl PostProcessMaterial for Tint {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/tint.wgsl".into()
+///     }
+/// }
+/// ```
+pub trait PostProcessMaterial: AsBindGroup + Asset + Clone + Sized {
+    /// Returns this material's fragment shader, which needs a `fragment` entry point.
+    ///
+    /// Unlike other materials there is no default shader, so this must not return
+    /// [`ShaderRef::Default`].
+    fn fragment_shader() -> ShaderRef;
+
+    /// Customizes the pipeline of this material.
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: PostProcessMaterialKey<Self>) {}
+}
+
+pub struct PostProcessMaterialKey<M: PostProcessMaterial> {
+    pub hdr: bool,
+    pub bind_group_data: M::Data,
+}
+
+impl<M: PostProcessMaterial> Eq for PostProcessMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: PostProcessMaterial> PartialEq for PostProcessMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.hdr == other.hdr && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: PostProcessMaterial> Clone for PostProcessMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            hdr: self.hdr,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: PostProcessMaterial> Hash for PostProcessMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hdr.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+/// Adds the necessary ECS resources and render logic to apply the [`PostProcessMaterial`] `M` to
+/// cameras with a `Handle<M>`.
+pub struct PostProcessMaterialPlugin<M: PostProcessMaterial>(PhantomData<M>);
+
+impl<M: PostProcessMaterial> Default for PostProcessMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: PostProcessMaterial> Plugin for PostProcessMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        app.init_asset::<M>().add_plugins((
+            ExtractComponentPlugin::<Handle<M>>::default(),
+            RenderAssetPlugin::<PreparedPostProcessMaterial<M>>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<PostProcessMaterialPipeline<M>>>()
+            .add_systems(
+                Render,
+                prepare_post_process_material_pipelines::<M>.in_set(RenderSet::Prepare),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessMaterialNode<M>>>(
+                Core3d,
+                PostProcessMaterialLabel::of::<M>(),
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::Tonemapping,
+                    PostProcessMaterialLabel::of::<M>(),
+                    Node3d::EndMainPassPostProcessing,
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessMaterialNode<M>>>(
+                Core2d,
+                PostProcessMaterialLabel::of::<M>(),
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    Node2d::Tonemapping,
+                    PostProcessMaterialLabel::of::<M>(),
+                    Node2d::EndMainPassPostProcessing,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<PostProcessMaterialPipeline<M>>();
+    }
+}
+
+/// The label of the render graph node that applies a [`PostProcessMaterial`], in the 2d and 3d
+/// graphs.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PostProcessMaterialLabel(pub TypeId);
+
+impl PostProcessMaterialLabel {
+    /// Returns the label of the node that applies `M`.
+    pub fn of<M: PostProcessMaterial>() -> Self {
+        Self(TypeId::of::<M>())
+    }
+}
+
+#[derive(Resource)]
+pub struct PostProcessMaterialPipeline<M: PostProcessMaterial> {
+    pub screen_layout: BindGroupLayout,
+    pub material_layout: BindGroupLayout,
+    pub sampler: Sampler,
+    pub fragment_shader: Handle<Shader>,
+    marker: PhantomData<M>,
+}
+
+impl<M: PostProcessMaterial> SpecializedRenderPipeline for PostProcessMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = PostProcessMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = RenderPipelineDescriptor {
+            label: Some("post_process_material_pipeline".into()),
+            layout: vec![self.screen_layout.clone(), self.material_layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.fragment_shader.clone(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        };
+
+        M::specialize(&mut descriptor, key);
+
+        descriptor
+    }
+}
+
+impl<M: PostProcessMaterial> FromWorld for PostProcessMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let material_layout = M::bind_group_layout(render_device);
+
+        let screen_layout = render_device.create_bind_group_layout(
+            "post_process_material_screen_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        PostProcessMaterialPipeline {
+            screen_layout,
+            material_layout,
+            sampler,
+            fragment_shader: match M::fragment_shader() {
+                ShaderRef::Default => panic!(
+                    "PostProcessMaterial::fragment_shader of {} returned ShaderRef::Default",
+                    std::any::type_name::<M>()
+                ),
+                ShaderRef::Handle(handle) => handle,
+                ShaderRef::Path(path) => asset_server.load(path),
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+pub struct PreparedPostProcessMaterial<M: PostProcessMaterial> {
+    pub bindings: Vec<(u32, OwnedBindingResource)>,
+    pub bind_group: BindGroup,
+    pub key: M::Data,
+}
+
+impl<M: PostProcessMaterial> RenderAsset for PreparedPostProcessMaterial<M> {
+    type SourceAsset = M;
+
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<RenderAssets<GpuImage>>,
+        SRes<FallbackImage>,
+        SRes<PostProcessMaterialPipeline<M>>,
+    );
+
+    fn prepare_asset(
+        material: Self::SourceAsset,
+        (render_device, images, fallback_image, pipeline): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
+        match material.as_bind_group(
+            &pipeline.material_layout,
+            render_device,
+            images,
+            fallback_image,
+        ) {
+            Ok(prepared) => Ok(PreparedPostProcessMaterial {
+                bindings: prepared.bindings,
+                bind_group: prepared.bind_group,
+                key: prepared.data,
+            }),
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                Err(PrepareAssetError::RetryNextUpdate(material))
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ViewPostProcessMaterialPipeline<M: PostProcessMaterial> {
+    pub pipeline_id: CachedRenderPipelineId,
+    marker: PhantomData<M>,
+}
+
+fn prepare_post_process_material_pipelines<M: PostProcessMaterial>(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PostProcessMaterialPipeline<M>>>,
+    post_process_pipeline: Res<PostProcessMaterialPipeline<M>>,
+    materials: Res<RenderAssets<PreparedPostProcessMaterial<M>>>,
+    views: Query<(Entity, &ExtractedView, &Handle<M>)>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    for (entity, view, handle) in &views {
+        // The material may not be prepared yet
+        let Some(material) = materials.get(handle) else {
+            continue;
+        };
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &post_process_pipeline,
+            PostProcessMaterialKey {
+                hdr: view.hdr,
+                bind_group_data: material.key.clone(),
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewPostProcessMaterialPipeline::<M> {
+                pipeline_id,
+                marker: PhantomData,
+            });
+    }
+}
@@ -0,0 +1,107 @@
+use std::{marker::PhantomData, sync::Mutex};
+
+use crate::post_process_material::{
+    PostProcessMaterial, PostProcessMaterialPipeline, PreparedPostProcessMaterial,
+    ViewPostProcessMaterialPipeline,
+};
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryItem;
+use bevy_render::{
+    diagnostic::RecordDiagnostics,
+    render_asset::RenderAssets,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        BindGroup, BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment,
+        RenderPassDescriptor, TextureViewId,
+    },
+    renderer::RenderContext,
+    view::ViewTarget,
+};
+
+/// Applies the [`PostProcessMaterial`] `M` of a view.
+pub struct PostProcessMaterialNode<M: PostProcessMaterial> {
+    cached_texture_bind_group: Mutex<Option<(TextureViewId, BindGroup)>>,
+    marker: PhantomData<M>,
+}
+
+impl<M: PostProcessMaterial> Default for PostProcessMaterialNode<M> {
+    fn default() -> Self {
+        Self {
+            cached_texture_bind_group: Mutex::new(None),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: PostProcessMaterial> ViewNode for PostProcessMaterialNode<M> {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static Handle<M>,
+        &'static ViewPostProcessMaterialPipeline<M>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, material, pipeline): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let post_process_pipeline = world.resource::<PostProcessMaterialPipeline<M>>();
+        let materials = world.resource::<RenderAssets<PreparedPostProcessMaterial<M>>>();
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline.pipeline_id) else {
+            return Ok(());
+        };
+        let Some(material) = materials.get(material) else {
+            return Ok(());
+        };
+
+        let post_process = target.post_process_write();
+        let source = post_process.source;
+        let destination = post_process.destination;
+        let mut cached_bind_group = self.cached_texture_bind_group.lock().unwrap();
+        let bind_group = match &mut *cached_bind_group {
+            Some((id, bind_group)) if source.id() == *id => bind_group,
+            cached_bind_group => {
+                let bind_group = render_context.render_device().create_bind_group(
+                    None,
+                    &post_process_pipeline.screen_layout,
+                    &BindGroupEntries::sequential((source, &post_process_pipeline.sampler)),
+                );
+
+                let (_, bind_group) = cached_bind_group.insert((source.id(), bind_group));
+                bind_group
+            }
+        };
+
+        let diagnostics = render_context.diagnostic_recorder();
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("post_process_material_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        };
+
+        let mut render_pass = render_context
+            .command_encoder()
+            .begin_render_pass(&pass_descriptor);
+        let pass_span = diagnostics.pass_span(&mut render_pass, "post_process_material");
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_bind_group(1, &material.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        pass_span.end(&mut render_pass);
+
+        Ok(())
+    }
+}
@@ -26,6 +26,7 @@
 //! Currently only works for 3D.
 
 pub mod node;
+pub mod readback;
 
 use std::ops::Range;
 
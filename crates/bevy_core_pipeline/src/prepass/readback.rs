@@ -0,0 +1,387 @@
+//! Read the depth and normal prepass textures of a camera back to the CPU.
+//!
+//! The prepass textures are read back each frame while the readback component is on the camera,
+//! and arrive in the main world a few frames later as an event. The camera needs the matching
+//! prepass component, and [`Msaa::Off`](bevy_render::view::Msaa::Off), as multisampled textures
+//! can't be read back.
+
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_math::{URect, UVec2, Vec3};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    gpu_readback::GpuReadbacks,
+    render_resource::TextureAspect,
+    renderer::RenderDevice,
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::warn_once;
+
+use crate::{core_3d::prepare_prepass_textures, prepass::ViewPrepassTextures};
+
+/// Adds support for [`ReadbackDepthPrepass`] and [`ReadbackNormalPrepass`].
+pub struct PrepassReadbackPlugin;
+
+impl Plugin for PrepassReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.add_plugins((
+            ExtractComponentPlugin::<ReadbackDepthPrepass>::default(),
+            ExtractComponentPlugin::<ReadbackNormalPrepass>::default(),
+        ))
+        .add_event::<DepthPrepassReadback>()
+        .add_event::<NormalPrepassReadback>()
+        .insert_resource(PrepassReadbackReceiver(rx))
+        .add_systems(First, send_prepass_readback_events);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(PrepassReadbackSender(tx))
+            .add_systems(
+                Render,
+                request_prepass_readbacks
+                    .in_set(RenderSet::PrepareResources)
+                    .after(prepare_prepass_textures),
+            );
+    }
+}
+
+/// Reads back the depth prepass of a camera each frame, which is sent to the main world as a
+/// [`DepthPrepassReadback`] event.
+///
+/// The camera also needs a [`DepthPrepass`](crate::prepass::DepthPrepass).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::UVec2;
+/// # use bevy_core_pipeline::prepass::readback::DepthPrepassReadback;
+/// fn print_center_depth(mut readbacks: EventReader<DepthPrepassReadback>) {
+///     for readback in readbacks.read() {
+///         let center = (readback.region.min + readback.region.max) / 2;
+///         println!("The depth at the center is {:?}", readback.get(center));
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_center_depth);
+/// ```
+#[derive(Component, ExtractComponent, Clone, Copy, Debug, Default)]
+pub struct ReadbackDepthPrepass {
+    /// The part of the depth texture to read back, in physical pixels of the render target, or
+    /// the whole texture if `None`.
+    pub region: Option<URect>,
+}
+
+/// Sent when the depth prepass of a camera with [`ReadbackDepthPrepass`] has been read back.
+#[derive(Event, Clone, Debug)]
+pub struct DepthPrepassReadback {
+    /// The camera the depth was read back from.
+    pub entity: Entity,
+    /// The part of the depth texture that was read back.
+    pub region: URect,
+    /// The depth of each pixel in `region`, row by row.
+    ///
+    /// Bevy uses reversed depth, so `1.0` is on the near plane and `0.0` is infinitely far away.
+    pub depth: Vec<f32>,
+}
+
+impl DepthPrepassReadback {
+    /// Returns the depth at `pixel`, in physical pixels of the render target, if it is within
+    /// [`region`](Self::region).
+    pub fn get(&self, pixel: UVec2) -> Option<f32> {
+        pixel_index(self.region, pixel).and_then(|index| self.depth.get(index).copied())
+    }
+}
+
+/// Reads back the normal prepass of a camera each frame, which is sent to the main world as a
+/// [`NormalPrepassReadback`] event.
+///
+/// The camera also needs a [`NormalPrepass`](crate::prepass::NormalPrepass).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{UVec2, Vec3};
+/// # use bevy_core_pipeline::prepass::readback::NormalPrepassReadback;
+/// fn find_floor(mut readbacks: EventReader<NormalPrepassReadback>) {
+///     for readback in readbacks.read() {
+///         let facing_up = readback
+///             .normals
+///             .iter()
+///             .filter(|normal| normal.dot(Vec3::Y) > 0.9)
+///             .count();
+///         println!("{facing_up} pixels are facing up");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(find_floor);
+/// ```
+#[derive(Component, ExtractComponent, Clone, Copy, Debug, Default)]
+pub struct ReadbackNormalPrepass {
+    /// The part of the normal texture to read back, in physical pixels of the render target, or
+    /// the whole texture if `None`.
+    pub region: Option<URect>,
+}
+
+/// Sent when the normal prepass of a camera with [`ReadbackNormalPrepass`] has been read back.
+#[derive(Event, Clone, Debug)]
+pub struct NormalPrepassReadback {
+    /// The camera the normals were read back from.
+    pub entity: Entity,
+    /// The part of the normal texture that was read back.
+    pub region: URect,
+    /// The world space normal of each pixel in `region`, row by row.
+    ///
+    /// Pixels not covered by any mesh are [`Vec3::ZERO`]. The normals are stored with 10 bits
+    /// per component, so they are only approximately normalized.
+    pub normals: Vec<Vec3>,
+}
+
+impl NormalPrepassReadback {
+    /// Returns the normal at `pixel`, in physical pixels of the render target, if it is within
+    /// [`region`](Self::region).
+    pub fn get(&self, pixel: UVec2) -> Option<Vec3> {
+        pixel_index(self.region, pixel).and_then(|index| self.normals.get(index).copied())
+    }
+}
+
+/// Returns the index of `pixel` in the row by row data read back from `region`.
+fn pixel_index(region: URect, pixel: UVec2) -> Option<usize> {
+    // `URect::contains` includes the max edge, which is outside of the pixels read back
+    if !region.contains(pixel) || pixel.cmpeq(region.max).any() {
+        return None;
+    }
+    let local = pixel - region.min;
+    Some(local.y as usize * region.width() as usize + local.x as usize)
+}
+
+/// Decodes a pixel of the normal prepass, see [`NORMAL_PREPASS_FORMAT`](crate::prepass::NORMAL_PREPASS_FORMAT).
+fn decode_normal(packed: u32) -> Vec3 {
+    // The alpha is written as 1.0 wherever there is a mesh
+    if packed >> 30 == 0 {
+        return Vec3::ZERO;
+    }
+    let component = |shift: u32| ((packed >> shift) & 0x3ff) as f32 / 1023.0;
+    // The prepass stores `normal * 0.5 + 0.5`
+    Vec3::new(component(0), component(10), component(20)) * 2.0 - 1.0
+}
+
+/// Prepass data read back in the render world.
+enum PrepassReadback {
+    Depth(DepthPrepassReadback),
+    Normal(NormalPrepassReadback),
+}
+
+/// Receives the prepass data read back in the render world.
+#[derive(Resource)]
+struct PrepassReadbackReceiver(Receiver<PrepassReadback>);
+
+/// Sends the prepass data read back to the main world.
+#[derive(Resource)]
+struct PrepassReadbackSender(Sender<PrepassReadback>);
+
+fn send_prepass_readback_events(
+    receiver: Res<PrepassReadbackReceiver>,
+    mut depth_events: EventWriter<DepthPrepassReadback>,
+    mut normal_events: EventWriter<NormalPrepassReadback>,
+) {
+    while let Ok(readback) = receiver.0.try_recv() {
+        match readback {
+            PrepassReadback::Depth(readback) => {
+                depth_events.send(readback);
+            }
+            PrepassReadback::Normal(readback) => {
+                normal_events.send(readback);
+            }
+        }
+    }
+}
+
+/// Returns the whole of a texture of `size`, or the part of it in `region`.
+fn readback_region(size: UVec2, region: Option<URect>) -> URect {
+    let full = URect::from_corners(UVec2::ZERO, size);
+    region.map_or(full, |region| region.intersect(full))
+}
+
+/// Splits the data read back into its 4 byte pixels.
+fn pixels(data: &[u8]) -> impl Iterator<Item = [u8; 4]> + '_ {
+    data.chunks_exact(4)
+        .map(|bytes| [bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn request_prepass_readbacks(
+    mut readbacks: ResMut<GpuReadbacks>,
+    device: Res<RenderDevice>,
+    sender: Res<PrepassReadbackSender>,
+    views: Query<(
+        Entity,
+        &ViewPrepassTextures,
+        Option<&ReadbackDepthPrepass>,
+        Option<&ReadbackNormalPrepass>,
+    )>,
+) {
+    for (entity, prepass_textures, depth_readback, normal_readback) in &views {
+        // Keyed by the view, so skipped readbacks go first next frame
+        readbacks.for_entity(entity, |readbacks| {
+            if let (Some(readback), Some(depth)) = (depth_readback, &prepass_textures.depth) {
+                let texture = &depth.texture.texture;
+                let size = UVec2::new(texture.width(), texture.height());
+                let region = readback_region(size, readback.region);
+                let sender = sender.0.clone();
+                let result = readbacks.read_texture(
+                    &device,
+                    texture,
+                    region,
+                    TextureAspect::DepthOnly,
+                    move |result| {
+                        let data = match result {
+                            Ok(data) => data,
+                            Err(err) => {
+                                warn_once!(
+                                    "Can't read back the depth prepass of {entity:?}: {err}"
+                                );
+                                return;
+                            }
+                        };
+                        let depth = pixels(&data).map(f32::from_ne_bytes).collect();
+                        // The main world may have been dropped during shutdown
+                        let _ = sender.try_send(PrepassReadback::Depth(DepthPrepassReadback {
+                            entity,
+                            region,
+                            depth,
+                        }));
+                    },
+                );
+                if let Err(err) = result {
+                    warn_once!("Can't read back the depth prepass of {entity:?}: {err}");
+                }
+            }
+
+            if let (Some(readback), Some(normal)) = (normal_readback, &prepass_textures.normal) {
+                let texture = &normal.texture.texture;
+                let size = UVec2::new(texture.width(), texture.height());
+                let region = readback_region(size, readback.region);
+                let sender = sender.0.clone();
+                let result = readbacks.read_texture(
+                    &device,
+                    texture,
+                    region,
+                    TextureAspect::All,
+                    move |result| {
+                        let data = match result {
+                            Ok(data) => data,
+                            Err(err) => {
+                                warn_once!(
+                                    "Can't read back the normal prepass of {entity:?}: {err}"
+                                );
+                                return;
+                            }
+                        };
+                        let normals = pixels(&data)
+                            .map(|pixel| decode_normal(u32::from_ne_bytes(pixel)))
+                            .collect();
+                        // The main world may have been dropped during shutdown
+                        let _ = sender.try_send(PrepassReadback::Normal(NormalPrepassReadback {
+                            entity,
+                            region,
+                            normals,
+                        }));
+                    },
+                );
+                if let Err(err) = result {
+                    warn_once!("Can't read back the normal prepass of {entity:?}: {err}");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_index_in_region() {
+        let region = URect::new(2, 3, 6, 5);
+
+        assert_eq!(pixel_index(region, UVec2::new(2, 3)), Some(0));
+        assert_eq!(pixel_index(region, UVec2::new(5, 3)), Some(3));
+        assert_eq!(pixel_index(region, UVec2::new(2, 4)), Some(4));
+        assert_eq!(pixel_index(region, UVec2::new(5, 4)), Some(7));
+    }
+
+    #[test]
+    fn pixel_index_on_max_edge() {
+        let region = URect::new(2, 3, 6, 5);
+
+        assert_eq!(pixel_index(region, UVec2::new(6, 3)), None);
+        assert_eq!(pixel_index(region, UVec2::new(2, 5)), None);
+        assert_eq!(pixel_index(region, region.max), None);
+    }
+
+    #[test]
+    fn pixel_index_outside_region() {
+        let region = URect::new(2, 3, 6, 5);
+
+        assert_eq!(pixel_index(region, UVec2::new(1, 3)), None);
+        assert_eq!(pixel_index(region, UVec2::new(2, 2)), None);
+        assert_eq!(pixel_index(region, UVec2::new(10, 10)), None);
+        assert_eq!(pixel_index(URect::default(), UVec2::ZERO), None);
+    }
+
+    #[test]
+    fn readback_region_of_whole_texture() {
+        let size = UVec2::new(8, 4);
+
+        assert_eq!(readback_region(size, None), URect::new(0, 0, 8, 4));
+        assert_eq!(
+            readback_region(size, Some(URect::new(1, 1, 3, 2))),
+            URect::new(1, 1, 3, 2)
+        );
+    }
+
+    #[test]
+    fn readback_region_partly_outside_texture() {
+        let size = UVec2::new(8, 4);
+
+        let region = readback_region(size, Some(URect::new(6, 2, 12, 10)));
+        assert_eq!(region, URect::new(6, 2, 8, 4));
+        assert_eq!(pixel_index(region, UVec2::new(7, 3)), Some(3));
+        assert_eq!(pixel_index(region, UVec2::new(8, 3)), None);
+
+        assert!(readback_region(size, Some(URect::new(10, 10, 12, 12))).is_empty());
+    }
+
+    /// Packs `normal` the way the normal prepass stores it in an `Rgb10a2Unorm` texture.
+    fn encode_normal(normal: Vec3, alpha: u32) -> u32 {
+        let [x, y, z] = ((normal * 0.5 + 0.5) * 1023.0)
+            .round()
+            .as_uvec3()
+            .to_array();
+        x | y << 10 | z << 20 | alpha << 30
+    }
+
+    #[test]
+    fn decode_axis_normals() {
+        for normal in [
+            Vec3::X,
+            Vec3::NEG_X,
+            Vec3::Y,
+            Vec3::NEG_Y,
+            Vec3::Z,
+            Vec3::NEG_Z,
+        ] {
+            let decoded = decode_normal(encode_normal(normal, 3));
+            // Zero is halfway between two 10 bit values
+            assert!(
+                decoded.abs_diff_eq(normal, 1.0 / 1023.0),
+                "{normal} was decoded as {decoded}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_without_mesh() {
+        assert_eq!(decode_normal(0), Vec3::ZERO);
+        assert_eq!(decode_normal(encode_normal(Vec3::Y, 0)), Vec3::ZERO);
+    }
+}
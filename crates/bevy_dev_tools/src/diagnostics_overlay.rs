@@ -0,0 +1,309 @@
+//! Module containing logic for the diagnostics overlay.
+
+use bevy_app::{Plugin, Startup, Update};
+use bevy_asset::Handle;
+use bevy_color::Color;
+use bevy_diagnostic::{
+    DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::default;
+
+/// Global [`ZIndex`] used to render the diagnostics overlay.
+///
+/// This is below the [FPS overlay](crate::fps_overlay::FPS_OVERLAY_ZINDEX) and the other
+/// overlays of this crate.
+pub const DIAGNOSTICS_OVERLAY_ZINDEX: i32 = i32::MAX - 128;
+
+/// A plugin that adds an overlay showing the current value and recent history of a list of
+/// [diagnostics](bevy_diagnostic), which can be toggled with a key.
+///
+/// By default the frame rate, frame time and entity count are shown, and this plugin adds the
+/// [`FrameTimeDiagnosticsPlugin`] and [`EntityCountDiagnosticsPlugin`] if they weren't added
+/// before. Any other diagnostic can be added to [`DiagnosticsOverlayConfig::diagnostics`], as long
+/// as the plugin measuring it is added as well.
+///
+/// Each diagnostic is shown with its smoothed value and a plot of the measurements in its
+/// history, scaled to the largest of them.
+#[derive(Default)]
+pub struct DiagnosticsOverlayPlugin {
+    /// Starting configuration of the overlay, this can be later be changed through the
+    /// [`DiagnosticsOverlayConfig`] resource.
+    pub config: DiagnosticsOverlayConfig,
+}
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        // TODO: Use plugin dependencies, see https://github.com/bevyengine/bevy/issues/69
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+        app.insert_resource(self.config.clone())
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    toggle_overlay,
+                    customize_overlay.run_if(resource_changed::<DiagnosticsOverlayConfig>),
+                    update_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configuration options for the diagnostics overlay.
+#[derive(Resource, Clone)]
+pub struct DiagnosticsOverlayConfig {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+    /// The key that shows and hides the overlay.
+    pub toggle_key: KeyCode,
+    /// The diagnostics shown, in order.
+    pub diagnostics: Vec<DiagnosticPath>,
+    /// The number of measurements shown in the plot of each diagnostic.
+    ///
+    /// This is limited by the history length of each diagnostic.
+    pub plot_samples: usize,
+    /// The width of the plot of each diagnostic, in logical pixels.
+    pub plot_width: f32,
+    /// The height of the plot of each diagnostic, in logical pixels.
+    pub plot_height: f32,
+    /// The color of the plots.
+    pub plot_color: Color,
+    /// Configuration of text in the overlay.
+    pub text_config: TextStyle,
+    /// The color behind the overlay.
+    pub background_color: Color,
+}
+
+impl Default for DiagnosticsOverlayConfig {
+    fn default() -> Self {
+        DiagnosticsOverlayConfig {
+            enabled: true,
+            toggle_key: KeyCode::F2,
+            diagnostics: vec![
+                FrameTimeDiagnosticsPlugin::FPS,
+                FrameTimeDiagnosticsPlugin::FRAME_TIME,
+                EntityCountDiagnosticsPlugin::ENTITY_COUNT,
+            ],
+            plot_samples: 20,
+            plot_width: 100.0,
+            plot_height: 16.0,
+            plot_color: Color::srgb(0.3, 0.8, 1.0),
+            text_config: TextStyle {
+                font: Handle::<Font>::default(),
+                font_size: 16.0,
+                color: Color::WHITE,
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.7),
+        }
+    }
+}
+
+#[derive(Component)]
+struct DiagnosticsOverlayRoot;
+
+/// The parts of [`DiagnosticsOverlayConfig`] the rows are spawned with.
+#[derive(PartialEq)]
+struct RowLayout {
+    diagnostics: Vec<DiagnosticPath>,
+    plot_samples: usize,
+    plot_width: f32,
+    plot_height: f32,
+    plot_color: Color,
+}
+
+impl RowLayout {
+    fn new(config: &DiagnosticsOverlayConfig) -> Self {
+        Self {
+            diagnostics: config.diagnostics.clone(),
+            plot_samples: config.plot_samples,
+            plot_width: config.plot_width,
+            plot_height: config.plot_height,
+            plot_color: config.plot_color,
+        }
+    }
+}
+
+#[derive(Component)]
+struct DiagnosticsOverlayRow;
+
+#[derive(Component)]
+struct DiagnosticLabel(usize);
+
+#[derive(Component)]
+struct DiagnosticPlotBar {
+    row: usize,
+    sample: usize,
+}
+
+fn setup(mut commands: Commands) {
+    // The rows are spawned by `customize_overlay`
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                // We need to make sure the overlay doesn't affect the position of other UI nodes
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                left: Val::Px(0.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            z_index: ZIndex::Global(DIAGNOSTICS_OVERLAY_ZINDEX),
+            ..default()
+        },
+        DiagnosticsOverlayRoot,
+    ));
+}
+
+fn spawn_row(parent: &mut ChildBuilder, row: usize, config: &DiagnosticsOverlayConfig) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            DiagnosticsOverlayRow,
+        ))
+        .with_children(|c| {
+            // Bars grow from the bottom of the plot
+            c.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(config.plot_width),
+                    height: Val::Px(config.plot_height),
+                    margin: UiRect::right(Val::Px(6.0)),
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|c| {
+                let samples = config.plot_samples.max(1);
+                for sample in 0..samples {
+                    c.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.0 / samples as f32),
+                                height: Val::Percent(0.0),
+                                ..default()
+                            },
+                            background_color: config.plot_color.into(),
+                            ..default()
+                        },
+                        DiagnosticPlotBar { row, sample },
+                    ));
+                }
+            });
+            c.spawn((TextBundle::default(), DiagnosticLabel(row)));
+        });
+}
+
+fn toggle_overlay(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<DiagnosticsOverlayConfig>) {
+    if keys.just_pressed(config.toggle_key) {
+        config.enabled = !config.enabled;
+    }
+}
+
+fn customize_overlay(
+    mut commands: Commands,
+    config: Res<DiagnosticsOverlayConfig>,
+    mut roots: Query<(Entity, &mut Style, &mut BackgroundColor), With<DiagnosticsOverlayRoot>>,
+    rows: Query<Entity, With<DiagnosticsOverlayRow>>,
+    mut layout: Local<Option<RowLayout>>,
+) {
+    // Only respawn the rows when they change, not when the overlay is shown or hidden
+    let new_layout = RowLayout::new(&config);
+    let respawn = layout.as_ref() != Some(&new_layout);
+    *layout = Some(new_layout);
+
+    for (root, mut style, mut background_color) in &mut roots {
+        style.display = if config.enabled {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        *background_color = config.background_color.into();
+
+        if !respawn {
+            continue;
+        }
+        for row in &rows {
+            commands.entity(row).despawn_recursive();
+        }
+        commands.entity(root).with_children(|c| {
+            for row in 0..config.diagnostics.len() {
+                spawn_row(c, row, &config);
+            }
+        });
+    }
+}
+
+fn update_overlay(
+    config: Res<DiagnosticsOverlayConfig>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut labels: Query<(&DiagnosticLabel, &mut Text)>,
+    mut bars: Query<(&DiagnosticPlotBar, &mut Style), Without<DiagnosticLabel>>,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    for (DiagnosticLabel(row), mut text) in &mut labels {
+        let Some(path) = config.diagnostics.get(*row) else {
+            continue;
+        };
+        let value = diagnostics
+            .get(path)
+            .and_then(|diagnostic| Some((diagnostic.smoothed()?, &diagnostic.suffix)));
+        let value = match value {
+            Some((value, suffix)) => format!("{path}: {value:.2}{suffix}"),
+            None => format!("{path}: -"),
+        };
+        text.sections = vec![TextSection::new(value, config.text_config.clone())];
+    }
+
+    let samples = config.plot_samples.max(1);
+    for (bar, mut style) in &mut bars {
+        let Some(diagnostic) = config
+            .diagnostics
+            .get(bar.row)
+            .and_then(|path| diagnostics.get(path))
+        else {
+            style.height = Val::Percent(0.0);
+            continue;
+        };
+        // Only the newest measurements are plotted, with the newest on the right
+        let len = diagnostic.history_len().min(samples);
+        let history = || diagnostic.values().skip(diagnostic.history_len() - len);
+        let max = history().copied().fold(0.0, f64::max);
+        let height = bar
+            .sample
+            .checked_sub(samples - len)
+            .and_then(|index| history().nth(index))
+            .filter(|_| max > 0.0)
+            .map_or(0.0, |value| (value / max) as f32 * 100.0);
+        style.height = Val::Percent(height);
+    }
+}
@@ -0,0 +1,351 @@
+//! Module containing logic for the frame time breakdown overlay.
+
+use bevy_app::{First, Plugin, Startup, Update};
+use bevy_asset::Handle;
+use bevy_color::Color;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_log::{warn_once, SpanTimings};
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_time::{Real, Time};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::{default, HashMap};
+
+/// Global [`ZIndex`] used to render the frame time overlay.
+///
+/// This is below the [log console](crate::log_console::LOG_CONSOLE_ZINDEX), so the console can be
+/// read while the overlay is shown.
+pub const FRAME_TIME_OVERLAY_ZINDEX: i32 = i32::MAX - 96;
+
+/// A plugin that adds an overlay showing which spans, such as systems, take the most time each
+/// frame.
+///
+/// The timings are read from the [`SpanTimings`] resource, which is added by the
+/// [`LogPlugin`](bevy_log::LogPlugin) when [`LogPlugin::span_timings`](bevy_log::LogPlugin::span_timings)
+/// is enabled. Bevy only emits a span for each system with the `trace` feature.
+///
+/// Each row shows the smoothed time spent in a span, and the longest time over the last second,
+/// so that spikes stay visible long enough to be read. The bars are scaled to the frame time.
+#[derive(Default)]
+pub struct FrameTimeOverlayPlugin {
+    /// Starting configuration of the overlay, this can be later be changed through the
+    /// [`FrameTimeOverlayConfig`] resource.
+    pub config: FrameTimeOverlayConfig,
+}
+
+impl Plugin for FrameTimeOverlayPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<FrameTimeBreakdown>()
+            .add_systems(Startup, setup)
+            .add_systems(First, collect_timings)
+            .add_systems(
+                Update,
+                (
+                    toggle_overlay,
+                    customize_overlay.run_if(resource_changed::<FrameTimeOverlayConfig>),
+                    update_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configuration options for the frame time overlay.
+#[derive(Resource, Clone)]
+pub struct FrameTimeOverlayConfig {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+    /// The key that shows and hides the overlay.
+    pub toggle_key: KeyCode,
+    /// The number of spans shown, slowest first.
+    pub rows: usize,
+    /// The width in logical pixels of a bar for a span that takes the whole frame.
+    pub bar_width: f32,
+    /// The color of the bars.
+    pub bar_color: Color,
+    /// How much of the previous smoothed time is kept each frame, between `0.0` and `1.0`.
+    pub smoothing: f32,
+    /// Configuration of text in the overlay.
+    pub text_config: TextStyle,
+    /// The color behind the overlay.
+    pub background_color: Color,
+}
+
+impl Default for FrameTimeOverlayConfig {
+    fn default() -> Self {
+        FrameTimeOverlayConfig {
+            enabled: true,
+            toggle_key: KeyCode::F3,
+            rows: 12,
+            bar_width: 200.0,
+            bar_color: Color::srgb(1.0, 0.6, 0.2),
+            smoothing: 0.9,
+            text_config: TextStyle {
+                font: Handle::<Font>::default(),
+                font_size: 14.0,
+                color: Color::WHITE,
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.7),
+        }
+    }
+}
+
+/// The time spent in each span, averaged over recent frames.
+#[derive(Resource, Default)]
+struct FrameTimeBreakdown {
+    frame: SmoothedTime,
+    spans: HashMap<String, SmoothedTime>,
+    /// Seconds since the peaks were last moved to `shown_peak`.
+    peak_age: f32,
+}
+
+/// A smoothed time and its peak, in milliseconds.
+#[derive(Default, Clone, Copy)]
+struct SmoothedTime {
+    smoothed: f32,
+    peak: f32,
+    shown_peak: f32,
+}
+
+impl SmoothedTime {
+    fn add(&mut self, millis: f32, smoothing: f32) {
+        self.smoothed = self.smoothed * smoothing + millis * (1.0 - smoothing);
+        self.peak = self.peak.max(millis);
+    }
+
+    fn reset_peak(&mut self) {
+        self.shown_peak = self.peak;
+        self.peak = 0.0;
+    }
+}
+
+#[derive(Component)]
+struct FrameTimeOverlayRoot;
+
+#[derive(Component)]
+struct FrameTimeHeader;
+
+#[derive(Component)]
+struct FrameTimeRow;
+
+#[derive(Component)]
+struct FrameTimeBar(usize);
+
+#[derive(Component)]
+struct FrameTimeLabel(usize);
+
+fn setup(mut commands: Commands, config: Res<FrameTimeOverlayConfig>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // We need to make sure the overlay doesn't affect the position of other UI nodes
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                background_color: config.background_color.into(),
+                z_index: ZIndex::Global(FRAME_TIME_OVERLAY_ZINDEX),
+                ..default()
+            },
+            FrameTimeOverlayRoot,
+        ))
+        .with_children(|c| {
+            // The rows are spawned by `customize_overlay`
+            c.spawn((TextBundle::default(), FrameTimeHeader));
+        });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, row: usize, config: &FrameTimeOverlayConfig) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            FrameTimeRow,
+        ))
+        .with_children(|c| {
+            // The bar sits in a track of the full width, so the labels line up
+            c.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(config.bar_width),
+                    height: Val::Px(config.text_config.font_size * 0.6),
+                    margin: UiRect::right(Val::Px(6.0)),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|c| {
+                c.spawn((
+                    NodeBundle {
+                        style: Style {
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: config.bar_color.into(),
+                        ..default()
+                    },
+                    FrameTimeBar(row),
+                ));
+            });
+            c.spawn((TextBundle::default(), FrameTimeLabel(row)));
+        });
+}
+
+fn collect_timings(
+    time: Res<Time<Real>>,
+    config: Res<FrameTimeOverlayConfig>,
+    span_timings: Option<Res<SpanTimings>>,
+    mut breakdown: ResMut<FrameTimeBreakdown>,
+) {
+    let Some(span_timings) = span_timings else {
+        return;
+    };
+    // Keep draining the timings while hidden, so they don't pile up
+    let timings = span_timings.take();
+    if !config.enabled {
+        return;
+    }
+
+    let smoothing = config.smoothing.clamp(0.0, 1.0);
+    let breakdown = &mut *breakdown;
+    breakdown
+        .frame
+        .add(time.delta_seconds() * 1000.0, smoothing);
+
+    let mut seen = HashMap::with_capacity(timings.len());
+    for timing in timings {
+        seen.insert(timing.name, timing.total.as_secs_f32() * 1000.0);
+    }
+    for (name, span) in &mut breakdown.spans {
+        span.add(seen.remove(name).unwrap_or(0.0), smoothing);
+    }
+    for (name, millis) in seen {
+        let mut span = SmoothedTime::default();
+        span.add(millis, smoothing);
+        breakdown.spans.insert(name, span);
+    }
+    // Forget spans that stopped running
+    breakdown
+        .spans
+        .retain(|_, span| span.smoothed > 1e-4 || span.peak > 0.0 || span.shown_peak > 0.0);
+
+    breakdown.peak_age += time.delta_seconds();
+    if breakdown.peak_age >= 1.0 {
+        breakdown.peak_age = 0.0;
+        breakdown.frame.reset_peak();
+        breakdown
+            .spans
+            .values_mut()
+            .for_each(SmoothedTime::reset_peak);
+    }
+}
+
+fn toggle_overlay(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<FrameTimeOverlayConfig>) {
+    if keys.just_pressed(config.toggle_key) {
+        config.enabled = !config.enabled;
+    }
+}
+
+fn customize_overlay(
+    mut commands: Commands,
+    config: Res<FrameTimeOverlayConfig>,
+    mut roots: Query<(Entity, &mut Style, &mut BackgroundColor), With<FrameTimeOverlayRoot>>,
+    rows: Query<Entity, With<FrameTimeRow>>,
+) {
+    for (root, mut style, mut background_color) in &mut roots {
+        style.display = if config.enabled {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        *background_color = config.background_color.into();
+
+        // (Re)spawn the rows, as their number or style may have changed
+        for row in &rows {
+            commands.entity(row).despawn_recursive();
+        }
+        commands.entity(root).with_children(|c| {
+            for row in 0..config.rows {
+                spawn_row(c, row, &config);
+            }
+        });
+    }
+}
+
+fn update_overlay(
+    config: Res<FrameTimeOverlayConfig>,
+    span_timings: Option<Res<SpanTimings>>,
+    breakdown: Res<FrameTimeBreakdown>,
+    mut sorted: Local<Vec<(String, SmoothedTime)>>,
+    mut header: Query<&mut Text, With<FrameTimeHeader>>,
+    mut bars: Query<(&FrameTimeBar, &mut Style)>,
+    mut labels: Query<(&FrameTimeLabel, &mut Text), Without<FrameTimeHeader>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    if span_timings.is_none() {
+        warn_once!(
+            "The frame time overlay needs the SpanTimings resource, see `LogPlugin::span_timings`"
+        );
+    }
+
+    let frame = breakdown.frame;
+    for mut text in &mut header {
+        text.sections = vec![TextSection::new(
+            format!(
+                "Frame: {:.2} ms (peak {:.2} ms)",
+                frame.smoothed, frame.shown_peak
+            ),
+            config.text_config.clone(),
+        )];
+    }
+
+    sorted.clear();
+    sorted.extend(
+        breakdown
+            .spans
+            .iter()
+            .map(|(name, span)| (name.clone(), *span)),
+    );
+    sorted.sort_unstable_by(|(_, a), (_, b)| b.smoothed.total_cmp(&a.smoothed));
+
+    let frame_time = frame.smoothed.max(1e-3);
+    for (FrameTimeBar(row), mut style) in &mut bars {
+        let fraction = sorted
+            .get(*row)
+            .map_or(0.0, |(_, span)| (span.smoothed / frame_time).min(1.0));
+        style.width = Val::Percent(fraction * 100.0);
+    }
+    for (FrameTimeLabel(row), mut text) in &mut labels {
+        let value = sorted.get(*row).map_or(String::new(), |(name, span)| {
+            // Systems are the most common spans, so their prefix is left out
+            let name = name.strip_prefix("system: ").unwrap_or(name);
+            format!(
+                "{:>6.2} ms (peak {:>6.2}) {name}",
+                span.smoothed, span.shown_peak
+            )
+        });
+        text.sections = vec![TextSection::new(value, config.text_config.clone())];
+    }
+}
@@ -0,0 +1,459 @@
+//! Golden image tests, which render a scene offscreen and compare it to a reference image.
+//!
+//! A [`GoldenImageTest`] takes an [`App`] with rendering set up but no window, renders the camera
+//! marked with [`GoldenImageCamera`] to an image, reads the image back with a
+//! [`ReadbackTexture`] and compares it to a reference PNG image with a tolerance. A missing
+//! reference image fails the test, with the rendered image written next to where it should be.
+//! When the `BEVY_UPDATE_GOLDEN_IMAGES` environment variable is set, reference images are written
+//! from the rendered images instead, so that they can be reviewed and committed.
+//!
+//! These tests need a GPU adapter, so they should be skipped on machines without one.
+
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+};
+
+use bevy_app::{App, PluginsState};
+use bevy_asset::Assets;
+use bevy_ecs::{component::Component, entity::Entity, event::Events, query::With};
+use bevy_math::UVec2;
+use bevy_render::{
+    camera::{Camera, RenderTarget},
+    gpu_readback::{ReadbackComplete, ReadbackError, ReadbackFailed, ReadbackTexture},
+    render_resource::{
+        Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    },
+    texture::Image,
+};
+use thiserror::Error;
+
+/// The environment variable which, when set, makes every [`GoldenImageTest`] overwrite its
+/// reference image with the rendered one instead of comparing them.
+pub const UPDATE_GOLDEN_IMAGES: &str = "BEVY_UPDATE_GOLDEN_IMAGES";
+
+/// Marks the camera rendered by a [`GoldenImageTest`].
+///
+/// The test sets the [`target`](Camera::target) of the camera to its own image.
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct GoldenImageCamera;
+
+/// How much a rendered image may differ from its reference image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoldenImageTolerance {
+    /// The largest difference between a color channel of the two images, from 0 to 255, that
+    /// doesn't count as a mismatch.
+    pub channel: u8,
+    /// The largest fraction of mismatched pixels, from 0 to 1, that still passes the test.
+    pub mismatched_pixels: f32,
+}
+
+impl Default for GoldenImageTolerance {
+    /// Allows small differences in every pixel, such as from different GPUs dithering or
+    /// rounding differently, but no larger ones.
+    fn default() -> Self {
+        Self {
+            channel: 2,
+            mismatched_pixels: 0.0,
+        }
+    }
+}
+
+/// The differences found by [`compare_images`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GoldenImageComparison {
+    /// The number of pixels that differ by more than the channel tolerance.
+    pub mismatched_pixels: usize,
+    /// The number of pixels compared.
+    pub total_pixels: usize,
+    /// The largest difference between a color channel of the two images.
+    pub max_difference: u8,
+}
+
+impl GoldenImageComparison {
+    /// Returns whether the images are within `tolerance` of each other.
+    pub fn passes(&self, tolerance: GoldenImageTolerance) -> bool {
+        self.mismatched_pixels as f32
+            <= tolerance.mismatched_pixels * self.total_pixels.max(1) as f32
+    }
+}
+
+/// Compares two RGBA8 images of the same size, counting the pixels with a color channel that
+/// differs by more than `channel_tolerance`.
+///
+/// ```
+/// # use bevy_dev_tools::golden_image::compare_images;
+/// let expected = [0, 0, 0, 255, 255, 255, 255, 255];
+/// let actual = [1, 0, 0, 255, 200, 255, 255, 255];
+/// let comparison = compare_images(&expected, &actual, 2);
+/// assert_eq!(comparison.mismatched_pixels, 1);
+/// assert_eq!(comparison.max_difference, 55);
+/// ```
+pub fn compare_images(
+    expected: &[u8],
+    actual: &[u8],
+    channel_tolerance: u8,
+) -> GoldenImageComparison {
+    let mut comparison = GoldenImageComparison::default();
+    for (expected, actual) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        let difference = expected
+            .iter()
+            .zip(actual)
+            .map(|(expected, actual)| expected.abs_diff(*actual))
+            .max()
+            .unwrap_or(0);
+        comparison.total_pixels += 1;
+        comparison.max_difference = comparison.max_difference.max(difference);
+        if difference > channel_tolerance {
+            comparison.mismatched_pixels += 1;
+        }
+    }
+    comparison
+}
+
+/// An error from a [`GoldenImageTest`].
+#[derive(Debug, Error)]
+pub enum GoldenImageError {
+    /// There is no entity with both a [`Camera`] and a [`GoldenImageCamera`].
+    #[error("no camera is marked with GoldenImageCamera")]
+    NoCamera,
+    /// The rendered image wasn't read back in time.
+    #[error("the rendered image wasn't read back within {0} frames")]
+    Timeout(u32),
+    /// The rendered image couldn't be read back.
+    #[error(transparent)]
+    Readback(#[from] ReadbackError),
+    /// There is no reference image. The rendered image is written next to where it should be, to
+    /// review it.
+    #[error(
+        "there is no reference image at {}, see {} or set {UPDATE_GOLDEN_IMAGES} to write it",
+        reference_path.display(),
+        actual_path.display()
+    )]
+    MissingReference {
+        /// Where the reference image should be.
+        reference_path: PathBuf,
+        /// Where the rendered image was written.
+        actual_path: PathBuf,
+    },
+    /// The reference image has a different size than the test.
+    #[error("the reference image is {expected}, but the test renders {actual}")]
+    SizeMismatch {
+        /// The size of the reference image.
+        expected: UVec2,
+        /// The size of the rendered image.
+        actual: UVec2,
+    },
+    /// The rendered image differs too much from the reference image. It is written next to the
+    /// reference image to compare them.
+    #[error(
+        "{} of {} pixels differ from the reference image, by up to {}, see {}",
+        comparison.mismatched_pixels,
+        comparison.total_pixels,
+        comparison.max_difference,
+        actual_path.display()
+    )]
+    Mismatch {
+        /// The differences between the images.
+        comparison: GoldenImageComparison,
+        /// Where the rendered image was written.
+        actual_path: PathBuf,
+    },
+    /// An image couldn't be read or written.
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Renders a scene offscreen and compares it to a reference image, see the
+/// [module docs](self).
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_dev_tools::golden_image::{GoldenImageCamera, GoldenImageTest};
+/// # use bevy_ecs::prelude::*;
+/// # fn rendering_plugins(app: &mut App) {}
+/// # fn spawn_scene(mut commands: Commands) {}
+/// let mut app = App::new();
+/// // Add the rendering plugins without a window, and a system spawning the scene with a
+/// // `GoldenImageCamera` on the camera to render
+/// rendering_plugins(&mut app);
+/// app.add_systems(bevy_app::Startup, spawn_scene);
+///
+/// GoldenImageTest::new("ui_material").run(&mut app).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct GoldenImageTest {
+    /// The name of the test, which is the name of the reference image without its extension.
+    pub name: String,
+    /// The size of the rendered image.
+    pub size: UVec2,
+    /// The number of frames to render before reading the image back, so that assets can load.
+    pub warmup_frames: u32,
+    /// The number of frames to wait for the image to be read back.
+    pub timeout_frames: u32,
+    /// How much the rendered image may differ from the reference image.
+    pub tolerance: GoldenImageTolerance,
+    /// The directory of the reference images, relative to the working directory of the test.
+    pub reference_dir: PathBuf,
+}
+
+impl GoldenImageTest {
+    /// Creates a test rendering a 256 by 256 image, compared to `tests/golden_images/{name}.png`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            size: UVec2::splat(256),
+            warmup_frames: 10,
+            timeout_frames: 60,
+            tolerance: GoldenImageTolerance::default(),
+            reference_dir: PathBuf::from("tests/golden_images"),
+        }
+    }
+
+    /// Renders an image of `size` pixels.
+    pub fn with_size(mut self, size: UVec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Compares the images with `tolerance`.
+    pub fn with_tolerance(mut self, tolerance: GoldenImageTolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Returns the path of the reference image.
+    pub fn reference_path(&self) -> PathBuf {
+        self.reference_dir.join(format!("{}.png", self.name))
+    }
+
+    /// Renders `app` and compares the image to the reference image, returning the differences
+    /// when it passes.
+    ///
+    /// The app must not have been run yet, and must not have a runner that opens a window.
+    pub fn run(&self, app: &mut App) -> Result<GoldenImageComparison, GoldenImageError> {
+        while app.plugins_state() == PluginsState::Adding {
+            #[cfg(not(target_arch = "wasm32"))]
+            bevy_tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+        // Spawn the scene
+        app.update();
+
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(self.create_image());
+        let world = app.world_mut();
+        let mut cameras = world.query_filtered::<(Entity, &mut Camera), With<GoldenImageCamera>>();
+        let (camera, mut camera_component) = cameras
+            .iter_mut(world)
+            .next()
+            .ok_or(GoldenImageError::NoCamera)?;
+        camera_component.target = RenderTarget::Image(image.clone());
+
+        for _ in 0..self.warmup_frames {
+            app.update();
+        }
+
+        app.world_mut()
+            .entity_mut(camera)
+            .insert(ReadbackTexture(image));
+        let mut reader = app
+            .world()
+            .resource::<Events<ReadbackComplete>>()
+            .get_reader_current();
+        let mut failed_reader = app
+            .world()
+            .resource::<Events<ReadbackFailed>>()
+            .get_reader_current();
+        let mut data = None;
+        for _ in 0..self.timeout_frames {
+            app.update();
+            let failed = app.world().resource::<Events<ReadbackFailed>>();
+            // Readbacks over budget are requested again next frame
+            if let Some(failed) = failed_reader
+                .read(failed)
+                .find(|failed| failed.entity == camera && failed.error != ReadbackError::Deferred)
+            {
+                return Err(failed.error.clone().into());
+            }
+            let events = app.world().resource::<Events<ReadbackComplete>>();
+            data = reader
+                .read(events)
+                .filter(|readback| readback.entity == camera)
+                .last()
+                .map(|readback| readback.data.clone());
+            if data.is_some() {
+                break;
+            }
+        }
+        let data = data.ok_or(GoldenImageError::Timeout(self.timeout_frames))?;
+
+        self.compare(data)
+    }
+
+    fn create_image(&self) -> Image {
+        let size = Extent3d {
+            width: self.size.x,
+            height: self.size.y,
+            depth_or_array_layers: 1,
+        };
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: Some("golden_image"),
+                size,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8UnormSrgb,
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::COPY_SRC
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+            ..Default::default()
+        };
+        image.resize(size);
+        image
+    }
+
+    fn compare(&self, data: Vec<u8>) -> Result<GoldenImageComparison, GoldenImageError> {
+        let reference_path = self.reference_path();
+        if env::var_os(UPDATE_GOLDEN_IMAGES).is_some() {
+            self.save(&reference_path, &data)?;
+            return Ok(GoldenImageComparison::default());
+        }
+        let actual_path = reference_path.with_extension("actual.png");
+        if !reference_path.exists() {
+            self.save(&actual_path, &data)?;
+            return Err(GoldenImageError::MissingReference {
+                reference_path,
+                actual_path,
+            });
+        }
+
+        let reference = image::open(&reference_path)?.into_rgba8();
+        let reference_size = UVec2::new(reference.width(), reference.height());
+        if reference_size != self.size {
+            return Err(GoldenImageError::SizeMismatch {
+                expected: reference_size,
+                actual: self.size,
+            });
+        }
+
+        let comparison = compare_images(reference.as_raw(), &data, self.tolerance.channel);
+        if comparison.passes(self.tolerance) {
+            return Ok(comparison);
+        }
+        self.save(&actual_path, &data)?;
+        Err(GoldenImageError::Mismatch {
+            comparison,
+            actual_path,
+        })
+    }
+
+    fn save(&self, path: &Path, data: &[u8]) -> Result<(), GoldenImageError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        image::save_buffer(
+            path,
+            data,
+            self.size.x,
+            self.size.y,
+            image::ExtendedColorType::Rgba8,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: GoldenImageTolerance = GoldenImageTolerance {
+        channel: 2,
+        mismatched_pixels: 0.25,
+    };
+
+    #[test]
+    fn identical_images() {
+        let image = [
+            10, 20, 30, 255, 40, 50, 60, 255, 70, 80, 90, 255, 0, 0, 0, 0,
+        ];
+        let comparison = compare_images(&image, &image, TOLERANCE.channel);
+
+        assert_eq!(
+            comparison,
+            GoldenImageComparison {
+                mismatched_pixels: 0,
+                total_pixels: 4,
+                max_difference: 0,
+            }
+        );
+        assert!(comparison.passes(TOLERANCE));
+    }
+
+    #[test]
+    fn images_within_tolerance() {
+        let expected = [
+            10, 20, 30, 255, 40, 50, 60, 255, 70, 80, 90, 255, 0, 0, 0, 0,
+        ];
+        // Every pixel is off by at most the channel tolerance, except one of the four pixels
+        let actual = [
+            12, 18, 30, 255, 40, 51, 60, 254, 70, 80, 90, 255, 0, 0, 100, 0,
+        ];
+        let comparison = compare_images(&expected, &actual, TOLERANCE.channel);
+
+        assert_eq!(comparison.mismatched_pixels, 1);
+        assert_eq!(comparison.total_pixels, 4);
+        assert_eq!(comparison.max_difference, 100);
+        assert!(comparison.passes(TOLERANCE));
+    }
+
+    #[test]
+    fn images_over_tolerance() {
+        let expected = [
+            10, 20, 30, 255, 40, 50, 60, 255, 70, 80, 90, 255, 0, 0, 0, 0,
+        ];
+        let actual = [
+            13, 20, 30, 255, 40, 50, 60, 255, 70, 80, 90, 255, 0, 0, 0, 255,
+        ];
+        let comparison = compare_images(&expected, &actual, TOLERANCE.channel);
+
+        assert_eq!(comparison.mismatched_pixels, 2);
+        assert_eq!(comparison.max_difference, 255);
+        assert!(!comparison.passes(TOLERANCE));
+        assert!(!comparison.passes(GoldenImageTolerance::default()));
+    }
+
+    #[test]
+    fn missing_reference_image() {
+        if env::var_os(UPDATE_GOLDEN_IMAGES).is_some() {
+            return;
+        }
+        let mut test = GoldenImageTest::new("missing").with_size(UVec2::new(2, 1));
+        test.reference_dir = env::temp_dir().join(format!("bevy_golden_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&test.reference_dir);
+
+        let result = test.compare(vec![255, 0, 0, 255, 0, 0, 255, 255]);
+
+        let Err(GoldenImageError::MissingReference {
+            reference_path,
+            actual_path,
+        }) = result
+        else {
+            panic!("expected a missing reference image, got {result:?}");
+        };
+        assert!(!reference_path.exists());
+        let actual = image::open(&actual_path).unwrap().into_rgba8();
+        assert_eq!(actual.as_raw(), &[255, 0, 0, 255, 0, 0, 255, 255]);
+        std::fs::remove_dir_all(&test.reference_dir).unwrap();
+    }
+}
@@ -13,7 +13,13 @@ use bevy_app::prelude::*;
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "bevy_golden_image_testing")]
+pub mod golden_image;
+
+pub mod diagnostics_overlay;
 pub mod fps_overlay;
+pub mod frame_time_overlay;
+pub mod log_console;
 
 #[cfg(feature = "bevy_ui_debug")]
 pub mod ui_debug_overlay;
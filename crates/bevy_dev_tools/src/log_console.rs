@@ -0,0 +1,392 @@
+//! Module containing logic for the in-app log console.
+
+use bevy_app::{Plugin, Startup, Update};
+use bevy_asset::Handle;
+use bevy_color::{Alpha, Color};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::Component,
+    event::EventReader,
+    query::{With, Without},
+    schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    mouse::{MouseScrollUnit, MouseWheel},
+    ButtonInput, ButtonState,
+};
+use bevy_log::{warn_once, Level, LogRecord, RecentLogs};
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::default;
+
+/// Global [`ZIndex`] used to render the log console.
+///
+/// This is below the [FPS overlay](crate::fps_overlay::FPS_OVERLAY_ZINDEX), so both can be shown
+/// at the same time.
+pub const LOG_CONSOLE_ZINDEX: i32 = i32::MAX - 64;
+
+/// A plugin that adds a toggleable console showing the recent log records of the application.
+///
+/// The records are read from the [`RecentLogs`] resource, which is only added by the
+/// [`LogPlugin`](bevy_log::LogPlugin) when
+/// [`LogPlugin::recent_records`](bevy_log::LogPlugin::recent_records) is set, as it defaults to
+/// `0`. It is also the number of records available for scrollback:
+///
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup};
+/// # use bevy_dev_tools::log_console::LogConsolePlugin;
+/// # use bevy_log::LogPlugin;
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins.set(LogPlugin {
+///             recent_records: 1000,
+///             ..Default::default()
+///         }),
+///         LogConsolePlugin::default(),
+///     ))
+///     .run();
+/// ```
+///
+/// While the console is open:
+/// - Typing searches the messages and targets of the records, and `Backspace` and `Escape` edit
+///   the search.
+/// - `Tab` cycles through the lowest [`Level`] shown.
+/// - `PageUp`, `PageDown` and the mouse wheel scroll through older records.
+///
+/// Filters can also be set through the [`LogConsole`] resource, such as the
+/// [`target_filter`](LogConsole::target_filter) that has no key binding.
+#[derive(Default)]
+pub struct LogConsolePlugin {
+    /// Starting configuration of the console, this can be later be changed through the
+    /// [`LogConsoleConfig`] resource.
+    pub config: LogConsoleConfig,
+}
+
+impl Plugin for LogConsolePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<LogConsole>()
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    toggle_console,
+                    handle_input,
+                    customize_console.run_if(resource_changed::<LogConsoleConfig>),
+                    update_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configuration options for the log console.
+#[derive(Resource, Clone)]
+pub struct LogConsoleConfig {
+    /// The key that opens and closes the console.
+    pub toggle_key: KeyCode,
+    /// The number of records shown at once.
+    pub visible_lines: usize,
+    /// Configuration of text in the console. The color is used for the messages.
+    pub text_config: TextStyle,
+    /// The color behind the console.
+    pub background_color: Color,
+    /// The colors of the level and target of records, indexed from [`Level::ERROR`] to
+    /// [`Level::TRACE`].
+    pub level_colors: [Color; 5],
+}
+
+impl Default for LogConsoleConfig {
+    fn default() -> Self {
+        LogConsoleConfig {
+            toggle_key: KeyCode::Backquote,
+            visible_lines: 20,
+            text_config: TextStyle {
+                font: Handle::<Font>::default(),
+                font_size: 16.0,
+                color: Color::WHITE,
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+            level_colors: [
+                Color::srgb(1.0, 0.3, 0.3),
+                Color::srgb(1.0, 0.8, 0.2),
+                Color::srgb(0.4, 0.9, 0.4),
+                Color::srgb(0.4, 0.7, 1.0),
+                Color::srgb(0.7, 0.5, 1.0),
+            ],
+        }
+    }
+}
+
+impl LogConsoleConfig {
+    /// Returns the color used for records of `level`.
+    pub fn level_color(&self, level: Level) -> Color {
+        self.level_colors[level_index(level)]
+    }
+}
+
+/// The state of the log console, which can be changed to control it from code.
+#[derive(Resource, Debug, Clone)]
+pub struct LogConsole {
+    /// Whether the console is shown.
+    pub open: bool,
+    /// The lowest level of records shown, so [`Level::TRACE`] shows all records.
+    pub min_level: Level,
+    /// Only records whose target starts with this are shown, such as `bevy_render`.
+    pub target_filter: String,
+    /// Only records whose message or target contain this are shown, ignoring case.
+    pub search: String,
+    /// The number of matching records hidden below the console, to look at older records.
+    pub scroll: usize,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self {
+            open: false,
+            min_level: Level::TRACE,
+            target_filter: String::new(),
+            search: String::new(),
+            scroll: 0,
+        }
+    }
+}
+
+impl LogConsole {
+    /// Returns whether `record` passes the filters of the console.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        if record.level > self.min_level || !record.target.starts_with(&self.target_filter) {
+            return false;
+        }
+        if self.search.is_empty() {
+            return true;
+        }
+        let search = self.search.to_lowercase();
+        record.message.to_lowercase().contains(&search)
+            || record.target.to_lowercase().contains(&search)
+    }
+}
+
+/// Returns the index of `level`, from `0` for [`Level::ERROR`] to `4` for [`Level::TRACE`].
+fn level_index(level: Level) -> usize {
+    match level {
+        Level::ERROR => 0,
+        Level::WARN => 1,
+        Level::INFO => 2,
+        Level::DEBUG => 3,
+        Level::TRACE => 4,
+    }
+}
+
+#[derive(Component)]
+struct LogConsoleRoot;
+
+#[derive(Component)]
+struct LogConsoleRecords;
+
+#[derive(Component)]
+struct LogConsoleStatus;
+
+fn setup(mut commands: Commands, config: Res<LogConsoleConfig>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // We need to make sure the console doesn't affect the position of other UI nodes
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: config.background_color.into(),
+                // Render the console on top of everything but the FPS overlay
+                z_index: ZIndex::Global(LOG_CONSOLE_ZINDEX),
+                ..default()
+            },
+            LogConsoleRoot,
+        ))
+        .with_children(|c| {
+            c.spawn((TextBundle::default(), LogConsoleRecords));
+            c.spawn((TextBundle::default(), LogConsoleStatus));
+        });
+}
+
+fn toggle_console(
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<LogConsoleConfig>,
+    mut console: ResMut<LogConsole>,
+    mut roots: Query<&mut Style, With<LogConsoleRoot>>,
+) {
+    if keys.just_pressed(config.toggle_key) {
+        console.open = !console.open;
+    }
+    if !console.is_changed() {
+        return;
+    }
+    for mut style in &mut roots {
+        style.display = if console.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn handle_input(
+    config: Res<LogConsoleConfig>,
+    mut console: ResMut<LogConsole>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut scroll_events: EventReader<MouseWheel>,
+) {
+    if !console.open {
+        keyboard_events.clear();
+        scroll_events.clear();
+        return;
+    }
+
+    let page = config.visible_lines.max(1);
+    for event in keyboard_events.read() {
+        // The toggle key would otherwise be typed into the search when opening the console
+        if event.state != ButtonState::Pressed || event.key_code == config.toggle_key {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(characters) => {
+                console.search.push_str(characters);
+                console.scroll = 0;
+            }
+            Key::Space => console.search.push(' '),
+            Key::Backspace => {
+                console.search.pop();
+            }
+            Key::Escape => console.search.clear(),
+            Key::Tab => {
+                console.min_level = match console.min_level {
+                    Level::TRACE => Level::DEBUG,
+                    Level::DEBUG => Level::INFO,
+                    Level::INFO => Level::WARN,
+                    Level::WARN => Level::ERROR,
+                    Level::ERROR => Level::TRACE,
+                };
+                console.scroll = 0;
+            }
+            Key::PageUp => console.scroll += page,
+            Key::PageDown => console.scroll = console.scroll.saturating_sub(page),
+            _ => {}
+        }
+    }
+
+    let mut lines = 0.0;
+    for event in scroll_events.read() {
+        lines += match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 16.0,
+        };
+    }
+    let lines = lines.round() as isize;
+    if lines != 0 {
+        console.scroll = console.scroll.saturating_add_signed(lines);
+    }
+}
+
+fn customize_console(
+    config: Res<LogConsoleConfig>,
+    mut console: ResMut<LogConsole>,
+    mut roots: Query<&mut BackgroundColor, With<LogConsoleRoot>>,
+) {
+    for mut background_color in &mut roots {
+        *background_color = config.background_color.into();
+    }
+    // Redraw the text with the new style
+    console.set_changed();
+}
+
+fn update_text(
+    config: Res<LogConsoleConfig>,
+    recent_logs: Option<Res<RecentLogs>>,
+    mut console: ResMut<LogConsole>,
+    mut last_total: Local<Option<u64>>,
+    mut records_text: Query<&mut Text, With<LogConsoleRecords>>,
+    mut status_text: Query<&mut Text, (With<LogConsoleStatus>, Without<LogConsoleRecords>)>,
+) {
+    if !console.open {
+        return;
+    }
+    let Some(recent_logs) = recent_logs else {
+        warn_once!(
+            "The log console needs the RecentLogs resource, set `LogPlugin::recent_records` to add it"
+        );
+        return;
+    };
+
+    let total = recent_logs.total();
+    if !console.is_changed() && *last_total == Some(total) {
+        return;
+    }
+    *last_total = Some(total);
+
+    let mut matching = Vec::new();
+    recent_logs.read_since(0, |record| {
+        if console.matches(record) {
+            matching.push(record.clone());
+        }
+    });
+
+    // Don't scroll past the oldest record
+    let page = config.visible_lines.max(1);
+    let max_scroll = matching.len().saturating_sub(page);
+    if console.scroll > max_scroll {
+        console.bypass_change_detection().scroll = max_scroll;
+    }
+    let end = matching.len() - console.scroll;
+    let start = end.saturating_sub(page);
+
+    let mut sections = Vec::with_capacity((end - start) * 2);
+    for record in &matching[start..end] {
+        sections.push(TextSection::new(
+            format!("{:>5} {}: ", record.level.as_str(), record.target),
+            TextStyle {
+                color: config.level_color(record.level),
+                ..config.text_config.clone()
+            },
+        ));
+        sections.push(TextSection::new(
+            format!("{}\n", record.message),
+            config.text_config.clone(),
+        ));
+    }
+    for mut text in &mut records_text {
+        text.sections.clone_from(&sections);
+    }
+
+    let status = format!(
+        "Level: {} and above (Tab) | Target: {} | Search: {}_ | Showing {}-{} of {} (PageUp/PageDown)",
+        console.min_level,
+        if console.target_filter.is_empty() {
+            "any"
+        } else {
+            &console.target_filter
+        },
+        console.search,
+        if end > 0 { start + 1 } else { 0 },
+        end,
+        matching.len(),
+    );
+    for mut text in &mut status_text {
+        text.sections = vec![TextSection::new(
+            status.clone(),
+            TextStyle {
+                color: config.text_config.color.with_alpha(0.6),
+                ..config.text_config.clone()
+            },
+        )];
+    }
+}
@@ -12,6 +12,7 @@ use bevy_ecs::{
     schedule::IntoSystemConfigs,
     system::{Query, Res},
 };
+use bevy_math::Quat;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::primitives::Aabb;
 use bevy_transform::{
@@ -59,6 +60,12 @@ pub struct AabbGizmoConfigGroup {
     ///
     /// Defaults to `None`.
     pub default_color: Option<Color>,
+    /// Also draws the bounding sphere of each box when set to `true`.
+    ///
+    /// This is the sphere used for frustum culling.
+    ///
+    /// Defaults to `false`.
+    pub draw_sphere_bounds: bool,
 }
 
 /// Add this [`Component`] to an entity to draw its [`Aabb`] component.
@@ -80,7 +87,7 @@ fn draw_aabbs(
             .color
             .or(gizmos.config_ext.default_color)
             .unwrap_or_else(|| color_from_entity(entity));
-        gizmos.cuboid(aabb_transform(aabb, transform), color);
+        draw_aabb(&mut gizmos, aabb, transform, color);
     }
 }
 
@@ -93,7 +100,22 @@ fn draw_all_aabbs(
             .config_ext
             .default_color
             .unwrap_or_else(|| color_from_entity(entity));
-        gizmos.cuboid(aabb_transform(aabb, transform), color);
+        draw_aabb(&mut gizmos, aabb, transform, color);
+    }
+}
+
+fn draw_aabb(
+    gizmos: &mut Gizmos<AabbGizmoConfigGroup>,
+    aabb: Aabb,
+    transform: GlobalTransform,
+    color: Color,
+) {
+    gizmos.cuboid(aabb_transform(aabb, transform), color);
+
+    if gizmos.config_ext.draw_sphere_bounds {
+        let center = transform.transform_point(aabb.center.into());
+        let radius = transform.radius_vec3a(aabb.half_extents);
+        gizmos.sphere(center, Quat::IDENTITY, radius, color);
     }
 }
 
@@ -6,8 +6,8 @@
 use crate::circles::DEFAULT_CIRCLE_RESOLUTION;
 use crate::prelude::{GizmoConfigGroup, Gizmos};
 use bevy_color::Color;
-use bevy_math::{Quat, Vec2, Vec3};
-use std::f32::consts::TAU;
+use bevy_math::{Dir3, Quat, Vec2, Vec3};
+use std::{f32::consts::TAU, iter};
 
 // === 2D ===
 
@@ -28,6 +28,11 @@ where
     /// - `radius` controls the distance from `position` to this arc, and thus its curvature.
     /// - `color` sets the color to draw the arc.
     ///
+    /// # Builder methods
+    /// - The resolution of the arc (i.e. the level of detail) can be adjusted with the
+    /// `.resolution(...)` method.
+    /// - The arc can be closed into the outline of a circular sector with the `.sector()` method.
+    ///
     /// # Example
     /// ```
     /// # use bevy_gizmos::prelude::*;
@@ -63,6 +68,7 @@ where
             radius,
             color: color.into(),
             resolution: None,
+            sector: false,
         }
     }
 }
@@ -80,6 +86,7 @@ where
     radius: f32,
     color: Color,
     resolution: Option<usize>,
+    sector: bool,
 }
 
 impl<Config, Clear> Arc2dBuilder<'_, '_, '_, Config, Clear>
@@ -92,6 +99,13 @@ where
         self.resolution.replace(resolution);
         self
     }
+
+    /// Draw the outline of the circular sector of this arc, by connecting both ends of the arc
+    /// to its center.
+    pub fn sector(mut self) -> Self {
+        self.sector = true;
+        self
+    }
 }
 
 impl<Config, Clear> Drop for Arc2dBuilder<'_, '_, '_, Config, Clear>
@@ -115,7 +129,14 @@ where
             resolution,
         )
         .map(|vec2| (vec2 + self.position));
-        self.gizmos.linestrip_2d(positions, self.color);
+
+        if self.sector {
+            let center = iter::once(self.position);
+            self.gizmos
+                .linestrip_2d(center.clone().chain(positions).chain(center), self.color);
+        } else {
+            self.gizmos.linestrip_2d(positions, self.color);
+        }
     }
 }
 
@@ -160,8 +181,9 @@ where
     /// - `color`: color of the arc
     ///
     /// # Builder methods
-    /// The resolution of the arc (i.e. the level of detail) can be adjusted with the
+    /// - The resolution of the arc (i.e. the level of detail) can be adjusted with the
     /// `.resolution(...)` method.
+    /// - The arc can be closed into the outline of a circular sector with the `.sector()` method.
     ///
     /// # Example
     /// ```
@@ -204,6 +226,7 @@ where
             radius,
             color: color.into(),
             resolution: None,
+            sector: false,
         }
     }
 
@@ -217,8 +240,9 @@ where
     /// - `color`: color of the arc
     ///
     /// # Builder methods
-    /// The resolution of the arc (i.e. the level of detail) can be adjusted with the
+    /// - The resolution of the arc (i.e. the level of detail) can be adjusted with the
     /// `.resolution(...)` method.
+    /// - The arc can be closed into the outline of a circular sector with the `.sector()` method.
     ///
     /// # Examples
     /// ```
@@ -264,8 +288,9 @@ where
     /// - `color`: color of the arc
     ///
     /// # Builder methods
-    /// The resolution of the arc (i.e. the level of detail) can be adjusted with the
+    /// - The resolution of the arc (i.e. the level of detail) can be adjusted with the
     /// `.resolution(...)` method.
+    /// - The arc can be closed into the outline of a circular sector with the `.sector()` method.
     ///
     /// # Examples
     /// ```
@@ -310,6 +335,62 @@ where
         })
     }
 
+    /// Draws the shortest arc of radius `radius` around `center`, between the directions `from`
+    /// and `to`.
+    ///
+    /// This is useful to visualize angles, such as the field of view of an observer or the
+    /// difference between two rotations.
+    ///
+    /// # Arguments
+    /// - `center`: The center point around which the arc is drawn.
+    /// - `from`: The direction from `center` to the start of the arc.
+    /// - `to`: The direction from `center` to the end of the arc.
+    /// - `radius`: The distance between the arc and `center`.
+    /// - `color`: color of the arc
+    ///
+    /// # Builder methods
+    /// - The resolution of the arc (i.e. the level of detail) can be adjusted with the
+    /// `.resolution(...)` method.
+    /// - The arc can be closed into the outline of a circular sector with the `.sector()` method.
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::css::ORANGE;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A 90 degree field of view, looking along `Vec3::NEG_Z`
+    ///     let left = Dir3::new(Vec3::new(-1., 0., -1.)).unwrap();
+    ///     let right = Dir3::new(Vec3::new(1., 0., -1.)).unwrap();
+    ///     gizmos
+    ///         .arc_3d_between(Vec3::ZERO, left, right, 2., ORANGE)
+    ///         .sector()
+    ///         .resolution(16);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    ///
+    /// # Notes
+    /// - If `from` and `to` point in opposite directions, the plane of the arc is ambiguous.
+    #[inline]
+    pub fn arc_3d_between(
+        &mut self,
+        center: Vec3,
+        from: Dir3,
+        to: Dir3,
+        radius: f32,
+        color: impl Into<Color>,
+    ) -> Arc3dBuilder<'_, 'w, 's, Config, Clear> {
+        self.arc_from_to(
+            center,
+            center + from * radius,
+            center + to * radius,
+            color,
+            |x| x,
+        )
+    }
+
     #[inline]
     fn arc_from_to(
         &mut self,
@@ -340,6 +421,7 @@ where
             radius,
             color: color.into(),
             resolution: None,
+            sector: false,
         }
     }
 }
@@ -367,6 +449,7 @@ where
     radius: f32,
     color: Color,
     resolution: Option<usize>,
+    sector: bool,
 }
 
 impl<Config, Clear> Arc3dBuilder<'_, '_, '_, Config, Clear>
@@ -379,6 +462,13 @@ where
         self.resolution.replace(resolution);
         self
     }
+
+    /// Draw the outline of the circular sector of this arc, by connecting both ends of the arc
+    /// to its center.
+    pub fn sector(mut self) -> Self {
+        self.sector = true;
+        self
+    }
 }
 
 impl<Config, Clear> Drop for Arc3dBuilder<'_, '_, '_, Config, Clear>
@@ -403,7 +493,14 @@ where
             self.radius,
             resolution,
         );
-        self.gizmos.linestrip(positions, self.color);
+
+        if self.sector {
+            let center = iter::once(self.center);
+            self.gizmos
+                .linestrip(center.clone().chain(positions).chain(center), self.color);
+        } else {
+            self.gizmos.linestrip(positions, self.color);
+        }
     }
 }
 
@@ -1,6 +1,6 @@
 //! Additional [`Gizmos`] Functions -- Arrows
 //!
-//! Includes the implementation of [`Gizmos::arrow`] and [`Gizmos::arrow_2d`],
+//! Includes the implementation of [`Gizmos::arrow`], [`Gizmos::arrow_2d`] and [`Gizmos::axes`],
 //! and assorted support items.
 
 use crate::prelude::{GizmoConfigGroup, Gizmos};
@@ -172,8 +172,17 @@ where
     /// Draw a set of axes local to the given transform (`transform`), with length scaled by a factor
     /// of `base_length`.
     ///
+    /// The X, Y and Z axes are drawn as red, green and blue arrows respectively.
+    ///
     /// This should be called for each frame the axes need to be rendered.
     ///
+    /// To draw the axes of specific entities without writing a system, add the
+    /// [`ShowAxesGizmo`](crate::axes::ShowAxesGizmo) component to them.
+    ///
+    /// # Builder methods
+    ///
+    /// - The arrows can be labeled with the name of their axis using `.labels()`.
+    ///
     /// # Example
     /// ```
     /// # use bevy_gizmos::prelude::*;
@@ -186,19 +195,79 @@ where
     ///     query: Query<&Transform, With<MyComponent>>,
     /// ) {
     ///     for &transform in &query {
-    ///         gizmos.axes(transform, 1.);
+    ///         gizmos.axes(transform, 1.).labels();
     ///     }
     /// }
     /// # bevy_ecs::system::assert_is_system(draw_axes);
     /// ```
-    pub fn axes(&mut self, transform: impl TransformPoint, base_length: f32) {
-        let start = transform.transform_point(Vec3::ZERO);
-        let end_x = transform.transform_point(base_length * Vec3::X);
-        let end_y = transform.transform_point(base_length * Vec3::Y);
-        let end_z = transform.transform_point(base_length * Vec3::Z);
+    pub fn axes(
+        &mut self,
+        transform: impl TransformPoint,
+        base_length: f32,
+    ) -> AxesBuilder<'_, 'w, 's, Config, Clear> {
+        AxesBuilder {
+            gizmos: self,
+            start: transform.transform_point(Vec3::ZERO),
+            ends: [
+                transform.transform_point(base_length * Vec3::X),
+                transform.transform_point(base_length * Vec3::Y),
+                transform.transform_point(base_length * Vec3::Z),
+            ],
+            labels: false,
+        }
+    }
+}
 
-        self.arrow(start, end_x, RED);
-        self.arrow(start, end_y, GREEN);
-        self.arrow(start, end_z, BLUE);
+/// A builder returned by [`Gizmos::axes`].
+pub struct AxesBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    start: Vec3,
+    ends: [Vec3; 3],
+    labels: bool,
+}
+
+impl<Config, Clear> AxesBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Label the tip of each arrow with the name of its axis.
+    ///
+    /// The labels are sized relative to the length of their arrow and are laid out in the
+    /// world XY plane.
+    pub fn labels(mut self) -> Self {
+        self.labels = true;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for AxesBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let start = self.start;
+        for (end, label, color) in [
+            (self.ends[0], "X", RED),
+            (self.ends[1], "Y", GREEN),
+            (self.ends[2], "Z", BLUE),
+        ] {
+            self.gizmos.arrow(start, end, color);
+            if self.labels {
+                let axis = end - start;
+                self.gizmos
+                    .text(end + 0.15 * axis, label, color)
+                    .size(0.15 * axis.length());
+            }
+        }
     }
 }
@@ -0,0 +1,78 @@
+//! A module adding debug visualization of the local coordinate frame of entities.
+
+use crate as bevy_gizmos;
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component, reflect::ReflectComponent, schedule::IntoSystemConfigs, system::Query,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{config::GizmoConfigGroup, gizmos::Gizmos, AppGizmoBuilder};
+
+/// A [`Plugin`] that draws the axes of entities with a [`ShowAxesGizmo`] component.
+pub struct AxesGizmoPlugin;
+
+impl Plugin for AxesGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<AxesGizmoConfigGroup>()
+            .register_type::<ShowAxesGizmo>()
+            .init_gizmo_group::<AxesGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                draw_axes.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to draw the axes of entities with a [`ShowAxesGizmo`] component.
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+pub struct AxesGizmoConfigGroup {
+    /// Labels the tip of each axis with its name when set to `true`.
+    ///
+    /// Defaults to `false`.
+    pub labels: bool,
+}
+
+/// Add this [`Component`] to an entity to draw the X, Y and Z axes of its [`GlobalTransform`].
+///
+/// This is useful to diagnose orientation issues, see [`Gizmos::axes`].
+///
+/// # Example
+/// ```
+/// # use bevy_gizmos::axes::ShowAxesGizmo;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((TransformBundle::default(), ShowAxesGizmo { length: 2. }));
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component, Default)]
+pub struct ShowAxesGizmo {
+    /// The length of the axes, before the scale of the transform is applied.
+    ///
+    /// Defaults to `1.0`.
+    pub length: f32,
+}
+
+impl Default for ShowAxesGizmo {
+    fn default() -> Self {
+        Self { length: 1. }
+    }
+}
+
+fn draw_axes(
+    query: Query<(&GlobalTransform, &ShowAxesGizmo)>,
+    mut gizmos: Gizmos<AxesGizmoConfigGroup>,
+) {
+    let labels = gizmos.config_ext.labels;
+    for (&transform, axes) in &query {
+        let builder = gizmos.axes(transform, axes.length);
+        if labels {
+            builder.labels();
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! A module for capturing gizmos into a [`Mesh`].
+//!
+//! Captured gizmos are not rendered as gizmos. Instead, their lines are turned into a [`Mesh`]
+//! with a line list topology, so a debug visualization can be frozen, saved or rendered with a
+//! regular material.
+
+use bevy_color::LinearRgba;
+use bevy_math::Vec3;
+use bevy_render::{
+    mesh::{Mesh, PrimitiveTopology},
+    render_asset::RenderAssetUsages,
+};
+
+use crate::{config::GizmoConfigGroup, gizmos::Gizmos};
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Capture the gizmos drawn with the [`Gizmos`] passed to `draw` into a [`Mesh`], instead of
+    /// rendering them.
+    ///
+    /// The returned mesh uses [`PrimitiveTopology::LineList`] and has the
+    /// [`Mesh::ATTRIBUTE_POSITION`] and [`Mesh::ATTRIBUTE_COLOR`] attributes. Line strips are
+    /// split into separate segments. Line widths and other per-line settings such as
+    /// [`Gizmos::screen`] are not part of the mesh. Filled shapes, such as
+    /// [`Gizmos::polygon_filled_2d`], are discarded.
+    ///
+    /// If the gizmos are disabled, the returned mesh is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_asset::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{GREEN, RED};
+    /// fn freeze_path(mut gizmos: Gizmos, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = gizmos.capture(|gizmos| {
+    ///         gizmos.linestrip([Vec3::ZERO, Vec3::X, Vec3::Y], GREEN);
+    ///         gizmos.sphere(Vec3::Y, Quat::IDENTITY, 0.5, RED);
+    ///     });
+    ///     // The mesh can be rendered like any other, e.g. with a `StandardMaterial`
+    ///     meshes.add(mesh);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(freeze_path);
+    /// ```
+    pub fn capture(&mut self, draw: impl FnOnce(&mut Self)) -> Mesh {
+        let list_len = self.buffer.list_positions.len();
+        let strip_len = self.buffer.strip_positions.len();
+        let triangle_len = self.buffer.triangle_positions.len();
+
+        draw(self);
+
+        let buffer = &mut *self.buffer;
+        let list_positions = buffer.list_positions.split_off(list_len);
+        let list_colors = buffer.list_colors.split_off(list_len);
+        let strip_positions = buffer.strip_positions.split_off(strip_len);
+        let strip_colors = buffer.strip_colors.split_off(strip_len);
+        buffer.list_widths.truncate(list_len);
+        buffer.list_flags.truncate(list_len);
+        buffer.strip_widths.truncate(strip_len);
+        buffer.strip_flags.truncate(strip_len);
+        buffer.triangle_positions.truncate(triangle_len);
+        buffer.triangle_colors.truncate(triangle_len);
+        buffer.triangle_flags.truncate(triangle_len);
+
+        let mut positions = list_positions;
+        let mut colors = list_colors;
+        // Strips are separated by NaN positions, which must not end up in the mesh
+        for (positions_pair, colors_pair) in strip_positions.windows(2).zip(strip_colors.windows(2))
+        {
+            if positions_pair.iter().all(|position| !position.is_nan()) {
+                positions.extend_from_slice(positions_pair);
+                colors.extend_from_slice(colors_pair);
+            }
+        }
+
+        line_list_mesh(positions, colors)
+    }
+}
+
+fn line_list_mesh(positions: Vec<Vec3>, colors: Vec<LinearRgba>) -> Mesh {
+    Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_COLOR,
+            colors
+                .into_iter()
+                .map(|color| color.to_f32_array())
+                .collect::<Vec<_>>(),
+        )
+}
@@ -161,13 +161,47 @@ pub struct GizmoConfig {
     /// and your wireframe is z-fighting (flickering on/off) with your main model.
     /// You would set this value to a negative number close to 0.
     pub depth_bias: f32,
+    /// The opacity of the parts of gizmo lines that are hidden behind other geometry.
+    ///
+    /// When set above `0.`, occluded parts are drawn in a second pass with their alpha multiplied
+    /// by this value instead of disappearing, which keeps spatial relationships readable.
+    ///
+    /// This setting only affects 3D.
+    ///
+    /// Defaults to `0.`.
+    pub occluded_opacity: f32,
     /// Describes which rendering layers gizmos will be rendered to.
     ///
     /// Gizmos will only be rendered to cameras with intersecting layers.
+    /// Since each [`GizmoConfigGroup`] has its own config, this can be used to exclude debug
+    /// gizmos from secondary cameras such as minimaps, or to show only specific groups on them.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::view::RenderLayers;
+    /// # use bevy_ecs::prelude::*;
+    /// fn setup(mut config_store: ResMut<GizmoConfigStore>) {
+    ///     // Cameras without `RenderLayers` are on layer 0, so the default gizmos are only
+    ///     // drawn by cameras that are explicitly on layer 1.
+    ///     let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
+    ///     config.render_layers = RenderLayers::layer(1);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(setup);
+    /// ```
     pub render_layers: RenderLayers,
 
     /// Describe how lines should join
     pub line_joints: GizmoLineJoint,
+    /// The number of vertices the GPU buffers of each gizmo topology are created with.
+    ///
+    /// The buffers are kept across frames and grow when more vertices are drawn, so setting this
+    /// to the expected peak avoids reallocating them while the number of gizmos ramps up. They
+    /// shrink again when a gizmo uses at most a quarter of them for 120 frames in a row, but
+    /// never below this capacity.
+    ///
+    /// Defaults to `0`.
+    pub vertex_capacity: usize,
 }
 
 impl Default for GizmoConfig {
@@ -178,9 +212,11 @@ impl Default for GizmoConfig {
             line_perspective: false,
             line_style: GizmoLineStyle::Solid,
             depth_bias: 0.,
+            occluded_opacity: 0.,
             render_layers: Default::default(),
 
             line_joints: GizmoLineJoint::None,
+            vertex_capacity: 0,
         }
     }
 }
@@ -190,6 +226,7 @@ pub(crate) struct GizmoMeshConfig {
     pub line_perspective: bool,
     pub line_style: GizmoLineStyle,
     pub render_layers: RenderLayers,
+    pub occluded: bool,
 }
 
 impl From<&GizmoConfig> for GizmoMeshConfig {
@@ -198,6 +235,7 @@ impl From<&GizmoConfig> for GizmoMeshConfig {
             line_perspective: item.line_perspective,
             line_style: item.line_style,
             render_layers: item.render_layers.clone(),
+            occluded: item.occluded_opacity > 0.,
         }
     }
 }
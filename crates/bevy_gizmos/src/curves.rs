@@ -0,0 +1,403 @@
+//! Additional [`Gizmos`] Functions -- Curves
+//!
+//! Includes the implementation of [`Gizmos::curve`], [`Gizmos::cubic_bezier`] and
+//! [`Gizmos::catmull_rom`], their 2D counterparts, and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::{
+    cubic_splines::{CubicBezier, CubicCardinalSpline, CubicCurve, CubicGenerator, CubicSegment},
+    Mat4, Vec2, Vec3,
+};
+use bevy_render::camera::Camera;
+use bevy_transform::components::GlobalTransform;
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a [`CubicCurve`] in 3D.
+    ///
+    /// The curve is subdivided adaptively, using more line segments where it bends more, so the
+    /// number of segments doesn't have to be chosen by the caller.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Builder methods
+    /// The maximum distance between the drawn lines and the curve can be adjusted with the
+    /// `.tolerance(...)` method in world units, or with the `.pixel_tolerance(...)` method in
+    /// pixels on the screen of a camera.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::{prelude::*, cubic_splines::*};
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let curve = CubicBSpline::new([Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z, Vec3::ONE])
+    ///         .to_curve();
+    ///     gizmos.curve(&curve, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn curve(
+        &mut self,
+        curve: &CubicCurve<Vec3>,
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'_, 'w, 's, Config, Clear> {
+        CurveBuilder {
+            gizmos: self,
+            segments: curve.segments.clone(),
+            color: color.into(),
+            tolerance: CurveTolerance::Default,
+        }
+    }
+
+    /// Draw a [`CubicCurve`] in 2D.
+    ///
+    /// The curve is subdivided adaptively, using more line segments where it bends more, so the
+    /// number of segments doesn't have to be chosen by the caller.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Builder methods
+    /// The maximum distance between the drawn lines and the curve can be adjusted with the
+    /// `.tolerance(...)` method in world units, or with the `.pixel_tolerance(...)` method in
+    /// pixels on the screen of a camera.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::{prelude::*, cubic_splines::*};
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let curve = CubicBSpline::new([Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE]).to_curve();
+    ///     gizmos.curve_2d(&curve, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn curve_2d(
+        &mut self,
+        curve: &CubicCurve<Vec2>,
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'_, 'w, 's, Config, Clear> {
+        CurveBuilder {
+            gizmos: self,
+            segments: curve.segments.iter().map(extend_segment).collect(),
+            color: color.into(),
+            tolerance: CurveTolerance::Default,
+        }
+    }
+
+    /// Draw a cubic Bézier curve in 3D, from `control_points[0]` to `control_points[3]`.
+    ///
+    /// The inner control points define the tangents at the start and end of the curve.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Builder methods
+    /// The maximum distance between the drawn lines and the curve can be adjusted with the
+    /// `.tolerance(...)` method in world units, or with the `.pixel_tolerance(...)` method in
+    /// pixels on the screen of a camera.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.cubic_bezier([Vec3::ZERO, Vec3::Y, Vec3::X + Vec3::Y, Vec3::X], GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn cubic_bezier(
+        &mut self,
+        control_points: [Vec3; 4],
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'_, 'w, 's, Config, Clear> {
+        let curve = CubicBezier::new([control_points]).to_curve();
+        self.curve(&curve, color)
+    }
+
+    /// Draw a cubic Bézier curve in 2D, from `control_points[0]` to `control_points[3]`.
+    ///
+    /// The inner control points define the tangents at the start and end of the curve.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Builder methods
+    /// The maximum distance between the drawn lines and the curve can be adjusted with the
+    /// `.tolerance(...)` method in world units, or with the `.pixel_tolerance(...)` method in
+    /// pixels on the screen of a camera.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.cubic_bezier_2d(
+    ///         [
+    ///             Vec2::ZERO,
+    ///             Vec2::new(0., 100.),
+    ///             Vec2::new(100., 100.),
+    ///             Vec2::new(100., 0.),
+    ///         ],
+    ///         GREEN,
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn cubic_bezier_2d(
+        &mut self,
+        control_points: [Vec2; 4],
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'_, 'w, 's, Config, Clear> {
+        let curve = CubicBezier::new([control_points]).to_curve();
+        self.curve_2d(&curve, color)
+    }
+
+    /// Draw a Catmull-Rom spline in 3D, passing through all of the `points`.
+    ///
+    /// The tangent at each point is parallel to the line between its neighbors. Nothing is drawn
+    /// for fewer than two points.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Builder methods
+    /// The maximum distance between the drawn lines and the curve can be adjusted with the
+    /// `.tolerance(...)` method in world units, or with the `.pixel_tolerance(...)` method in
+    /// pixels on the screen of a camera.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let path = [Vec3::ZERO, Vec3::X, Vec3::new(1., 1., 0.), Vec3::Y, Vec3::ZERO];
+    ///     gizmos.catmull_rom(path, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn catmull_rom(
+        &mut self,
+        points: impl Into<Vec<Vec3>>,
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'_, 'w, 's, Config, Clear> {
+        let curve = CubicCardinalSpline::new_catmull_rom(points).to_curve();
+        self.curve(&curve, color)
+    }
+
+    /// Draw a Catmull-Rom spline in 2D, passing through all of the `points`.
+    ///
+    /// The tangent at each point is parallel to the line between its neighbors. Nothing is drawn
+    /// for fewer than two points.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Builder methods
+    /// The maximum distance between the drawn lines and the curve can be adjusted with the
+    /// `.tolerance(...)` method in world units, or with the `.pixel_tolerance(...)` method in
+    /// pixels on the screen of a camera.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let envelope: Vec<Vec2> = (0..20)
+    ///         .map(|i| Vec2::new(i as f32 * 10., (i as f32 * 0.5).sin().abs() * 50.))
+    ///         .collect();
+    ///     gizmos.catmull_rom_2d(envelope, GREEN).tolerance(0.5);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn catmull_rom_2d(
+        &mut self,
+        points: impl Into<Vec<Vec2>>,
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'_, 'w, 's, Config, Clear> {
+        let curve = CubicCardinalSpline::new_catmull_rom(points).to_curve();
+        self.curve_2d(&curve, color)
+    }
+}
+
+/// A builder returned by [`Gizmos::curve`], [`Gizmos::cubic_bezier`], [`Gizmos::catmull_rom`]
+/// and their 2D counterparts.
+pub struct CurveBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    segments: Vec<CubicSegment<Vec3>>,
+    color: Color,
+    tolerance: CurveTolerance,
+}
+
+/// The maximum distance between the lines drawn by a [`CurveBuilder`] and the curve.
+enum CurveTolerance {
+    /// A fraction of the size of each segment.
+    Default,
+    /// A distance in world units.
+    World(f32),
+    /// A distance in pixels on the screen of a camera.
+    Pixels {
+        pixels: f32,
+        clip_from_world: Mat4,
+        /// The number of pixels covered by a world unit at a `w` of 1 in clip space.
+        pixels_per_unit: f32,
+    },
+}
+
+impl<Config, Clear> CurveBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the maximum distance between the drawn lines and the curve, in world units.
+    ///
+    /// Defaults to 0.1% of the size of each curve segment, which doesn't take into account how
+    /// large the curve appears on screen. Use [`pixel_tolerance`](Self::pixel_tolerance) to
+    /// draw curves far away from the camera with fewer lines.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = CurveTolerance::World(tolerance);
+        self
+    }
+
+    /// Set the maximum distance between the drawn lines and the curve, in physical pixels on the
+    /// screen of `camera`.
+    ///
+    /// Each segment of the curve is subdivided according to how large it appears to the camera,
+    /// so that curves far away from the camera are drawn with fewer lines than close ones.
+    /// Segments behind the camera use the default tolerance.
+    pub fn pixel_tolerance(
+        mut self,
+        pixels: f32,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Self {
+        if let Some(viewport_size) = camera.physical_viewport_size() {
+            let projection = camera.projection_matrix();
+            self.tolerance = CurveTolerance::Pixels {
+                pixels,
+                clip_from_world: projection * camera_transform.compute_matrix().inverse(),
+                pixels_per_unit: 0.5 * projection.x_axis.x * viewport_size.x as f32,
+            };
+        }
+        self
+    }
+}
+
+impl<Config, Clear> Drop for CurveBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let Some(first) = self.segments.first() else {
+            return;
+        };
+
+        let mut positions = vec![first.position(0.)];
+        for segment in &self.segments {
+            let tolerance = self.tolerance.for_segment(segment);
+            let start = segment.position(0.);
+            let end = segment.position(1.);
+            subdivide(
+                segment,
+                (0., start),
+                (1., end),
+                tolerance,
+                0,
+                &mut positions,
+            );
+        }
+        self.gizmos.linestrip(positions, self.color);
+    }
+}
+
+/// Every segment is split at least this many times, as the midpoint of an S-shaped piece of a
+/// curve can lie on its chord.
+const MIN_CURVE_DEPTH: u32 = 2;
+/// Limits a segment to `2^MAX_CURVE_DEPTH` lines.
+const MAX_CURVE_DEPTH: u32 = 10;
+const DEFAULT_CURVE_TOLERANCE: f32 = 0.001;
+
+impl CurveTolerance {
+    /// Returns the tolerance of `segment` in world units.
+    fn for_segment(&self, segment: &CubicSegment<Vec3>) -> f32 {
+        match *self {
+            CurveTolerance::Default => default_tolerance(segment),
+            CurveTolerance::World(tolerance) => tolerance,
+            CurveTolerance::Pixels {
+                pixels,
+                clip_from_world,
+                pixels_per_unit,
+            } => {
+                // Scale by the depth of the middle of the segment
+                let w = clip_from_world.row(3).dot(segment.position(0.5).extend(1.));
+                let pixels_per_unit = pixels_per_unit / w;
+                if pixels_per_unit.is_finite() && pixels_per_unit > 0. {
+                    pixels / pixels_per_unit
+                } else {
+                    default_tolerance(segment)
+                }
+            }
+        }
+    }
+}
+
+fn default_tolerance(segment: &CubicSegment<Vec3>) -> f32 {
+    // The sum of the non-constant coefficients bounds the size of the segment
+    let [_, b, c, d] = segment.coeff;
+    DEFAULT_CURVE_TOLERANCE * (b.length() + c.length() + d.length())
+}
+
+/// Pushes the end positions of the lines approximating `segment` between the parameters of
+/// `start` and `end`, splitting the piece in half until its midpoint is within `tolerance` of
+/// its chord.
+fn subdivide(
+    segment: &CubicSegment<Vec3>,
+    start: (f32, Vec3),
+    end: (f32, Vec3),
+    tolerance: f32,
+    depth: u32,
+    positions: &mut Vec<Vec3>,
+) {
+    let t = (start.0 + end.0) * 0.5;
+    let mid = (t, segment.position(t));
+    let deviation = mid.1.distance((start.1 + end.1) * 0.5);
+
+    if depth < MIN_CURVE_DEPTH || (depth < MAX_CURVE_DEPTH && deviation > tolerance) {
+        subdivide(segment, start, mid, tolerance, depth + 1, positions);
+        subdivide(segment, mid, end, tolerance, depth + 1, positions);
+    } else {
+        positions.push(end.1);
+    }
+}
+
+fn extend_segment(segment: &CubicSegment<Vec2>) -> CubicSegment<Vec3> {
+    CubicSegment {
+        coeff: segment.coeff.map(|coeff| coeff.extend(0.)),
+    }
+}
@@ -0,0 +1,265 @@
+//! Additional [`Gizmos`] Functions -- Filled shapes
+//!
+//! Includes the implementation of [`Gizmos::polygon_filled_2d`], and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::Vec2;
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a filled polygon in 2D.
+    ///
+    /// This should be called for each frame the polygon needs to be rendered.
+    ///
+    /// The polygon is closed automatically, so the last point should not repeat the first one.
+    /// It may be concave, but must not intersect itself. The points can be given in either winding
+    /// order. Polygons with fewer than three points are not drawn.
+    ///
+    /// Semi-transparent fills are blended over the scene, which makes them useful to highlight
+    /// areas such as trigger zones or navigation regions.
+    ///
+    /// # Builder methods
+    ///
+    /// - An outline can be drawn around the polygon using `.outline(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::{Alpha, palettes::basic::{GREEN, WHITE}};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .polygon_filled_2d(
+    ///             [
+    ///                 Vec2::new(-100., -50.),
+    ///                 Vec2::new(100., -50.),
+    ///                 Vec2::new(0., 0.),
+    ///                 Vec2::new(100., 50.),
+    ///                 Vec2::new(-100., 50.),
+    ///             ],
+    ///             GREEN.with_alpha(0.3),
+    ///         )
+    ///         .outline(WHITE);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn polygon_filled_2d(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        color: impl Into<Color>,
+    ) -> FilledPolygon2dBuilder<'_, 'w, 's, Config, Clear> {
+        FilledPolygon2dBuilder {
+            gizmos: self,
+            points: points.into_iter().collect(),
+            color: color.into(),
+            outline: None,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::polygon_filled_2d`].
+pub struct FilledPolygon2dBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    points: Vec<Vec2>,
+    color: Color,
+    outline: Option<Color>,
+}
+
+impl<Config, Clear> FilledPolygon2dBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw an outline of the given color around the polygon.
+    pub fn outline(mut self, color: impl Into<Color>) -> Self {
+        self.outline = Some(color.into());
+        self
+    }
+}
+
+impl<Config, Clear> Drop for FilledPolygon2dBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled || self.points.len() < 3 {
+            return;
+        }
+
+        let triangles = triangulate(&self.points);
+        self.gizmos.extend_triangles(
+            triangles
+                .into_iter()
+                .flatten()
+                .map(|index| self.points[index].extend(0.)),
+            self.color,
+        );
+
+        if let Some(outline) = self.outline {
+            let closed = self.points.iter().chain(self.points.first()).copied();
+            self.gizmos.linestrip_2d(closed, outline);
+        }
+    }
+}
+
+/// Triangulates a simple polygon by ear clipping, returning the indices of the triangles.
+///
+/// The triangles are wound in the same order as the polygon. If the polygon intersects itself,
+/// the remaining vertices are fanned out once no more ears can be found. There are no triangles
+/// if there are fewer than 3 points.
+fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // Twice the signed area, positive for counter-clockwise polygons
+    let area: f32 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum();
+    let winding = if area < 0. { -1. } else { 1. };
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let ear = (0..len).find(|&i| {
+            let [a, b, c] = [
+                remaining[(i + len - 1) % len],
+                remaining[i],
+                remaining[(i + 1) % len],
+            ]
+            .map(|index| points[index]);
+            // The vertex must be convex, and the triangle must not contain any other vertex
+            winding * (b - a).perp_dot(c - b) > 0.
+                && remaining.iter().all(|&other| {
+                    let p = points[other];
+                    p == a || p == b || p == c || !triangle_contains(a, b, c, p)
+                })
+        });
+
+        let Some(i) = ear else {
+            break;
+        };
+        triangles.push([
+            remaining[(i + len - 1) % len],
+            remaining[i],
+            remaining[(i + 1) % len],
+        ]);
+        remaining.remove(i);
+    }
+
+    for i in 1..remaining.len() - 1 {
+        triangles.push([remaining[0], remaining[i], remaining[i + 1]]);
+    }
+
+    triangles
+}
+
+fn triangle_contains(a: Vec2, b: Vec2, c: Vec2, p: Vec2) -> bool {
+    let d1 = (b - a).perp_dot(p - a);
+    let d2 = (c - b).perp_dot(p - b);
+    let d3 = (a - c).perp_dot(p - c);
+    let has_negative = d1 < 0. || d2 < 0. || d3 < 0.;
+    let has_positive = d1 > 0. || d2 > 0. || d3 > 0.;
+    !(has_negative && has_positive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Twice the signed area of the triangle, positive if it is counter-clockwise.
+    fn signed_area([a, b, c]: [Vec2; 3]) -> f32 {
+        (b - a).perp_dot(c - a)
+    }
+
+    /// Checks that `points` is triangulated into `expected` triangles, wound like the polygon,
+    /// that cover its area exactly.
+    fn assert_triangulated(points: &[Vec2], expected: usize) {
+        let triangles = triangulate(points);
+        assert_eq!(triangles.len(), expected);
+
+        let polygon_area: f32 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.perp_dot(*b))
+            .sum();
+        let mut area = 0.;
+        for triangle in triangles {
+            let triangle_area = signed_area(triangle.map(|index| points[index]));
+            assert_eq!(triangle_area.signum(), polygon_area.signum());
+            area += triangle_area;
+        }
+        assert!((area - polygon_area).abs() < 1e-4);
+    }
+
+    #[test]
+    fn convex_polygon() {
+        let hexagon: Vec<Vec2> = (0..6)
+            .map(|i| Vec2::from_angle(i as f32 * std::f32::consts::TAU / 6.))
+            .collect();
+        assert_triangulated(&hexagon, 4);
+    }
+
+    #[test]
+    fn concave_polygon() {
+        // An arrow head pointing up, whose bottom vertex is reflex
+        let arrow = [
+            Vec2::new(0., 2.),
+            Vec2::new(-2., -2.),
+            Vec2::new(0., 0.),
+            Vec2::new(2., -2.),
+        ];
+        assert_triangulated(&arrow, 2);
+
+        let l_shape = [
+            Vec2::new(0., 0.),
+            Vec2::new(2., 0.),
+            Vec2::new(2., 1.),
+            Vec2::new(1., 1.),
+            Vec2::new(1., 2.),
+            Vec2::new(0., 2.),
+        ];
+        assert_triangulated(&l_shape, 4);
+    }
+
+    #[test]
+    fn clockwise_polygon() {
+        let l_shape = [
+            Vec2::new(0., 2.),
+            Vec2::new(1., 2.),
+            Vec2::new(1., 1.),
+            Vec2::new(2., 1.),
+            Vec2::new(2., 0.),
+            Vec2::new(0., 0.),
+        ];
+        assert_triangulated(&l_shape, 4);
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        assert!(triangulate(&[]).is_empty());
+        assert!(triangulate(&[Vec2::ZERO]).is_empty());
+        assert!(triangulate(&[Vec2::ZERO, Vec2::X]).is_empty());
+
+        // Collinear points have no ears, and are fanned out into triangles without area
+        let line = [Vec2::ZERO, Vec2::X, Vec2::new(2., 0.), Vec2::new(3., 0.)];
+        let triangles = triangulate(&line);
+        assert_eq!(triangles.len(), 2);
+        for triangle in triangles {
+            assert_eq!(signed_area(triangle.map(|index| line[index])), 0.);
+        }
+    }
+}
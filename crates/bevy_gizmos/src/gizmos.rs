@@ -1,6 +1,11 @@
 //! A module for the [`Gizmos`] [`SystemParam`].
 
-use std::{iter, marker::PhantomData, mem};
+use std::{
+    iter,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+};
 
 use crate::circles::DEFAULT_CIRCLE_RESOLUTION;
 use bevy_color::{Color, LinearRgba};
@@ -17,6 +22,7 @@ use crate::{
     config::GizmoConfigGroup,
     config::{DefaultGizmoConfigGroup, GizmoConfigStore},
     prelude::GizmoConfig,
+    retained::{RetainedGizmo, RetainedGizmos},
 };
 
 /// Storage of gizmo primitives.
@@ -24,8 +30,15 @@ use crate::{
 pub struct GizmoStorage<Config, Clear> {
     pub(crate) list_positions: Vec<Vec3>,
     pub(crate) list_colors: Vec<LinearRgba>,
+    pub(crate) list_widths: Vec<f32>,
+    pub(crate) list_flags: Vec<u32>,
     pub(crate) strip_positions: Vec<Vec3>,
     pub(crate) strip_colors: Vec<LinearRgba>,
+    pub(crate) strip_widths: Vec<f32>,
+    pub(crate) strip_flags: Vec<u32>,
+    pub(crate) triangle_positions: Vec<Vec3>,
+    pub(crate) triangle_colors: Vec<LinearRgba>,
+    pub(crate) triangle_flags: Vec<u32>,
     marker: PhantomData<(Config, Clear)>,
 }
 
@@ -34,8 +47,15 @@ impl<Config, Clear> Default for GizmoStorage<Config, Clear> {
         Self {
             list_positions: default(),
             list_colors: default(),
+            list_widths: default(),
+            list_flags: default(),
             strip_positions: default(),
             strip_colors: default(),
+            strip_widths: default(),
+            strip_flags: default(),
+            triangle_positions: default(),
+            triangle_colors: default(),
+            triangle_flags: default(),
             marker: PhantomData,
         }
     }
@@ -53,8 +73,16 @@ where
     ) {
         self.list_positions.extend(other.list_positions.iter());
         self.list_colors.extend(other.list_colors.iter());
+        self.list_widths.extend(other.list_widths.iter());
+        self.list_flags.extend(other.list_flags.iter());
         self.strip_positions.extend(other.strip_positions.iter());
         self.strip_colors.extend(other.strip_colors.iter());
+        self.strip_widths.extend(other.strip_widths.iter());
+        self.strip_flags.extend(other.strip_flags.iter());
+        self.triangle_positions
+            .extend(other.triangle_positions.iter());
+        self.triangle_colors.extend(other.triangle_colors.iter());
+        self.triangle_flags.extend(other.triangle_flags.iter());
     }
 
     pub(crate) fn swap<OtherConfig, OtherClear>(
@@ -63,16 +91,30 @@ where
     ) {
         mem::swap(&mut self.list_positions, &mut other.list_positions);
         mem::swap(&mut self.list_colors, &mut other.list_colors);
+        mem::swap(&mut self.list_widths, &mut other.list_widths);
+        mem::swap(&mut self.list_flags, &mut other.list_flags);
         mem::swap(&mut self.strip_positions, &mut other.strip_positions);
         mem::swap(&mut self.strip_colors, &mut other.strip_colors);
+        mem::swap(&mut self.strip_widths, &mut other.strip_widths);
+        mem::swap(&mut self.strip_flags, &mut other.strip_flags);
+        mem::swap(&mut self.triangle_positions, &mut other.triangle_positions);
+        mem::swap(&mut self.triangle_colors, &mut other.triangle_colors);
+        mem::swap(&mut self.triangle_flags, &mut other.triangle_flags);
     }
 
     /// Clear this gizmo storage of any requested gizmos.
     pub fn clear(&mut self) {
         self.list_positions.clear();
         self.list_colors.clear();
+        self.list_widths.clear();
+        self.list_flags.clear();
         self.strip_positions.clear();
         self.strip_colors.clear();
+        self.strip_widths.clear();
+        self.strip_flags.clear();
+        self.triangle_positions.clear();
+        self.triangle_colors.clear();
+        self.triangle_flags.clear();
     }
 }
 
@@ -136,8 +178,14 @@ where
     Config: GizmoConfigGroup,
     Clear: 'static + Send + Sync,
 {
-    buffer: Deferred<'s, GizmoBuffer<Config, Clear>>,
+    pub(crate) buffer: Deferred<'s, GizmoBuffer<Config, Clear>>,
     pub(crate) enabled: bool,
+    /// The line width of gizmos drawn from now on, in pixels.
+    ///
+    /// Zero means the line width of the current [`GizmoConfig`] is used.
+    pub(crate) line_width: f32,
+    /// The vertex flags of gizmos drawn from now on, such as [`SCREEN_SPACE_FLAG`].
+    pub(crate) flags: u32,
     /// The currently used [`GizmoConfig`]
     pub config: &'w GizmoConfig,
     /// The currently used [`GizmoConfigGroup`]
@@ -210,6 +258,8 @@ where
         Gizmos {
             buffer: f0,
             enabled: config.enabled,
+            line_width: 0.,
+            flags: 0,
             config,
             config_ext,
         }
@@ -227,15 +277,23 @@ where
 {
 }
 
-struct GizmoBuffer<Config, Clear>
+pub(crate) struct GizmoBuffer<Config, Clear>
 where
     Config: GizmoConfigGroup,
     Clear: 'static + Send + Sync,
 {
-    list_positions: Vec<Vec3>,
-    list_colors: Vec<LinearRgba>,
-    strip_positions: Vec<Vec3>,
-    strip_colors: Vec<LinearRgba>,
+    pub(crate) list_positions: Vec<Vec3>,
+    pub(crate) list_colors: Vec<LinearRgba>,
+    pub(crate) list_widths: Vec<f32>,
+    pub(crate) list_flags: Vec<u32>,
+    pub(crate) strip_positions: Vec<Vec3>,
+    pub(crate) strip_colors: Vec<LinearRgba>,
+    pub(crate) strip_widths: Vec<f32>,
+    pub(crate) strip_flags: Vec<u32>,
+    pub(crate) triangle_positions: Vec<Vec3>,
+    pub(crate) triangle_colors: Vec<LinearRgba>,
+    pub(crate) triangle_flags: Vec<u32>,
+    pub(crate) retained: Vec<RetainedGizmo>,
     marker: PhantomData<(Config, Clear)>,
 }
 
@@ -248,8 +306,16 @@ where
         Self {
             list_positions: default(),
             list_colors: default(),
+            list_widths: default(),
+            list_flags: default(),
             strip_positions: default(),
             strip_colors: default(),
+            strip_widths: default(),
+            strip_flags: default(),
+            triangle_positions: default(),
+            triangle_colors: default(),
+            triangle_flags: default(),
+            retained: default(),
             marker: PhantomData,
         }
     }
@@ -264,8 +330,194 @@ where
         let mut storage = world.resource_mut::<GizmoStorage<Config, Clear>>();
         storage.list_positions.append(&mut self.list_positions);
         storage.list_colors.append(&mut self.list_colors);
+        storage.list_widths.append(&mut self.list_widths);
+        storage.list_flags.append(&mut self.list_flags);
         storage.strip_positions.append(&mut self.strip_positions);
         storage.strip_colors.append(&mut self.strip_colors);
+        storage.strip_widths.append(&mut self.strip_widths);
+        storage.strip_flags.append(&mut self.strip_flags);
+        storage
+            .triangle_positions
+            .append(&mut self.triangle_positions);
+        storage.triangle_colors.append(&mut self.triangle_colors);
+        storage.triangle_flags.append(&mut self.triangle_flags);
+
+        if !self.retained.is_empty() {
+            world
+                .resource_mut::<RetainedGizmos<Config>>()
+                .gizmos
+                .append(&mut self.retained);
+        }
+    }
+}
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw gizmos with a line width of `line_width` pixels, instead of the line width of the
+    /// [`GizmoConfig`].
+    ///
+    /// Every gizmo drawn through the returned [`ScopedGizmos`] uses the given line width, until
+    /// it is dropped. Non-positive widths fall back to the line width of the [`GizmoConfig`].
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{GREEN, RED};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A single thick arrow
+    ///     gizmos.with_line_width(8.).arrow(Vec3::ZERO, Vec3::X, GREEN);
+    ///
+    ///     // Several thin lines
+    ///     let mut thin = gizmos.with_line_width(1.);
+    ///     thin.line(Vec3::ZERO, Vec3::Y, RED);
+    ///     thin.circle(Vec3::ZERO, Dir3::Y, 1., RED);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn with_line_width(&mut self, line_width: f32) -> ScopedGizmos<'_, 'w, 's, Config, Clear> {
+        let mut scoped = ScopedGizmos::new(self);
+        scoped.line_width = line_width.max(0.);
+        scoped
+    }
+
+    /// Draw gizmos in screen space, on top of everything else.
+    ///
+    /// Positions of gizmos drawn through the returned [`ScopedGizmos`] are given in physical
+    /// pixels, with the origin at the top left corner of the viewport and the y axis pointing down.
+    /// Only the `x` and `y` coordinates of 3D positions are used.
+    /// They are not affected by the camera's projection, which makes them suitable for HUD-style
+    /// overlays, rulers and markers at cursor positions, as returned by
+    /// `Window::physical_cursor_position`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{RED, WHITE};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let mut screen = gizmos.screen();
+    ///     // A ruler along the top of the viewport
+    ///     screen.line_2d(Vec2::new(10., 10.), Vec2::new(410., 10.), WHITE);
+    ///     for i in 0..=10 {
+    ///         let x = 10. + i as f32 * 40.;
+    ///         screen.line_2d(Vec2::new(x, 10.), Vec2::new(x, 20.), WHITE);
+    ///     }
+    ///     // A marker at a given pixel
+    ///     screen.circle_2d(Vec2::new(200., 150.), 12., RED);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn screen(&mut self) -> ScopedGizmos<'_, 'w, 's, Config, Clear> {
+        let mut scoped = ScopedGizmos::new(self);
+        scoped.flags |= SCREEN_SPACE_FLAG;
+        scoped
+    }
+
+    /// Draw gizmos through other geometry.
+    ///
+    /// Gizmos drawn through the returned [`ScopedGizmos`] are drawn on top of other geometry,
+    /// as if [`GizmoConfig::depth_bias`] was `-1.0`, while other gizmos keep respecting depth.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{GREEN, RED};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // Hidden behind geometry in front of it
+    ///     gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 1., RED);
+    ///
+    ///     // Always visible
+    ///     gizmos.x_ray().arrow(Vec3::ZERO, Vec3::Y * 2., GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn x_ray(&mut self) -> ScopedGizmos<'_, 'w, 's, Config, Clear> {
+        let mut scoped = ScopedGizmos::new(self);
+        scoped.flags |= X_RAY_FLAG;
+        scoped
+    }
+}
+
+/// Marks a gizmo vertex as given in screen space pixels, see [`Gizmos::screen`].
+///
+/// Must match the flag in `lines.wgsl` and `line_joints.wgsl`.
+pub(crate) const SCREEN_SPACE_FLAG: u32 = 1 << 0;
+/// Marks a gizmo vertex as drawn through other geometry, see [`Gizmos::x_ray`].
+///
+/// Must match the flag in `lines.wgsl` and `line_joints.wgsl`.
+pub(crate) const X_RAY_FLAG: u32 = 1 << 1;
+/// Marks a zero-length line segment as a point, see [`Gizmos::points`].
+///
+/// Must match the flag in `lines.wgsl`.
+pub(crate) const POINT_FLAG: u32 = 1 << 2;
+
+/// A [`Gizmos`] wrapper returned by [`Gizmos::with_line_width`], [`Gizmos::screen`] and
+/// [`Gizmos::x_ray`].
+///
+/// Gizmos drawn through it use the settings of the scope. The previous settings are restored
+/// when it is dropped, so scopes can be nested.
+pub struct ScopedGizmos<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    previous_line_width: f32,
+    previous_flags: u32,
+}
+
+impl<'a, 'w, 's, Config, Clear> ScopedGizmos<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn new(gizmos: &'a mut Gizmos<'w, 's, Config, Clear>) -> Self {
+        Self {
+            previous_line_width: gizmos.line_width,
+            previous_flags: gizmos.flags,
+            gizmos,
+        }
+    }
+}
+
+impl<'w, 's, Config, Clear> Deref for ScopedGizmos<'_, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    type Target = Gizmos<'w, 's, Config, Clear>;
+
+    fn deref(&self) -> &Self::Target {
+        self.gizmos
+    }
+}
+
+impl<Config, Clear> DerefMut for ScopedGizmos<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.gizmos
+    }
+}
+
+impl<Config, Clear> Drop for ScopedGizmos<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        self.gizmos.line_width = self.previous_line_width;
+        self.gizmos.flags = self.previous_flags;
     }
 }
 
@@ -380,6 +632,50 @@ where
         self.line_gradient(start, start + vector, start_color, end_color);
     }
 
+    /// Draw a point at each of the `positions` in 3D, as squares of `size` pixels.
+    ///
+    /// The size of the points is not affected by [`GizmoConfig::line_perspective`].
+    ///
+    /// All points are drawn as instances of a single draw call, which makes this suitable for
+    /// large point clouds.
+    ///
+    /// This should be called for each frame the points need to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let samples = (0..10_000).map(|i| {
+    ///         let t = i as f32 * 0.01;
+    ///         Vec3::new(t.cos(), t * 0.01, t.sin())
+    ///     });
+    ///     gizmos.points(samples, 3., GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn points(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec3>,
+        size: f32,
+        color: impl Into<Color>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let mut scoped = self.with_line_width(size);
+        scoped.flags |= POINT_FLAG;
+
+        // Points are zero-length line segments, which the shader draws as squares
+        let len = scoped.buffer.list_positions.len();
+        scoped.extend_list_positions(positions.into_iter().flat_map(|position| [position; 2]));
+        let count = scoped.buffer.list_positions.len() - len;
+        scoped.add_list_color(color, count);
+    }
+
     /// Draw a line in 3D made of straight segments between the points.
     ///
     /// This should be called for each frame the line needs to be rendered.
@@ -415,18 +711,28 @@ where
     ///
     /// This should be called for each frame the lines need to be rendered.
     ///
+    /// Each point has its own color, which is interpolated along the adjacent segments. This can be
+    /// used to encode a second dimension, such as time or magnitude, in the color of a single strip.
+    ///
     /// # Example
     /// ```
     /// # use bevy_gizmos::prelude::*;
     /// # use bevy_render::prelude::*;
     /// # use bevy_math::prelude::*;
-    /// # use bevy_color::palettes::basic::{BLUE, GREEN, RED};
+    /// # use bevy_color::{LinearRgba, Mix, palettes::basic::{BLUE, GREEN, RED}};
     /// fn system(mut gizmos: Gizmos) {
     ///     gizmos.linestrip_gradient([
     ///         (Vec3::ZERO, GREEN),
     ///         (Vec3::X, RED),
     ///         (Vec3::Y, BLUE)
     ///     ]);
+    ///
+    ///     // Color a sine wave by its magnitude
+    ///     gizmos.linestrip_gradient((0..100).map(|i| {
+    ///         let x = i as f32 / 10.;
+    ///         let color = LinearRgba::from(BLUE).mix(&RED.into(), x.sin().abs());
+    ///         (Vec3::new(x, x.sin(), 0.), color)
+    ///     }));
     /// }
     /// # bevy_ecs::system::assert_is_system(system);
     /// ```
@@ -443,6 +749,8 @@ where
         let GizmoBuffer {
             strip_positions,
             strip_colors,
+            strip_widths,
+            strip_flags,
             ..
         } = &mut *self.buffer;
 
@@ -457,6 +765,8 @@ where
 
         strip_positions.push(Vec3::NAN);
         strip_colors.push(LinearRgba::NAN);
+        strip_widths.resize(strip_positions.len(), self.line_width);
+        strip_flags.resize(strip_positions.len(), self.flags);
     }
 
     /// Draw a wireframe sphere in 3D made out of 3 circles around the axes.
@@ -615,6 +925,45 @@ where
         self.line_gradient(start.extend(0.), end.extend(0.), start_color, end_color);
     }
 
+    /// Draw a point at each of the `positions` in 2D, as squares of `size` pixels.
+    ///
+    /// All points are drawn as instances of a single draw call, which makes this suitable for
+    /// large point clouds.
+    ///
+    /// This should be called for each frame the points need to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let scatter = (0..10_000).map(|i| {
+    ///         let t = i as f32 * 0.1;
+    ///         Vec2::new(t.cos(), t.sin()) * t
+    ///     });
+    ///     gizmos.points_2d(scatter, 2., GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn points_2d(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec2>,
+        size: f32,
+        color: impl Into<Color>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.points(
+            positions.into_iter().map(|vec2| vec2.extend(0.)),
+            size,
+            color,
+        );
+    }
+
     /// Draw a line in 2D made of straight segments between the points.
     ///
     /// This should be called for each frame the line needs to be rendered.
@@ -646,6 +995,8 @@ where
     ///
     /// This should be called for each frame the line needs to be rendered.
     ///
+    /// See [`Gizmos::linestrip_gradient`] for how the colors are interpolated.
+    ///
     /// # Example
     /// ```
     /// # use bevy_gizmos::prelude::*;
@@ -762,6 +1113,9 @@ where
     #[inline]
     fn extend_list_positions(&mut self, positions: impl IntoIterator<Item = Vec3>) {
         self.buffer.list_positions.extend(positions);
+        let len = self.buffer.list_positions.len();
+        self.buffer.list_widths.resize(len, self.line_width);
+        self.buffer.list_flags.resize(len, self.flags);
     }
 
     #[inline]
@@ -787,6 +1141,24 @@ where
     fn extend_strip_positions(&mut self, positions: impl IntoIterator<Item = Vec3>) {
         self.buffer.strip_positions.extend(positions);
         self.buffer.strip_positions.push(Vec3::NAN);
+        let len = self.buffer.strip_positions.len();
+        self.buffer.strip_widths.resize(len, self.line_width);
+        self.buffer.strip_flags.resize(len, self.flags);
+    }
+
+    /// Adds filled triangles, given as consecutive triples of positions, in a single color.
+    #[inline]
+    pub(crate) fn extend_triangles(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec3>,
+        color: impl Into<Color>,
+    ) {
+        self.buffer.triangle_positions.extend(positions);
+        let len = self.buffer.triangle_positions.len();
+        self.buffer
+            .triangle_colors
+            .resize(len, LinearRgba::from(color.into()));
+        self.buffer.triangle_flags.resize(len, self.flags);
     }
 }
 
@@ -3,7 +3,10 @@
 //! Includes the implementation of[`Gizmos::grid`] and [`Gizmos::grid_2d`].
 //! and assorted support items.
 
-use crate::prelude::{GizmoConfigGroup, Gizmos};
+use crate::{
+    plot::format_tick,
+    prelude::{GizmoConfigGroup, Gizmos},
+};
 use bevy_color::LinearRgba;
 use bevy_math::{Quat, UVec2, UVec3, Vec2, Vec3};
 
@@ -21,6 +24,8 @@ where
     skew: Vec3,
     outer_edges: [bool; 3],
     color: LinearRgba,
+    major_lines: Option<(u32, LinearRgba)>,
+    labels: bool,
 }
 /// A builder returned by [`Gizmos::grid`] and [`Gizmos::grid_2d`]
 pub struct GridBuilder2d<'a, 'w, 's, Config, Clear>
@@ -36,6 +41,8 @@ where
     skew: Vec2,
     outer_edges: [bool; 2],
     color: LinearRgba,
+    major_lines: Option<(u32, LinearRgba)>,
+    labels: bool,
 }
 
 impl<Config, Clear> GridBuilder3d<'_, '_, '_, Config, Clear>
@@ -92,6 +99,24 @@ where
         self.outer_edges.fill(true);
         self
     }
+
+    /// Draw every `every`th line, counted from the outer edges, as a major line in `color`.
+    /// The remaining lines are minor lines drawn in the grid's color.
+    ///
+    /// Major lines are drawn twice as thick as minor lines.
+    pub fn major_lines(mut self, every: u32, color: impl Into<LinearRgba>) -> Self {
+        self.major_lines = Some((every.max(1), color.into()));
+        self
+    }
+
+    /// Draw numeric labels along the edges of the grid starting at its bottom-left-front corner.
+    ///
+    /// The labels show the distance of each line from the center of the grid, and are laid out
+    /// in the XY plane of the grid. If major lines are enabled, only major lines are labeled.
+    pub fn labels(mut self) -> Self {
+        self.labels = true;
+        self
+    }
 }
 
 impl<Config, Clear> GridBuilder2d<'_, '_, '_, Config, Clear>
@@ -136,6 +161,24 @@ where
         self.outer_edges.fill(true);
         self
     }
+
+    /// Draw every `every`th line, counted from the outer edges, as a major line in `color`.
+    /// The remaining lines are minor lines drawn in the grid's color.
+    ///
+    /// Major lines are drawn twice as thick as minor lines.
+    pub fn major_lines(mut self, every: u32, color: impl Into<LinearRgba>) -> Self {
+        self.major_lines = Some((every.max(1), color.into()));
+        self
+    }
+
+    /// Draw numeric labels along the bottom and left edges of the grid.
+    ///
+    /// The labels show the distance of each line from the center of the grid. If major lines
+    /// are enabled, only major lines are labeled.
+    pub fn labels(mut self) -> Self {
+        self.labels = true;
+        self
+    }
 }
 
 impl<Config, Clear> Drop for GridBuilder3d<'_, '_, '_, Config, Clear>
@@ -154,7 +197,21 @@ where
             self.skew,
             self.outer_edges,
             self.color,
+            self.major_lines,
         );
+
+        if self.labels {
+            draw_grid_labels(
+                self.gizmos,
+                self.position,
+                self.rotation,
+                self.spacing,
+                self.cell_count,
+                self.skew,
+                self.major_lines,
+                self.color,
+            );
+        }
     }
 }
 
@@ -173,7 +230,21 @@ where
             self.skew.extend(0.),
             [self.outer_edges[0], self.outer_edges[1], true],
             self.color,
+            self.major_lines,
         );
+
+        if self.labels {
+            draw_grid_labels(
+                self.gizmos,
+                self.position,
+                self.rotation,
+                self.spacing.extend(0.),
+                self.cell_count.extend(0),
+                self.skew.extend(0.),
+                self.major_lines,
+                self.color,
+            );
+        }
     }
 }
 impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
@@ -197,6 +268,8 @@ where
     ///
     /// - The skew of the grid can be adjusted using the `.skew(...)`, `.skew_x(...)` or `.skew_y(...)` methods. They behave very similar to their CSS equivalents.
     /// - All outer edges can be toggled on or off using `.outer_edges(...)`. Alternatively you can use `.outer_edges_x(...)` or `.outer_edges_y(...)` to toggle the outer edges along an axis.
+    /// - Every nth line can be highlighted as a major line using `.major_lines(...)`.
+    /// - Numeric labels along the edges can be enabled using `.labels()`.
     ///
     /// # Example
     /// ```
@@ -234,6 +307,8 @@ where
             skew: Vec2::ZERO,
             outer_edges: [false, false],
             color: color.into(),
+            major_lines: None,
+            labels: false,
         }
     }
 
@@ -253,6 +328,8 @@ where
     ///
     /// - The skew of the grid can be adjusted using the `.skew(...)`, `.skew_x(...)`, `.skew_y(...)` or  `.skew_z(...)` methods. They behave very similar to their CSS equivalents.
     /// - All outer edges can be toggled on or off using `.outer_edges(...)`. Alternatively you can use `.outer_edges_x(...)`, `.outer_edges_y(...)` or `.outer_edges_z(...)` to toggle the outer edges along an axis.
+    /// - Every nth line can be highlighted as a major line using `.major_lines(...)`.
+    /// - Numeric labels along the edges can be enabled using `.labels()`.
     ///
     /// # Example
     /// ```
@@ -290,6 +367,8 @@ where
             skew: Vec3::ZERO,
             outer_edges: [false, false, false],
             color: color.into(),
+            major_lines: None,
+            labels: false,
         }
     }
 
@@ -309,13 +388,15 @@ where
     ///
     /// - The skew of the grid can be adjusted using the `.skew(...)`, `.skew_x(...)` or `.skew_y(...)` methods. They behave very similar to their CSS equivalents.
     /// - All outer edges can be toggled on or off using `.outer_edges(...)`. Alternatively you can use `.outer_edges_x(...)` or `.outer_edges_y(...)` to toggle the outer edges along an axis.
+    /// - Every nth line can be highlighted as a major line using `.major_lines(...)`.
+    /// - Numeric labels along the edges can be enabled using `.labels()`.
     ///
     /// # Example
     /// ```
     /// # use bevy_gizmos::prelude::*;
     /// # use bevy_render::prelude::*;
     /// # use bevy_math::prelude::*;
-    /// # use bevy_color::palettes::basic::GREEN;
+    /// # use bevy_color::{palettes::basic::GREEN, LinearRgba};
     /// fn system(mut gizmos: Gizmos) {
     ///     gizmos.grid_2d(
     ///         Vec2::ZERO,
@@ -326,6 +407,18 @@ where
     ///         )
     ///         .skew_x(0.25)
     ///         .outer_edges();
+    ///
+    ///     // A measurement grid with a brighter line every 5 cells and labeled edges.
+    ///     gizmos.grid_2d(
+    ///         Vec2::ZERO,
+    ///         0.0,
+    ///         UVec2::new(20, 20),
+    ///         Vec2::splat(10.),
+    ///         LinearRgba::rgb(0.1, 0.1, 0.1)
+    ///         )
+    ///         .major_lines(5, LinearRgba::rgb(0.4, 0.4, 0.4))
+    ///         .labels()
+    ///         .outer_edges();
     /// }
     /// # bevy_ecs::system::assert_is_system(system);
     /// ```
@@ -346,6 +439,8 @@ where
             skew: Vec2::ZERO,
             outer_edges: [false, false],
             color: color.into(),
+            major_lines: None,
+            labels: false,
         }
     }
 }
@@ -360,6 +455,7 @@ fn draw_grid<Config, Clear>(
     skew: Vec3,
     outer_edges: [bool; 3],
     color: LinearRgba,
+    major_lines: Option<(u32, LinearRgba)>,
 ) where
     Config: GizmoConfigGroup,
     Clear: 'static + Send + Sync,
@@ -368,6 +464,30 @@ fn draw_grid<Config, Clear>(
         return;
     }
 
+    // Index of the first drawn line along each axis, counted from the outer edge.
+    let first_line = outer_edges.map(|outer_edge| if outer_edge { 0 } else { 1 });
+    // A line is a major line if it lies on major lines along both other axes.
+    let major_line = |a: u32, b: u32| match major_lines {
+        Some((every, major_color)) if a % every == 0 && b % every == 0 => Some(major_color),
+        _ => None,
+    };
+    let line_width = if gizmos.line_width > 0. {
+        gizmos.line_width
+    } else {
+        gizmos.config.line_width
+    };
+    let mut draw_line = |start: Vec3, end: Vec3, major_color: Option<LinearRgba>| {
+        if let Some(major_color) = major_color {
+            gizmos.with_line_width(2. * line_width).line(
+                rotation * start,
+                rotation * end,
+                major_color,
+            );
+        } else {
+            gizmos.line(rotation * start, rotation * end, color);
+        }
+    };
+
     // Offset between two adjacent grid cells along the x/y-axis and accounting for skew.
     let dx = spacing.x
         * Vec3::new(1., skew.y.tan(), skew.z.tan())
@@ -409,37 +529,101 @@ fn draw_grid<Config, Clear>(
     // Lines along the x direction
     let dline = dx * cell_count.x as f32;
     for iy in 0..line_count.y {
-        let iy = iy as f32;
         for iz in 0..line_count.z {
-            let iz = iz as f32;
+            let major_color = major_line(iy + first_line[1], iz + first_line[2]);
+            let (iy, iz) = (iy as f32, iz as f32);
             let line_start = x_start + iy * dy + iz * dz;
             let line_end = line_start + dline;
 
-            gizmos.line(rotation * line_start, rotation * line_end, color);
+            draw_line(line_start, line_end, major_color);
         }
     }
     // Lines along the y direction
     let dline = dy * cell_count.y as f32;
     for ix in 0..line_count.x {
-        let ix = ix as f32;
         for iz in 0..line_count.z {
-            let iz = iz as f32;
+            let major_color = major_line(ix + first_line[0], iz + first_line[2]);
+            let (ix, iz) = (ix as f32, iz as f32);
             let line_start = y_start + ix * dx + iz * dz;
             let line_end = line_start + dline;
 
-            gizmos.line(rotation * line_start, rotation * line_end, color);
+            draw_line(line_start, line_end, major_color);
         }
     }
     // Lines along the z direction
     let dline = dz * cell_count.z as f32;
     for ix in 0..line_count.x {
-        let ix = ix as f32;
         for iy in 0..line_count.y {
-            let iy = iy as f32;
+            let major_color = major_line(ix + first_line[0], iy + first_line[1]);
+            let (ix, iy) = (ix as f32, iy as f32);
             let line_start = z_start + ix * dx + iy * dy;
             let line_end = line_start + dline;
 
-            gizmos.line(rotation * line_start, rotation * line_end, color);
+            draw_line(line_start, line_end, major_color);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_grid_labels<Config, Clear>(
+    gizmos: &mut Gizmos<'_, '_, Config, Clear>,
+    position: Vec3,
+    rotation: Quat,
+    spacing: Vec3,
+    cell_count: UVec3,
+    skew: Vec3,
+    major_lines: Option<(u32, LinearRgba)>,
+    color: LinearRgba,
+) where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    if !gizmos.enabled {
+        return;
+    }
+
+    let (every, color) = major_lines.unwrap_or((1, color));
+    // Size the labels to the smallest cells, ignoring the axes without any cells.
+    let size = 0.35
+        * Vec3::select(
+            cell_count.cmpne(UVec3::ZERO),
+            spacing,
+            Vec3::splat(f32::INFINITY),
+        )
+        .min_element();
+    if !size.is_finite() {
+        return;
+    }
+
+    // Offset between two adjacent grid cells along the x/y/z-axis and accounting for skew.
+    let dx = spacing.x * Vec3::new(1., skew.y.tan(), skew.z.tan());
+    let dy = spacing.y * Vec3::new(skew.x.tan(), 1., skew.z.tan());
+    let dz = spacing.z * Vec3::new(skew.x.tan(), skew.y.tan(), 1.);
+    let grid_start = position
+        - cell_count.x as f32 / 2.0 * dx
+        - cell_count.y as f32 / 2.0 * dy
+        - cell_count.z as f32 / 2.0 * dz;
+
+    // The labels of each axis are moved away from the grid along another axis.
+    let axes = [
+        (cell_count.x, spacing.x, dx, dy, Vec2::new(0., 0.5)),
+        (cell_count.y, spacing.y, dy, dx, Vec2::new(0.5, 0.)),
+        (cell_count.z, spacing.z, dz, dx, Vec2::new(0.5, 0.)),
+    ];
+    for (count, spacing, step, away, anchor) in axes {
+        if count == 0 {
+            continue;
+        }
+        for i in (0..=count).step_by(every as usize) {
+            let value = (i as f32 - count as f32 / 2.0) * spacing;
+            let label = format_tick(value, spacing * every as f32);
+            let label_position =
+                grid_start + i as f32 * step - away.normalize_or_zero() * 0.5 * size;
+            gizmos
+                .text(rotation * label_position, &label, color)
+                .rotation(rotation)
+                .size(size)
+                .anchor(anchor);
         }
     }
 }
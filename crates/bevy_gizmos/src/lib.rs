@@ -34,12 +34,23 @@ pub enum GizmoRenderSystem {
 pub mod aabb;
 pub mod arcs;
 pub mod arrows;
+pub mod axes;
+pub mod capture;
 pub mod circles;
 pub mod config;
+pub mod curves;
+pub mod filled;
 pub mod gizmos;
 pub mod grid;
+pub mod measure;
+pub mod palette;
+pub mod plot;
 pub mod primitives;
+pub mod retained;
 pub mod rounded_box;
+pub mod text;
+pub mod trail;
+pub mod vector_field;
 
 #[cfg(feature = "bevy_pbr")]
 pub mod light;
@@ -54,12 +65,14 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         aabb::{AabbGizmoConfigGroup, ShowAabbGizmo},
+        axes::{AxesGizmoConfigGroup, ShowAxesGizmo},
         config::{
             DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore,
             GizmoLineJoint, GizmoLineStyle,
         },
         gizmos::Gizmos,
         primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
+        trail::{GizmoTrail, TrailGizmoConfigGroup},
         AppGizmoBuilder,
     };
 
@@ -68,8 +81,9 @@ pub mod prelude {
 }
 
 use aabb::AabbGizmoPlugin;
+use axes::AxesGizmoPlugin;
 use bevy_app::{App, FixedFirst, FixedLast, Last, Plugin, RunFixedMainLoop};
-use bevy_asset::{load_internal_asset, Asset, AssetApp, Assets, Handle};
+use bevy_asset::{load_internal_asset, Asset, AssetApp, AssetId, Assets, Handle};
 use bevy_color::LinearRgba;
 use bevy_ecs::{
     component::Component,
@@ -84,19 +98,18 @@ use bevy_math::Vec3;
 use bevy_reflect::TypePath;
 use bevy_render::{
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
-    render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
     render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
     render_resource::{
         binding_types::uniform_buffer, BindGroup, BindGroupEntries, BindGroupLayout,
-        BindGroupLayoutEntries, Buffer, BufferInitDescriptor, BufferUsages, Shader, ShaderStages,
+        BindGroupLayoutEntries, Buffer, BufferDescriptor, BufferUsages, Shader, ShaderStages,
         ShaderType, VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode,
     },
-    renderer::RenderDevice,
+    renderer::{RenderDevice, RenderQueue},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
 use bevy_time::Fixed;
-use bevy_utils::TypeIdMap;
-use bytemuck::cast_slice;
+use bevy_utils::{hashbrown::hash_map::Entry, HashMap, TypeIdMap};
+use bytemuck::{bytes_of, cast_slice, Pod};
 use config::{
     DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoLineJoint,
     GizmoMeshConfig,
@@ -104,10 +117,18 @@ use config::{
 use gizmos::{GizmoStorage, Swap};
 #[cfg(feature = "bevy_pbr")]
 use light::LightGizmoPlugin;
-use std::{any::TypeId, mem};
+use palette::{Palette, PaletteLoader};
+use retained::{update_retained_gizmos, RetainedGizmos};
+use std::{any::TypeId, mem, ops::Range};
+use trail::TrailGizmoPlugin;
 
 const LINE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(7414812689238026784);
 const LINE_JOINT_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(1162780797909187908);
+const TRIANGLE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(5393460734921864271);
+
+/// The number of frames in a row a line gizmo has to use at most a quarter of its GPU buffers
+/// before they shrink.
+const SHRINK_FRAMES: u32 = 120;
 
 /// A [`Plugin`] that provides an immediate mode drawing api for visual debugging.
 ///
@@ -129,16 +150,23 @@ impl Plugin for GizmoPlugin {
             "line_joints.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            TRIANGLE_SHADER_HANDLE,
+            "triangles.wgsl",
+            Shader::from_wgsl
+        );
 
         app.register_type::<GizmoConfig>()
             .register_type::<GizmoConfigStore>()
             .add_plugins(UniformComponentPlugin::<LineGizmoUniform>::default())
             .init_asset::<LineGizmo>()
-            .add_plugins(RenderAssetPlugin::<GpuLineGizmo>::default())
+            .init_asset::<Palette>()
+            .init_asset_loader::<PaletteLoader>()
             .init_resource::<LineGizmoHandles>()
             // We insert the Resource GizmoConfigStore into the world implicitly here if it does not exist.
             .init_gizmo_group::<DefaultGizmoConfigGroup>()
-            .add_plugins(AabbGizmoPlugin);
+            .add_plugins((AabbGizmoPlugin, AxesGizmoPlugin, TrailGizmoPlugin));
 
         #[cfg(feature = "bevy_pbr")]
         app.add_plugins(LightGizmoPlugin);
@@ -147,12 +175,18 @@ impl Plugin for GizmoPlugin {
             return;
         };
 
-        render_app.add_systems(
-            Render,
-            prepare_line_gizmo_bind_group.in_set(RenderSet::PrepareBindGroups),
-        );
+        render_app
+            .init_resource::<LineGizmoBuffers>()
+            .init_resource::<ExtractedLineGizmos>()
+            .add_systems(
+                Render,
+                (
+                    prepare_line_gizmo_buffers.in_set(RenderSet::PrepareAssets),
+                    prepare_line_gizmo_bind_group.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
 
-        render_app.add_systems(ExtractSchedule, extract_gizmo_data);
+        render_app.add_systems(ExtractSchedule, (extract_gizmo_data, extract_line_gizmos));
 
         #[cfg(feature = "bevy_sprite")]
         if app.is_plugin_added::<bevy_sprite::SpritePlugin>() {
@@ -177,7 +211,7 @@ impl Plugin for GizmoPlugin {
         let line_layout = render_device.create_bind_group_layout(
             "LineGizmoUniform layout",
             &BindGroupLayoutEntries::single(
-                ShaderStages::VERTEX,
+                ShaderStages::VERTEX_FRAGMENT,
                 uniform_buffer::<LineGizmoUniform>(true),
             ),
         );
@@ -221,10 +255,12 @@ impl AppGizmoBuilder for App {
 
         handles.list.insert(TypeId::of::<Config>(), None);
         handles.strip.insert(TypeId::of::<Config>(), None);
+        handles.triangles.insert(TypeId::of::<Config>(), None);
 
         self.init_resource::<GizmoStorage<Config, ()>>()
             .init_resource::<GizmoStorage<Config, Fixed>>()
             .init_resource::<GizmoStorage<Config, Swap<Fixed>>>()
+            .init_resource::<RetainedGizmos<Config>>()
             .add_systems(
                 RunFixedMainLoop,
                 start_gizmo_context::<Config, Fixed>.before(bevy_time::run_fixed_main_schedule),
@@ -239,6 +275,7 @@ impl AppGizmoBuilder for App {
                 Last,
                 (
                     propagate_gizmos::<Config, Fixed>.before(UpdateGizmoMeshes),
+                    update_retained_gizmos::<Config>.before(UpdateGizmoMeshes),
                     update_gizmo_meshes::<Config>.in_set(UpdateGizmoMeshes),
                 ),
             );
@@ -270,6 +307,7 @@ impl AppGizmoBuilder for App {
 struct LineGizmoHandles {
     list: TypeIdMap<Option<Handle<LineGizmo>>>,
     strip: TypeIdMap<Option<Handle<LineGizmo>>>,
+    triangles: TypeIdMap<Option<Handle<LineGizmo>>>,
 }
 
 /// Start a new gizmo clearing context.
@@ -344,56 +382,125 @@ pub struct UpdateGizmoMeshes;
 /// Prepare gizmos for rendering.
 ///
 /// This also clears the default `GizmoStorage`.
+///
+/// The line gizmo assets are only modified if their vertices changed, so unchanged gizmos are not
+/// uploaded to the GPU again. The vertex allocations of the previous frame are reused.
 fn update_gizmo_meshes<Config: GizmoConfigGroup>(
     mut line_gizmos: ResMut<Assets<LineGizmo>>,
     mut handles: ResMut<LineGizmoHandles>,
     mut storage: ResMut<GizmoStorage<Config, ()>>,
     config_store: Res<GizmoConfigStore>,
 ) {
-    if storage.list_positions.is_empty() {
-        handles.list.insert(TypeId::of::<Config>(), None);
-    } else if let Some(handle) = handles.list.get_mut(&TypeId::of::<Config>()) {
-        if let Some(handle) = handle {
-            let list = line_gizmos.get_mut(handle.id()).unwrap();
-
-            list.positions = mem::take(&mut storage.list_positions);
-            list.colors = mem::take(&mut storage.list_colors);
-        } else {
-            let mut list = LineGizmo {
-                strip: false,
-                ..Default::default()
-            };
-
-            list.positions = mem::take(&mut storage.list_positions);
-            list.colors = mem::take(&mut storage.list_colors);
-
-            *handle = Some(line_gizmos.add(list));
-        }
-    }
-
+    let storage = &mut *storage;
+    let handles = &mut *handles;
     let (config, _) = config_store.config::<Config>();
-    if storage.strip_positions.is_empty() {
-        handles.strip.insert(TypeId::of::<Config>(), None);
-    } else if let Some(handle) = handles.strip.get_mut(&TypeId::of::<Config>()) {
-        if let Some(handle) = handle {
-            let strip = line_gizmos.get_mut(handle.id()).unwrap();
+    let vertex_capacity = config.vertex_capacity;
+
+    let list = LineGizmo {
+        positions: mem::take(&mut storage.list_positions),
+        colors: mem::take(&mut storage.list_colors),
+        widths: mem::take(&mut storage.list_widths),
+        flags: mem::take(&mut storage.list_flags),
+        strip: false,
+        ..Default::default()
+    };
+    let list = update_line_gizmo(
+        &mut line_gizmos,
+        handles.list.entry(TypeId::of::<Config>()).or_default(),
+        list,
+        vertex_capacity,
+    );
+    storage.list_positions = list.positions;
+    storage.list_colors = list.colors;
+    storage.list_widths = list.widths;
+    storage.list_flags = list.flags;
+
+    let strip = LineGizmo {
+        positions: mem::take(&mut storage.strip_positions),
+        colors: mem::take(&mut storage.strip_colors),
+        widths: mem::take(&mut storage.strip_widths),
+        flags: mem::take(&mut storage.strip_flags),
+        strip: true,
+        joints: config.line_joints,
+        ..Default::default()
+    };
+    let strip = update_line_gizmo(
+        &mut line_gizmos,
+        handles.strip.entry(TypeId::of::<Config>()).or_default(),
+        strip,
+        vertex_capacity,
+    );
+    storage.strip_positions = strip.positions;
+    storage.strip_colors = strip.colors;
+    storage.strip_widths = strip.widths;
+    storage.strip_flags = strip.flags;
+
+    // Triangles have no line widths
+    let triangles = LineGizmo {
+        positions: mem::take(&mut storage.triangle_positions),
+        colors: mem::take(&mut storage.triangle_colors),
+        flags: mem::take(&mut storage.triangle_flags),
+        triangles: true,
+        ..Default::default()
+    };
+    let triangles = update_line_gizmo(
+        &mut line_gizmos,
+        handles.triangles.entry(TypeId::of::<Config>()).or_default(),
+        triangles,
+        vertex_capacity,
+    );
+    storage.triangle_positions = triangles.positions;
+    storage.triangle_colors = triangles.colors;
+    storage.triangle_flags = triangles.flags;
+
+    // Keep the allocations swapped out of the assets for the next frame
+    storage.clear();
+}
 
-            strip.positions = mem::take(&mut storage.strip_positions);
-            strip.colors = mem::take(&mut storage.strip_colors);
-            strip.joints = config.line_joints;
-        } else {
-            let mut strip = LineGizmo {
-                strip: true,
-                joints: config.line_joints,
-                ..Default::default()
-            };
+/// Replaces the line gizmo at `handle` with `gizmo`, creating it if needed, and returns the
+/// previous vertex allocations of the gizmo for reuse.
+///
+/// Only the range of vertices that changed is recorded to be uploaded to the GPU, and an existing
+/// gizmo isn't modified if its vertices didn't change, unless its buffers are resized.
+fn update_line_gizmo(
+    line_gizmos: &mut Assets<LineGizmo>,
+    handle: &mut Option<Handle<LineGizmo>>,
+    mut gizmo: LineGizmo,
+    vertex_capacity: usize,
+) -> LineGizmo {
+    if gizmo.positions.is_empty() {
+        *handle = None;
+        return gizmo;
+    }
 
-            strip.positions = mem::take(&mut storage.strip_positions);
-            strip.colors = mem::take(&mut storage.strip_colors);
+    let Some(handle) = handle else {
+        gizmo.changed = 0..gizmo.positions.len();
+        gizmo.reserve(vertex_capacity);
+        *handle = Some(line_gizmos.add(gizmo));
+        return LineGizmo::default();
+    };
 
-            *handle = Some(line_gizmos.add(strip));
+    let existing = line_gizmos.get(handle.id()).unwrap();
+    let changed = existing.changed_range(&gizmo);
+    if changed.is_none() && existing.joints == gizmo.joints {
+        // Unchanged gizmos still count towards shrinking their buffers, and grow them when the
+        // vertex capacity of their config grows
+        if vertex_capacity > existing.capacity || existing.underused(vertex_capacity) {
+            let existing = line_gizmos.get_mut(handle.id()).unwrap();
+            if existing.reserve(vertex_capacity) {
+                existing.version = existing.version.wrapping_add(1);
+            }
         }
+        return gizmo;
     }
+
+    let existing = line_gizmos.get_mut(handle.id()).unwrap();
+    existing.swap_vertices(&mut gizmo);
+    existing.joints = gizmo.joints;
+    existing.changed = changed.unwrap_or(0..0);
+    existing.version = existing.version.wrapping_add(1);
+    existing.reserve(vertex_capacity);
+    gizmo
 }
 
 fn extract_gizmo_data(
@@ -401,7 +508,12 @@ fn extract_gizmo_data(
     handles: Extract<Res<LineGizmoHandles>>,
     config: Extract<Res<GizmoConfigStore>>,
 ) {
-    for (group_type_id, handle) in handles.list.iter().chain(handles.strip.iter()) {
+    for (group_type_id, handle) in handles
+        .list
+        .iter()
+        .chain(handles.strip.iter())
+        .chain(handles.triangles.iter())
+    {
         let Some((config, _)) = config.get_config_dyn(group_type_id) else {
             continue;
         };
@@ -425,8 +537,7 @@ fn extract_gizmo_data(
                 line_width: config.line_width,
                 depth_bias: config.depth_bias,
                 joints_resolution,
-                #[cfg(feature = "webgl")]
-                _padding: Default::default(),
+                occluded_opacity: config.occluded_opacity,
             },
             (*handle).clone_weak(),
             GizmoMeshConfig::from(config),
@@ -440,59 +551,306 @@ struct LineGizmoUniform {
     depth_bias: f32,
     // Only used by gizmo line t if the current configs `line_joints` is set to `GizmoLineJoint::Round(_)`
     joints_resolution: u32,
-    /// WebGL2 structs must be 16 byte aligned.
-    #[cfg(feature = "webgl")]
-    _padding: f32,
+    // Only used when drawing the occluded parts of lines, see `GizmoConfig::occluded_opacity`
+    // This also pads the struct to 16 bytes, as required by WebGL2.
+    occluded_opacity: f32,
 }
 
 #[derive(Asset, Debug, Default, Clone, TypePath)]
 struct LineGizmo {
     positions: Vec<Vec3>,
     colors: Vec<LinearRgba>,
+    /// Per-vertex line widths. A width of zero means the line width of the [`GizmoConfig`] is used.
+    widths: Vec<f32>,
+    /// Per-vertex flags, such as [`SCREEN_SPACE_FLAG`](gizmos::SCREEN_SPACE_FLAG).
+    flags: Vec<u32>,
     /// Whether this gizmo's topology is a line-strip or line-list
     strip: bool,
+    /// Whether this gizmo's topology is a triangle-list of filled shapes instead of lines.
+    /// Triangles have no line widths.
+    triangles: bool,
     /// Whether this gizmo should draw line joints. This is only applicable if the gizmo's topology is line-strip.
     joints: GizmoLineJoint,
+    /// The number of vertices the GPU buffers of this gizmo are allocated for.
+    capacity: usize,
+    /// The number of frames in a row this gizmo used at most a quarter of its capacity.
+    underused_frames: u32,
+    /// The range of vertices that changed in the last modification of this gizmo.
+    changed: Range<usize>,
+    /// Incremented on every modification of this gizmo, to tell whether the GPU buffers missed
+    /// one and need to be uploaded in full.
+    version: u32,
+}
+
+impl LineGizmo {
+    /// Returns the range of vertices that differ between this gizmo and `other`, or `None` if
+    /// they have the same vertices.
+    fn changed_range(&self, other: &LineGizmo) -> Option<Range<usize>> {
+        [
+            changed_range(&self.positions, &other.positions),
+            changed_range(&self.colors, &other.colors),
+            changed_range(&self.widths, &other.widths),
+            changed_range(&self.flags, &other.flags),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+    }
+
+    /// Swap the vertices of this gizmo with those of `other`.
+    fn swap_vertices(&mut self, other: &mut LineGizmo) {
+        mem::swap(&mut self.positions, &mut other.positions);
+        mem::swap(&mut self.colors, &mut other.colors);
+        mem::swap(&mut self.widths, &mut other.widths);
+        mem::swap(&mut self.flags, &mut other.flags);
+    }
+
+    /// Returns the smallest capacity fitting the vertices of this gizmo, and at least
+    /// `vertex_capacity` vertices.
+    fn fitting_capacity(&self, vertex_capacity: usize) -> usize {
+        self.positions
+            .len()
+            .next_power_of_two()
+            .max(vertex_capacity)
+    }
+
+    /// Returns whether this gizmo uses at most a quarter of its GPU buffers.
+    fn underused(&self, vertex_capacity: usize) -> bool {
+        self.fitting_capacity(vertex_capacity) <= self.capacity / 4
+    }
+
+    /// Resizes the GPU buffers to fit the vertices of this gizmo, and at least `vertex_capacity`
+    /// vertices, and returns whether they were resized. The buffers are uploaded in full when
+    /// they are resized.
+    ///
+    /// The buffers grow right away, but only shrink once this gizmo used at most a quarter of
+    /// them for [`SHRINK_FRAMES`] frames in a row, so a single frame with many vertices doesn't
+    /// keep them large for the rest of the app.
+    fn reserve(&mut self, vertex_capacity: usize) -> bool {
+        let len = self.positions.len();
+        if len > self.capacity || vertex_capacity > self.capacity {
+            self.capacity = self.fitting_capacity(vertex_capacity);
+        } else if self.underused(vertex_capacity) {
+            self.underused_frames += 1;
+            if self.underused_frames < SHRINK_FRAMES {
+                return false;
+            }
+            self.capacity = self.fitting_capacity(vertex_capacity);
+        } else {
+            self.underused_frames = 0;
+            return false;
+        }
+        self.underused_frames = 0;
+        self.changed = 0..len;
+        true
+    }
 }
 
+/// Returns the range of `new` that differs from `old`, or `None` if they are equal.
+fn changed_range<T: Pod>(old: &[T], new: &[T]) -> Option<Range<usize>> {
+    // Compare bytes rather than values, as the NaN separators of strips never compare equal
+    if cast_slice::<T, u8>(old) == cast_slice::<T, u8>(new) {
+        return None;
+    }
+    let differ = |(a, b): (&T, &T)| bytes_of(a) != bytes_of(b);
+
+    let start = old
+        .iter()
+        .zip(new)
+        .position(differ)
+        .unwrap_or(old.len().min(new.len()));
+    let end = if old.len() == new.len() {
+        let unchanged_end = old.iter().rev().zip(new.iter().rev()).position(differ);
+        new.len() - unchanged_end.unwrap_or(0)
+    } else {
+        new.len()
+    };
+    Some(start..end)
+}
+
+/// The GPU buffers of a [`LineGizmo`], which are kept across frames.
 #[derive(Debug, Clone)]
 struct GpuLineGizmo {
     position_buffer: Buffer,
     color_buffer: Buffer,
+    width_buffer: Buffer,
+    flag_buffer: Buffer,
     vertex_count: u32,
     strip: bool,
+    triangles: bool,
     joints: GizmoLineJoint,
+    /// The number of vertices the buffers are allocated for.
+    capacity: usize,
+    /// The [`LineGizmo::version`] the buffers are up to date with.
+    version: u32,
 }
 
-impl RenderAsset for GpuLineGizmo {
-    type SourceAsset = LineGizmo;
-    type Param = SRes<RenderDevice>;
+/// The [`GpuLineGizmo`] of each [`LineGizmo`].
+#[derive(Resource, Default)]
+struct LineGizmoBuffers(HashMap<AssetId<LineGizmo>, GpuLineGizmo>);
 
-    fn prepare_asset(
-        gizmo: Self::SourceAsset,
-        render_device: &mut SystemParamItem<Self::Param>,
-    ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
-        let position_buffer_data = cast_slice(&gizmo.positions);
-        let position_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Position Buffer"),
-            contents: position_buffer_data,
-        });
+impl LineGizmoBuffers {
+    fn get(&self, id: impl Into<AssetId<LineGizmo>>) -> Option<&GpuLineGizmo> {
+        self.0.get(&id.into())
+    }
+}
 
-        let color_buffer_data = cast_slice(&gizmo.colors);
-        let color_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Color Buffer"),
-            contents: color_buffer_data,
-        });
+/// The changed vertices of a [`LineGizmo`], extracted to the render world.
+struct ExtractedLineGizmo {
+    id: AssetId<LineGizmo>,
+    /// The index of the first vertex in `positions`, `colors`, `widths` and `flags`.
+    offset: usize,
+    positions: Vec<Vec3>,
+    colors: Vec<LinearRgba>,
+    widths: Vec<f32>,
+    flags: Vec<u32>,
+    vertex_count: usize,
+    strip: bool,
+    triangles: bool,
+    joints: GizmoLineJoint,
+    capacity: usize,
+    version: u32,
+}
 
-        Ok(GpuLineGizmo {
-            position_buffer,
-            color_buffer,
-            vertex_count: gizmo.positions.len() as u32,
+#[derive(Resource, Default)]
+struct ExtractedLineGizmos {
+    changed: Vec<ExtractedLineGizmo>,
+    /// All line gizmos of this frame, the buffers of any other gizmo are freed.
+    live: Vec<AssetId<LineGizmo>>,
+}
+
+fn extract_line_gizmos(
+    mut extracted: ResMut<ExtractedLineGizmos>,
+    buffers: Res<LineGizmoBuffers>,
+    handles: Extract<Res<LineGizmoHandles>>,
+    line_gizmos: Extract<Res<Assets<LineGizmo>>>,
+) {
+    extracted.changed.clear();
+    extracted.live.clear();
+
+    for handle in handles
+        .list
+        .values()
+        .chain(handles.strip.values())
+        .chain(handles.triangles.values())
+        .flatten()
+    {
+        let Some(gizmo) = line_gizmos.get(handle) else {
+            continue;
+        };
+        extracted.live.push(handle.id());
+
+        // Upload everything unless the buffers only missed the last modification and didn't grow
+        let changed = match buffers.get(handle) {
+            Some(gpu) if gpu.version == gizmo.version => continue,
+            Some(gpu)
+                if gpu.version.wrapping_add(1) == gizmo.version
+                    && gpu.capacity == gizmo.capacity =>
+            {
+                gizmo.changed.clone()
+            }
+            _ => 0..gizmo.positions.len(),
+        };
+        let slice = |len: usize| changed.start.min(len)..changed.end.min(len);
+
+        extracted.changed.push(ExtractedLineGizmo {
+            id: handle.id(),
+            offset: changed.start,
+            positions: gizmo.positions[slice(gizmo.positions.len())].to_vec(),
+            colors: gizmo.colors[slice(gizmo.colors.len())].to_vec(),
+            widths: gizmo.widths[slice(gizmo.widths.len())].to_vec(),
+            flags: gizmo.flags[slice(gizmo.flags.len())].to_vec(),
+            vertex_count: gizmo.positions.len(),
             strip: gizmo.strip,
+            triangles: gizmo.triangles,
             joints: gizmo.joints,
-        })
+            capacity: gizmo.capacity,
+            version: gizmo.version,
+        });
+    }
+}
+
+/// Uploads the changed vertices of the line gizmos to their buffers, which are only reallocated
+/// when they need to grow.
+fn prepare_line_gizmo_buffers(
+    mut extracted: ResMut<ExtractedLineGizmos>,
+    mut buffers: ResMut<LineGizmoBuffers>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let ExtractedLineGizmos { changed, live } = &mut *extracted;
+    buffers.0.retain(|id, _| live.contains(id));
+
+    for gizmo in changed.drain(..) {
+        let create_buffer = |label: &str, vertex_size: usize| {
+            render_device.create_buffer(&BufferDescriptor {
+                label: Some(label),
+                size: (gizmo.capacity * vertex_size) as u64,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+
+        let gpu = match buffers.0.entry(gizmo.id) {
+            Entry::Occupied(entry) if entry.get().capacity == gizmo.capacity => entry.into_mut(),
+            entry => {
+                let gpu = GpuLineGizmo {
+                    position_buffer: create_buffer(
+                        "LineGizmo Position Buffer",
+                        mem::size_of::<Vec3>(),
+                    ),
+                    color_buffer: create_buffer(
+                        "LineGizmo Color Buffer",
+                        mem::size_of::<LinearRgba>(),
+                    ),
+                    width_buffer: create_buffer("LineGizmo Width Buffer", mem::size_of::<f32>()),
+                    flag_buffer: create_buffer("LineGizmo Flag Buffer", mem::size_of::<u32>()),
+                    vertex_count: 0,
+                    strip: gizmo.strip,
+                    triangles: gizmo.triangles,
+                    joints: gizmo.joints,
+                    capacity: gizmo.capacity,
+                    version: gizmo.version,
+                };
+                match entry {
+                    Entry::Occupied(mut entry) => {
+                        entry.insert(gpu);
+                        entry.into_mut()
+                    }
+                    Entry::Vacant(entry) => entry.insert(gpu),
+                }
+            }
+        };
+
+        let write = |buffer: &Buffer, data: &[u8], vertex_size: usize| {
+            if !data.is_empty() {
+                render_queue.write_buffer(buffer, (gizmo.offset * vertex_size) as u64, data);
+            }
+        };
+        write(
+            &gpu.position_buffer,
+            cast_slice(&gizmo.positions),
+            mem::size_of::<Vec3>(),
+        );
+        write(
+            &gpu.color_buffer,
+            cast_slice(&gizmo.colors),
+            mem::size_of::<LinearRgba>(),
+        );
+        write(
+            &gpu.width_buffer,
+            cast_slice(&gizmo.widths),
+            mem::size_of::<f32>(),
+        );
+        write(
+            &gpu.flag_buffer,
+            cast_slice(&gizmo.flags),
+            mem::size_of::<u32>(),
+        );
+
+        gpu.vertex_count = gizmo.vertex_count as u32;
+        gpu.joints = gizmo.joints;
+        gpu.version = gizmo.version;
     }
 }
 
@@ -551,7 +909,7 @@ impl<const I: usize, P: PhaseItem> RenderCommand<P> for SetLineGizmoBindGroup<I>
 
 struct DrawLineGizmo;
 impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
-    type Param = SRes<RenderAssets<GpuLineGizmo>>;
+    type Param = SRes<LineGizmoBuffers>;
     type ViewQuery = ();
     type ItemQuery = Read<Handle<LineGizmo>>;
 
@@ -581,11 +939,17 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
             pass.set_vertex_buffer(2, line_gizmo.color_buffer.slice(..));
             pass.set_vertex_buffer(3, line_gizmo.color_buffer.slice(..));
 
+            pass.set_vertex_buffer(4, line_gizmo.width_buffer.slice(..));
+            pass.set_vertex_buffer(5, line_gizmo.flag_buffer.slice(..));
+
             u32::max(line_gizmo.vertex_count, 1) - 1
         } else {
             pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..));
             pass.set_vertex_buffer(1, line_gizmo.color_buffer.slice(..));
 
+            pass.set_vertex_buffer(2, line_gizmo.width_buffer.slice(..));
+            pass.set_vertex_buffer(3, line_gizmo.flag_buffer.slice(..));
+
             line_gizmo.vertex_count / 2
         };
 
@@ -597,7 +961,7 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
 
 struct DrawLineJointGizmo;
 impl<P: PhaseItem> RenderCommand<P> for DrawLineJointGizmo {
-    type Param = SRes<RenderAssets<GpuLineGizmo>>;
+    type Param = SRes<LineGizmoBuffers>;
     type ViewQuery = ();
     type ItemQuery = Read<Handle<LineGizmo>>;
 
@@ -631,6 +995,9 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineJointGizmo {
 
             pass.set_vertex_buffer(3, line_gizmo.color_buffer.slice(..));
 
+            pass.set_vertex_buffer(4, line_gizmo.width_buffer.slice(..));
+            pass.set_vertex_buffer(5, line_gizmo.flag_buffer.slice(..));
+
             u32::max(line_gizmo.vertex_count, 2) - 2
         };
 
@@ -647,6 +1014,41 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineJointGizmo {
     }
 }
 
+struct DrawTriangleGizmo;
+impl<P: PhaseItem> RenderCommand<P> for DrawTriangleGizmo {
+    type Param = SRes<LineGizmoBuffers>;
+    type ViewQuery = ();
+    type ItemQuery = Read<Handle<LineGizmo>>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        handle: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        line_gizmos: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(handle) = handle else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(line_gizmo) = line_gizmos.into_inner().get(handle) else {
+            return RenderCommandResult::Failure;
+        };
+
+        if line_gizmo.vertex_count < 3 || !line_gizmo.triangles {
+            return RenderCommandResult::Success;
+        }
+
+        pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..));
+        pass.set_vertex_buffer(1, line_gizmo.color_buffer.slice(..));
+        pass.set_vertex_buffer(2, line_gizmo.flag_buffer.slice(..));
+
+        pass.draw(0..line_gizmo.vertex_count, 0..1);
+
+        RenderCommandResult::Success
+    }
+}
+
 fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
     use VertexFormat::*;
     let mut position_layout = VertexBufferLayout {
@@ -669,6 +1071,27 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
         }],
     };
 
+    // Each segment is drawn with the width and flags of its first vertex
+    let mut width_layout = VertexBufferLayout {
+        array_stride: Float32.size(),
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![VertexAttribute {
+            format: Float32,
+            offset: 0,
+            shader_location: 4,
+        }],
+    };
+
+    let mut flag_layout = VertexBufferLayout {
+        array_stride: Uint32.size(),
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![VertexAttribute {
+            format: Uint32,
+            offset: 0,
+            shader_location: 5,
+        }],
+    };
+
     if strip {
         vec![
             position_layout.clone(),
@@ -683,6 +1106,8 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
                 color_layout.attributes[0].offset = Float32x4.size();
                 color_layout
             },
+            width_layout,
+            flag_layout,
         ]
     } else {
         position_layout.array_stride *= 2;
@@ -699,7 +1124,10 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
             shader_location: 3,
         });
 
-        vec![position_layout, color_layout]
+        width_layout.array_stride *= 2;
+        flag_layout.array_stride *= 2;
+
+        vec![position_layout, color_layout, width_layout, flag_layout]
     }
 }
 
@@ -725,6 +1153,27 @@ fn line_joint_gizmo_vertex_buffer_layouts() -> Vec<VertexBufferLayout> {
         }],
     };
 
+    // Each joint is drawn with the width and flags of its middle vertex
+    let width_layout = VertexBufferLayout {
+        array_stride: Float32.size(),
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![VertexAttribute {
+            format: Float32,
+            offset: Float32.size(),
+            shader_location: 4,
+        }],
+    };
+
+    let flag_layout = VertexBufferLayout {
+        array_stride: Uint32.size(),
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![VertexAttribute {
+            format: Uint32,
+            offset: Uint32.size(),
+            shader_location: 5,
+        }],
+    };
+
     vec![
         position_layout.clone(),
         {
@@ -737,6 +1186,144 @@ fn line_joint_gizmo_vertex_buffer_layouts() -> Vec<VertexBufferLayout> {
             position_layout.attributes[0].offset = 2 * Float32x3.size();
             position_layout
         },
-        color_layout.clone(),
+        color_layout,
+        width_layout,
+        flag_layout,
     ]
 }
+
+fn triangle_gizmo_vertex_buffer_layouts() -> Vec<VertexBufferLayout> {
+    use VertexFormat::*;
+    [(Float32x3, 0), (Float32x4, 1), (Uint32, 2)]
+        .into_iter()
+        .map(|(format, shader_location)| VertexBufferLayout {
+            array_stride: format.size(),
+            step_mode: VertexStepMode::Vertex,
+            attributes: vec![VertexAttribute {
+                format,
+                offset: 0,
+                shader_location,
+            }],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_gizmo(len: usize) -> LineGizmo {
+        LineGizmo {
+            positions: vec![Vec3::ZERO; len],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reserve_grows_right_away() {
+        let mut gizmo = line_gizmo(5);
+        assert!(gizmo.reserve(0));
+        assert_eq!(gizmo.capacity, 8);
+        assert_eq!(gizmo.changed, 0..5);
+
+        gizmo.positions.resize(6, Vec3::ZERO);
+        assert!(!gizmo.reserve(0));
+        assert!(gizmo.reserve(100));
+        assert_eq!(gizmo.capacity, 100);
+    }
+
+    #[test]
+    fn reserve_shrinks_after_underused_frames() {
+        let mut gizmo = line_gizmo(1000);
+        gizmo.reserve(0);
+        assert_eq!(gizmo.capacity, 1024);
+
+        // Using half of the buffers doesn't shrink them
+        gizmo.positions.truncate(512);
+        for _ in 0..SHRINK_FRAMES {
+            assert!(!gizmo.reserve(0));
+        }
+
+        // A frame using more than a quarter of the buffers starts the count over
+        gizmo.positions.truncate(10);
+        for _ in 1..SHRINK_FRAMES {
+            assert!(!gizmo.reserve(0));
+        }
+        gizmo.positions.resize(300, Vec3::ZERO);
+        assert!(!gizmo.reserve(0));
+        gizmo.positions.truncate(10);
+        for _ in 1..SHRINK_FRAMES {
+            assert!(!gizmo.reserve(0));
+        }
+        assert!(gizmo.reserve(0));
+        assert_eq!(gizmo.capacity, 16);
+        assert_eq!(gizmo.changed, 0..10);
+    }
+
+    #[test]
+    fn reserve_keeps_vertex_capacity() {
+        let mut gizmo = line_gizmo(1000);
+        gizmo.reserve(200);
+        gizmo.positions.truncate(10);
+        for _ in 1..SHRINK_FRAMES {
+            gizmo.reserve(200);
+        }
+        assert!(gizmo.reserve(200));
+        assert_eq!(gizmo.capacity, 200);
+    }
+
+    #[test]
+    fn changed_range_of_equal_slices() {
+        assert_eq!(
+            changed_range::<f32>(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]),
+            None
+        );
+        assert_eq!(changed_range::<f32>(&[], &[]), None);
+    }
+
+    #[test]
+    fn changed_range_with_nan_separators() {
+        let strip = [Vec3::ZERO, Vec3::NAN, Vec3::ONE];
+        assert_eq!(changed_range(&strip, &strip), None);
+    }
+
+    #[test]
+    fn changed_range_in_the_middle() {
+        let old = [1, 2, 3, 4, 5, 6];
+        let new = [1, 2, 7, 4, 8, 6];
+        assert_eq!(changed_range::<u32>(&old, &new), Some(2..5));
+    }
+
+    #[test]
+    fn changed_range_of_longer_slice() {
+        assert_eq!(
+            changed_range::<u32>(&[1, 2, 3], &[1, 2, 3, 4, 5]),
+            Some(3..5)
+        );
+        assert_eq!(changed_range::<u32>(&[1, 2, 3], &[1, 9, 3, 4]), Some(1..4));
+    }
+
+    #[test]
+    fn changed_range_of_shorter_prefix() {
+        // Nothing in `new` changed, but it is shorter, so the vertex count still needs updating
+        assert_eq!(changed_range::<u32>(&[1, 2, 3, 4], &[1, 2]), Some(2..2));
+    }
+
+    #[test]
+    fn static_gizmo_grows_with_vertex_capacity() {
+        let mut line_gizmos = Assets::<LineGizmo>::default();
+        let mut handle = None;
+
+        update_line_gizmo(&mut line_gizmos, &mut handle, line_gizmo(5), 0);
+        let id = handle.as_ref().unwrap().id();
+        assert_eq!(line_gizmos.get(id).unwrap().capacity, 8);
+        let version = line_gizmos.get(id).unwrap().version;
+
+        // The same vertices again, but with a larger vertex capacity in the config
+        update_line_gizmo(&mut line_gizmos, &mut handle, line_gizmo(5), 64);
+        let gizmo = line_gizmos.get(id).unwrap();
+        assert_eq!(gizmo.capacity, 64);
+        assert_eq!(gizmo.changed, 0..5);
+        assert_ne!(gizmo.version, version);
+    }
+}
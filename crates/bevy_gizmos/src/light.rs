@@ -7,7 +7,7 @@ use crate::{self as bevy_gizmos, primitives::dim3::GizmoPrimitive3d};
 use bevy_app::{Plugin, PostUpdate};
 use bevy_color::{
     palettes::basic::{BLUE, GREEN, RED},
-    Color, Oklcha,
+    Alpha, Color, Oklcha,
 };
 use bevy_ecs::{
     component::Component,
@@ -31,7 +31,24 @@ use crate::{
     AppGizmoBuilder,
 };
 
+/// The fractions of the range attenuation of a light at which its falloff is drawn.
+const FALLOFF_LEVELS: [f32; 3] = [0.75, 0.5, 0.25];
+
+/// Returns the distances at which the range attenuation of a light with the given range drops
+/// to each of the [`FALLOFF_LEVELS`].
+///
+/// This inverts the smooth window `(1 - (distance / range)^4)^2` applied by the PBR shaders,
+/// ignoring the inverse square falloff which does not depend on the range.
+fn falloff_distances(range: f32) -> impl Iterator<Item = (f32, f32)> {
+    FALLOFF_LEVELS
+        .into_iter()
+        .map(move |level| (level, range * (1.0 - level.sqrt()).powf(0.25)))
+}
+
 /// Draws a standard sphere for the radius and an axis sphere for the range.
+///
+/// If [`LightGizmoConfigGroup::draw_falloff`] is set, fainter spheres are drawn where the range
+/// attenuation drops to 75%, 50% and 25%.
 fn point_light_gizmo(
     transform: &GlobalTransform,
     point_light: &PointLight,
@@ -52,10 +69,26 @@ fn point_light_gizmo(
     gizmos
         .sphere(position, Quat::IDENTITY, point_light.range, color)
         .resolution(32);
+
+    if gizmos.config_ext.draw_falloff {
+        for (level, distance) in falloff_distances(point_light.range) {
+            gizmos
+                .sphere(
+                    position,
+                    Quat::IDENTITY,
+                    distance,
+                    color.with_alpha(color.alpha() * level),
+                )
+                .resolution(32);
+        }
+    }
 }
 
 /// Draws a sphere for the radius, two cones for the inner and outer angles, plus two 3d arcs crossing the
 /// farthest point of effect of the spot light along its direction.
+///
+/// If [`LightGizmoConfigGroup::draw_falloff`] is set, fainter arcs are drawn where the range
+/// attenuation drops to 75%, 50% and 25%.
 fn spot_light_gizmo(
     transform: &GlobalTransform,
     spot_light: &SpotLight,
@@ -92,6 +125,17 @@ fn spot_light_gizmo(
             .base_resolution(32);
     }
 
+    let falloff = gizmos
+        .config_ext
+        .draw_falloff
+        .then(|| falloff_distances(spot_light.range))
+        .into_iter()
+        .flatten()
+        .map(|(level, distance)| (distance, color.with_alpha(color.alpha() * level)));
+    let arcs: Vec<_> = std::iter::once((spot_light.range, color))
+        .chain(falloff)
+        .collect();
+
     for arc_rotation in [
         Quat::from_rotation_y(PI / 2.0 - spot_light.outer_angle),
         Quat::from_euler(
@@ -101,15 +145,17 @@ fn spot_light_gizmo(
             PI / 2.0 - spot_light.outer_angle,
         ),
     ] {
-        gizmos
-            .arc_3d(
-                2.0 * spot_light.outer_angle,
-                spot_light.range,
-                translation,
-                rotation * arc_rotation,
-                color,
-            )
-            .resolution(16);
+        for &(radius, color) in &arcs {
+            gizmos
+                .arc_3d(
+                    2.0 * spot_light.outer_angle,
+                    radius,
+                    translation,
+                    rotation * arc_rotation,
+                    color,
+                )
+                .resolution(16);
+        }
     }
 }
 
@@ -183,6 +229,11 @@ pub struct LightGizmoConfigGroup {
     ///
     /// Defaults to [`BLUE`].
     pub directional_light_color: Color,
+    /// Also draw where the range attenuation of [`PointLight`]s and [`SpotLight`]s drops to
+    /// 75%, 50% and 25% if true.
+    ///
+    /// Defaults to `false`.
+    pub draw_falloff: bool,
 }
 
 impl Default for LightGizmoConfigGroup {
@@ -193,6 +244,7 @@ impl Default for LightGizmoConfigGroup {
             point_light_color: RED.into(),
             spot_light_color: GREEN.into(),
             directional_light_color: BLUE.into(),
+            draw_falloff: false,
         }
     }
 }
@@ -7,10 +7,7 @@ struct LineGizmoUniform {
     line_width: f32,
     depth_bias: f32,
     resolution: u32,
-#ifdef SIXTEEN_BYTE_ALIGNMENT
-    // WebGL2 structs must be 16 byte aligned.
-    _padding: f32,
-#endif
+    occluded_opacity: f32,
 }
 
 @group(1) @binding(0) var<uniform> joints_gizmo: LineGizmoUniform;
@@ -20,6 +17,8 @@ struct VertexInput {
     @location(1) position_b: vec3<f32>,
     @location(2) position_c: vec3<f32>,
     @location(3) color: vec4<f32>,
+    @location(4) line_width: f32,
+    @location(5) flags: u32,
     @builtin(vertex_index) index: u32,
 };
 
@@ -30,6 +29,10 @@ struct VertexOutput {
 
 const EPSILON: f32 = 4.88e-04;
 
+// Must match the flags in `gizmos.rs`
+const SCREEN_SPACE_FLAG: u32 = 1u;
+const X_RAY_FLAG: u32 = 2u;
+
 @vertex
 fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
     var positions = array<vec2<f32>, 3>(
@@ -39,9 +42,11 @@ fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
     );
     var position = positions[vertex.index];
 
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
-    var clip_c = view.view_proj * vec4(vertex.position_c, 1.);
+    let screen_space = (vertex.flags & SCREEN_SPACE_FLAG) != 0u;
+
+    var clip_a = project(vertex.position_a, screen_space);
+    var clip_b = project(vertex.position_b, screen_space);
+    var clip_c = project(vertex.position_c, screen_space);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_c);
@@ -55,10 +60,12 @@ fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
     let screen_c = resolution * (0.5 * clip_c.xy / clip_c.w + 0.5);
 
     var color = vertex.color;
-    var line_width = joints_gizmo.line_width;
+    var line_width = select(joints_gizmo.line_width, vertex.line_width, vertex.line_width > 0.);
 
 #ifdef PERSPECTIVE
-    line_width /= clip_b.w;
+    if !screen_space {
+        line_width /= clip_b.w;
+    }
 #endif
 
     // Line thinness fade from https://acegikmo.com/shapes/docs/#anti-aliasing
@@ -80,7 +87,7 @@ fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
 
     let screen = screen_b + position.x * p0 + position.y * p1;
 
-    let depth = depth(clip_b);
+    let depth = depth(clip_b, vertex.flags);
 
     var clip_position = vec4(clip_b.w * ((2. * screen) / resolution - 1.), depth, clip_b.w);
     return VertexOutput(clip_position, color);
@@ -98,9 +105,11 @@ fn vertex_miter(vertex: VertexInput) -> VertexOutput {
     );
     var position = positions[vertex.index];
  
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
-    var clip_c = view.view_proj * vec4(vertex.position_c, 1.);
+    let screen_space = (vertex.flags & SCREEN_SPACE_FLAG) != 0u;
+
+    var clip_a = project(vertex.position_a, screen_space);
+    var clip_b = project(vertex.position_b, screen_space);
+    var clip_c = project(vertex.position_c, screen_space);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_c);
@@ -114,10 +123,12 @@ fn vertex_miter(vertex: VertexInput) -> VertexOutput {
     let screen_c = resolution * (0.5 * clip_c.xy / clip_c.w + 0.5);
 
     var color = vertex.color;
-    var line_width = joints_gizmo.line_width;
+    var line_width = select(joints_gizmo.line_width, vertex.line_width, vertex.line_width > 0.);
 
 #ifdef PERSPECTIVE
-    line_width /= clip_b.w;
+    if !screen_space {
+        line_width /= clip_b.w;
+    }
 #endif
 
     // Line thinness fade from https://acegikmo.com/shapes/docs/#anti-aliasing
@@ -140,7 +151,7 @@ fn vertex_miter(vertex: VertexInput) -> VertexOutput {
     
     var screen = screen_b + position.x * p0 + position.y * p1 + position.z * p2;
 
-    var depth = depth(clip_b);
+    var depth = depth(clip_b, vertex.flags);
 
     var clip_position = vec4(clip_b.w * ((2. * screen) / resolution - 1.), depth, clip_b.w);
     return VertexOutput(clip_position, color);
@@ -148,9 +159,11 @@ fn vertex_miter(vertex: VertexInput) -> VertexOutput {
 
 @vertex
 fn vertex_round(vertex: VertexInput) -> VertexOutput {
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
-    var clip_c = view.view_proj * vec4(vertex.position_c, 1.);
+    let screen_space = (vertex.flags & SCREEN_SPACE_FLAG) != 0u;
+
+    var clip_a = project(vertex.position_a, screen_space);
+    var clip_b = project(vertex.position_b, screen_space);
+    var clip_c = project(vertex.position_c, screen_space);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_c);
@@ -164,10 +177,12 @@ fn vertex_round(vertex: VertexInput) -> VertexOutput {
     let screen_c = resolution * (0.5 * clip_c.xy / clip_c.w + 0.5);
 
     var color = vertex.color;
-    var line_width = joints_gizmo.line_width;
+    var line_width = select(joints_gizmo.line_width, vertex.line_width, vertex.line_width > 0.);
 
 #ifdef PERSPECTIVE
-    line_width /= clip_b.w;
+    if !screen_space {
+        line_width /= clip_b.w;
+    }
 #endif
 
     // Line thinness fade from https://acegikmo.com/shapes/docs/#anti-aliasing
@@ -196,12 +211,21 @@ fn vertex_round(vertex: VertexInput) -> VertexOutput {
 
     var screen = screen_b + position_x * ab_norm + position_y * ab;
 
-    var depth = depth(clip_b);
+    var depth = depth(clip_b, vertex.flags);
 
     var clip_position = vec4(clip_b.w * ((2. * screen) / resolution - 1.), depth, clip_b.w);
     return VertexOutput(clip_position, color);
 }
 
+// Screen space positions are given in pixels, with the origin at the top left of the viewport.
+fn project(position: vec3<f32>, screen_space: bool) -> vec4<f32> {
+    if screen_space {
+        let ndc = 2. * position.xy / view.viewport.zw - 1.;
+        return vec4(ndc.x, -ndc.y, 1., 1.);
+    }
+    return view.view_proj * vec4(position, 1.);
+}
+
 fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
     // Move a if a is behind the near plane and b is in front. 
     if a.z > a.w && b.z <= b.w {
@@ -216,10 +240,16 @@ fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
     return a;
 }
 
-fn depth(clip: vec4<f32>) -> f32 {
+fn depth(clip: vec4<f32>, flags: u32) -> f32 {
+    // X-ray gizmos are drawn as if their depth bias was -1, in front of everything else
+    let depth_bias = select(joints_gizmo.depth_bias, -1., (flags & X_RAY_FLAG) != 0u);
+
     var depth: f32;
-    if joints_gizmo.depth_bias >= 0. {
-        depth = clip.z * (1. - joints_gizmo.depth_bias);
+    if (flags & SCREEN_SPACE_FLAG) != 0u {
+        // Screen space gizmos are drawn on the near plane, in front of everything else
+        depth = clip.w;
+    } else if depth_bias >= 0. {
+        depth = clip.z * (1. - depth_bias);
     } else {
         // depth * (clip.w / depth)^-depth_bias. So that when -depth_bias is 1.0, this is equal to clip.w
         // and when equal to 0.0, it is exactly equal to depth.
@@ -228,7 +258,7 @@ fn depth(clip: vec4<f32>) -> f32 {
         // of this value means nothing can be in front of this
         // The reason this uses an exponential function is that it makes it much easier for the
         // user to chose a value that is convenient for them
-        depth = clip.z * exp2(-joints_gizmo.depth_bias * log2(clip.w / clip.z - EPSILON));
+        depth = clip.z * exp2(-depth_bias * log2(clip.w / clip.z - EPSILON));
     }
     return depth;
 }
@@ -244,5 +274,9 @@ struct FragmentOutput {
 @fragment
 fn fragment(in: FragmentInput) -> FragmentOutput {
     // return FragmentOutput(vec4(1, 1, 1, 1));
+#ifdef OCCLUDED
+    return FragmentOutput(vec4(in.color.rgb, in.color.a * joints_gizmo.occluded_opacity));
+#else
     return FragmentOutput(in.color);
+#endif
 }
\ No newline at end of file
@@ -7,10 +7,8 @@
 struct LineGizmoUniform {
     line_width: f32,
     depth_bias: f32,
-#ifdef SIXTEEN_BYTE_ALIGNMENT
-    // WebGL2 structs must be 16 byte aligned.
-    _padding: vec2<f32>,
-#endif
+    joints_resolution: u32,
+    occluded_opacity: f32,
 }
 
 @group(1) @binding(0) var<uniform> line_gizmo: LineGizmoUniform;
@@ -20,6 +18,8 @@ struct VertexInput {
     @location(1) position_b: vec3<f32>,
     @location(2) color_a: vec4<f32>,
     @location(3) color_b: vec4<f32>,
+    @location(4) line_width: f32,
+    @location(5) flags: u32,
     @builtin(vertex_index) index: u32,
 };
 
@@ -31,6 +31,11 @@ struct VertexOutput {
 
 const EPSILON: f32 = 4.88e-04;
 
+// Must match the flags in `gizmos.rs`
+const SCREEN_SPACE_FLAG: u32 = 1u;
+const X_RAY_FLAG: u32 = 2u;
+const POINT_FLAG: u32 = 4u;
+
 @vertex
 fn vertex(vertex: VertexInput) -> VertexOutput {
     var positions = array<vec2<f32>, 6>(
@@ -43,9 +48,11 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     );
     let position = positions[vertex.index];
 
+    let screen_space = (vertex.flags & SCREEN_SPACE_FLAG) != 0u;
+
     // algorithm based on https://wwwtyro.net/2019/11/18/instanced-lines.html
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
+    var clip_a = project(vertex.position_a, screen_space);
+    var clip_b = project(vertex.position_b, screen_space);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_b);
@@ -56,12 +63,16 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     let screen_a = resolution * (0.5 * clip_a.xy / clip_a.w + 0.5);
     let screen_b = resolution * (0.5 * clip_b.xy / clip_b.w + 0.5);
 
-    let y_basis = normalize(screen_b - screen_a);
+    // Points are zero-length segments, drawn as screen aligned squares
+    let point = (vertex.flags & POINT_FLAG) != 0u;
+    let y_basis = select(normalize(screen_b - screen_a), vec2(0., 1.), point);
     let x_basis = vec2(-y_basis.y, y_basis.x);
 
     var color = mix(vertex.color_a, vertex.color_b, position.y);
 
-    var line_width = line_gizmo.line_width;
+    // A per-vertex line width of zero means the line width of the config is used
+    let base_line_width = select(line_gizmo.line_width, vertex.line_width, vertex.line_width > 0.);
+    var line_width = base_line_width;
     var alpha = 1.;
 
     var uv: f32;
@@ -81,7 +92,7 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     // Offset to compensate for moved clip positions. If removed dots on lines will slide when position a is ofscreen.
     let clipped_offset = length(position_a.xyz - vertex.position_a);
 
-    uv = (clipped_offset + position.y * world_distance) * resolution.y / near_clipping_plane_height / line_gizmo.line_width;
+    uv = (clipped_offset + position.y * world_distance) * resolution.y / near_clipping_plane_height / base_line_width;
 #else
     // Get the distance of b to the camera along camera axes
     let camera_b = view.inverse_projection * clip_b;
@@ -95,9 +106,15 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     else {
         depth_adaptment = -camera_b.z;
     }
-    uv = position.y * depth_adaptment * length(screen_b - screen_a) / line_gizmo.line_width;
+    uv = position.y * depth_adaptment * length(screen_b - screen_a) / base_line_width;
 #endif
 
+    if screen_space || point {
+        // Screen space gizmos and points are not affected by the projection, points are sized in pixels
+        line_width = base_line_width;
+        uv = position.y * length(screen_b - screen_a) / base_line_width;
+    }
+
     // Line thinness fade from https://acegikmo.com/shapes/docs/#anti-aliasing
     if line_width > 0.0 && line_width < 1. {
         color.a *= line_width;
@@ -105,11 +122,20 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     }
 
     let x_offset = line_width * position.x * x_basis;
-    let screen = mix(screen_a, screen_b, position.y) + x_offset;
+    var screen = mix(screen_a, screen_b, position.y) + x_offset;
+    if point {
+        screen += line_width * (position.y - 0.5) * y_basis;
+    }
+
+    // X-ray gizmos are drawn as if their depth bias was -1, in front of everything else
+    let depth_bias = select(line_gizmo.depth_bias, -1., (vertex.flags & X_RAY_FLAG) != 0u);
 
     var depth: f32;
-    if line_gizmo.depth_bias >= 0. {
-        depth = clip.z * (1. - line_gizmo.depth_bias);
+    if screen_space {
+        // Screen space gizmos are drawn on the near plane, in front of everything else
+        depth = clip.w;
+    } else if depth_bias >= 0. {
+        depth = clip.z * (1. - depth_bias);
     } else {
         // depth * (clip.w / depth)^-depth_bias. So that when -depth_bias is 1.0, this is equal to clip.w
         // and when equal to 0.0, it is exactly equal to depth.
@@ -118,7 +144,7 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
         // of this value means nothing can be in front of this
         // The reason this uses an exponential function is that it makes it much easier for the
         // user to chose a value that is convenient for them
-        depth = clip.z * exp2(-line_gizmo.depth_bias * log2(clip.w / clip.z - EPSILON));
+        depth = clip.z * exp2(-depth_bias * log2(clip.w / clip.z - EPSILON));
     }
 
     var clip_position = vec4(clip.w * ((2. * screen) / resolution - 1.), depth, clip.w);
@@ -126,6 +152,15 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     return VertexOutput(clip_position, color, uv);
 }
 
+// Screen space positions are given in pixels, with the origin at the top left of the viewport.
+fn project(position: vec3<f32>, screen_space: bool) -> vec4<f32> {
+    if screen_space {
+        let ndc = 2. * position.xy / view.viewport.zw - 1.;
+        return vec4(ndc.x, -ndc.y, 1., 1.);
+    }
+    return view.view_proj * vec4(position, 1.);
+}
+
 fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
     // Move a if a is behind the near plane and b is in front. 
     if a.z > a.w && b.z <= b.w {
@@ -152,7 +187,7 @@ struct FragmentOutput {
 
 @fragment
 fn fragment_solid(in: FragmentInput) -> FragmentOutput {
-    return FragmentOutput(in.color);
+    return FragmentOutput(occlude(in.color));
 }
 @fragment
 fn fragment_dotted(in: FragmentInput) -> FragmentOutput {
@@ -163,5 +198,14 @@ fn fragment_dotted(in: FragmentInput) -> FragmentOutput {
     alpha = 1 - floor((in.uv * in.position.w) % 2.0);
 #endif
     
-    return FragmentOutput(vec4(in.color.xyz, in.color.w * alpha));
+    return FragmentOutput(occlude(vec4(in.color.xyz, in.color.w * alpha)));
+}
+
+// Dims the occluded parts of lines, which are drawn in a separate pass.
+fn occlude(color: vec4<f32>) -> vec4<f32> {
+#ifdef OCCLUDED
+    return vec4(color.rgb, color.a * line_gizmo.occluded_opacity);
+#else
+    return color;
+#endif
 }
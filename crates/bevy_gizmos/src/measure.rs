@@ -0,0 +1,311 @@
+//! Additional [`Gizmos`] Functions -- Measurements
+//!
+//! Includes the implementation of [`Gizmos::measure`], [`Gizmos::measure_2d`],
+//! [`Gizmos::protractor`] and [`Gizmos::protractor_2d`], and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::{Dir2, Dir3, Quat, Vec2, Vec3};
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw the distance between two points in 3D, as a line with tick marks at its ends and a
+    /// label showing its length.
+    ///
+    /// This should be called for each frame the measurement needs to be rendered.
+    ///
+    /// The label is laid out in the XY plane by default, see [`Gizmos::text`].
+    ///
+    /// # Builder methods
+    ///
+    /// - The cap height of the label can be adjusted using `.label_size(...)`. It defaults to a
+    /// tenth of the measured distance.
+    /// - The orientation of the label can be adjusted using `.label_rotation(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::WHITE;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.measure(Vec3::ZERO, Vec3::new(2., 1., 0.), WHITE);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn measure(
+        &mut self,
+        start: Vec3,
+        end: Vec3,
+        color: impl Into<Color>,
+    ) -> MeasureBuilder<'_, 'w, 's, Config, Clear> {
+        MeasureBuilder {
+            gizmos: self,
+            start,
+            end,
+            normal: (end - start).normalize_or_zero().any_orthonormal_vector(),
+            color: color.into(),
+            label_size: None,
+            label_rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// Draw the distance between two points in 2D, as a line with tick marks at its ends and a
+    /// label showing its length.
+    ///
+    /// This should be called for each frame the measurement needs to be rendered.
+    ///
+    /// # Builder methods
+    ///
+    /// - The cap height of the label can be adjusted using `.label_size(...)`. It defaults to a
+    /// tenth of the measured distance.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::WHITE;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .measure_2d(Vec2::ZERO, Vec2::new(200., 100.), WHITE)
+    ///         .label_size(16.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn measure_2d(
+        &mut self,
+        start: Vec2,
+        end: Vec2,
+        color: impl Into<Color>,
+    ) -> MeasureBuilder<'_, 'w, 's, Config, Clear> {
+        MeasureBuilder {
+            gizmos: self,
+            start: start.extend(0.),
+            end: end.extend(0.),
+            normal: (end - start).normalize_or_zero().perp().extend(0.),
+            color: color.into(),
+            label_size: None,
+            label_rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// Draw the angle between two directions in 3D, as two rays from `origin` joined by an arc
+    /// and a label showing the angle in degrees.
+    ///
+    /// This should be called for each frame the protractor needs to be rendered.
+    ///
+    /// The label is laid out in the XY plane by default, see [`Gizmos::text`].
+    ///
+    /// # Builder methods
+    ///
+    /// - The length of the rays can be adjusted using `.length(...)`. It defaults to `1.0`.
+    /// - The cap height of the label can be adjusted using `.label_size(...)`. It defaults to
+    /// 15% of the length of the rays.
+    /// - The orientation of the label can be adjusted using `.label_rotation(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::YELLOW;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .protractor(Vec3::ZERO, Dir3::X, Dir3::new(Vec3::ONE).unwrap(), YELLOW)
+    ///         .length(2.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    ///
+    /// # Notes
+    /// - If `dir_a` and `dir_b` point in opposite directions, the plane of the arc is ambiguous.
+    pub fn protractor(
+        &mut self,
+        origin: Vec3,
+        dir_a: Dir3,
+        dir_b: Dir3,
+        color: impl Into<Color>,
+    ) -> ProtractorBuilder<'_, 'w, 's, Config, Clear> {
+        ProtractorBuilder {
+            gizmos: self,
+            origin,
+            dir_a,
+            dir_b,
+            color: color.into(),
+            length: 1.,
+            label_size: None,
+            label_rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// Draw the angle between two directions in 2D, as two rays from `origin` joined by an arc
+    /// and a label showing the angle in degrees.
+    ///
+    /// This should be called for each frame the protractor needs to be rendered.
+    ///
+    /// # Builder methods
+    ///
+    /// - The length of the rays can be adjusted using `.length(...)`. It defaults to `1.0`.
+    /// - The cap height of the label can be adjusted using `.label_size(...)`. It defaults to
+    /// 15% of the length of the rays.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::YELLOW;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .protractor_2d(Vec2::ZERO, Dir2::X, Dir2::Y, YELLOW)
+    ///         .length(100.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn protractor_2d(
+        &mut self,
+        origin: Vec2,
+        dir_a: Dir2,
+        dir_b: Dir2,
+        color: impl Into<Color>,
+    ) -> ProtractorBuilder<'_, 'w, 's, Config, Clear> {
+        self.protractor(
+            origin.extend(0.),
+            Dir3::new_unchecked(dir_a.extend(0.)),
+            Dir3::new_unchecked(dir_b.extend(0.)),
+            color,
+        )
+    }
+}
+
+/// A builder returned by [`Gizmos::measure`] and [`Gizmos::measure_2d`].
+pub struct MeasureBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    start: Vec3,
+    end: Vec3,
+    normal: Vec3,
+    color: Color,
+    label_size: Option<f32>,
+    label_rotation: Quat,
+}
+
+impl<Config, Clear> MeasureBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the cap height of the distance label.
+    pub fn label_size(mut self, size: f32) -> Self {
+        self.label_size = Some(size);
+        self
+    }
+
+    /// Set the orientation of the distance label.
+    pub fn label_rotation(mut self, rotation: Quat) -> Self {
+        self.label_rotation = rotation;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for MeasureBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let (start, end, color) = (self.start, self.end, self.color);
+        let distance = start.distance(end);
+        let tick = 0.05 * distance * self.normal;
+
+        self.gizmos.line(start, end, color);
+        self.gizmos.line(start - tick, start + tick, color);
+        self.gizmos.line(end - tick, end + tick, color);
+
+        let label = format!("{distance:.2}");
+        let label_size = self.label_size.unwrap_or(0.1 * distance);
+        self.gizmos
+            .text((start + end) / 2. + 2. * tick, &label, color)
+            .size(label_size)
+            .rotation(self.label_rotation);
+    }
+}
+
+/// A builder returned by [`Gizmos::protractor`] and [`Gizmos::protractor_2d`].
+pub struct ProtractorBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    origin: Vec3,
+    dir_a: Dir3,
+    dir_b: Dir3,
+    color: Color,
+    length: f32,
+    label_size: Option<f32>,
+    label_rotation: Quat,
+}
+
+impl<Config, Clear> ProtractorBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the length of the rays.
+    pub fn length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Set the cap height of the angle label.
+    pub fn label_size(mut self, size: f32) -> Self {
+        self.label_size = Some(size);
+        self
+    }
+
+    /// Set the orientation of the angle label.
+    pub fn label_rotation(mut self, rotation: Quat) -> Self {
+        self.label_rotation = rotation;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for ProtractorBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let (origin, dir_a, dir_b, color, length) =
+            (self.origin, self.dir_a, self.dir_b, self.color, self.length);
+
+        self.gizmos.line(origin, origin + dir_a * length, color);
+        self.gizmos.line(origin, origin + dir_b * length, color);
+        self.gizmos
+            .arc_3d_between(origin, dir_a, dir_b, 0.4 * length, color);
+
+        // Place the label between the rays, just outside of the arc
+        let bisector = (*dir_a + *dir_b)
+            .try_normalize()
+            .unwrap_or_else(|| dir_a.any_orthonormal_vector());
+        let angle = dir_a.angle_between(*dir_b).to_degrees();
+        let label = format!("{angle:.1}°");
+        let label_size = self.label_size.unwrap_or(0.15 * length);
+        self.gizmos
+            .text(origin + 0.6 * length * bisector, &label, color)
+            .size(label_size)
+            .rotation(self.label_rotation);
+    }
+}
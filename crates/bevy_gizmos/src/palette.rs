@@ -0,0 +1,285 @@
+//! Color palettes that can be loaded as assets, see [`Palette`].
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, LoadContext};
+use bevy_color::{Color, HexColorError, Srgba};
+use bevy_reflect::TypePath;
+use thiserror::Error;
+
+/// A list of colors, optionally named, that can be shared between projects as an asset.
+///
+/// Palettes can be loaded from GIMP palettes (`.gpl`) and from files with a hexadecimal color on
+/// each line (`.hex`), which are the formats most palette editors and sites export.
+///
+/// Palettes are handy for picking the colors of gizmos by name, or for giving each of a number of
+/// things its own color with [`cycle`](Self::cycle).
+///
+/// ```
+/// # use bevy_asset::{Assets, Handle};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gizmos::{gizmos::Gizmos, palette::Palette};
+/// # use bevy_transform::components::GlobalTransform;
+/// #[derive(Resource)]
+/// struct TeamPalette(Handle<Palette>);
+///
+/// fn draw_units(
+///     mut gizmos: Gizmos,
+///     palette: Res<TeamPalette>,
+///     palettes: Res<Assets<Palette>>,
+///     units: Query<&GlobalTransform>,
+/// ) {
+///     let Some(palette) = palettes.get(&palette.0) else {
+///         return;
+///     };
+///     for (index, transform) in units.iter().enumerate() {
+///         gizmos.circle(transform.translation(), transform.up(), 1.0, palette.cycle(index));
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(draw_units);
+/// ```
+#[derive(Asset, TypePath, Debug, Clone, Default, PartialEq)]
+pub struct Palette {
+    /// The name of the palette, if the file has one.
+    pub name: Option<String>,
+    /// The colors of the palette, in order.
+    pub colors: Vec<PaletteColor>,
+}
+
+/// A color in a [`Palette`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteColor {
+    /// The name of the color, if the file has one.
+    pub name: Option<String>,
+    /// The color.
+    pub color: Color,
+}
+
+impl Palette {
+    /// Parses a GIMP palette, which starts with a `GIMP Palette` line and has a color on each
+    /// line after that, as its red, green and blue components from 0 to 255 followed by its name.
+    ///
+    /// ```
+    /// # use bevy_color::Color;
+    /// # use bevy_gizmos::palette::Palette;
+    /// let palette = Palette::from_gpl(
+    ///     "GIMP Palette
+    ///     Name: Traffic lights
+    ///     ## Comments and the number of columns are ignored
+    ///     Columns: 3
+    ///     255   0   0 Stop
+    ///     255 191   0 Wait
+    ///       0 255   0 Go",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(palette.name.as_deref(), Some("Traffic lights"));
+    /// assert_eq!(palette.get("Go"), Some(Color::srgb_u8(0, 255, 0)));
+    /// ```
+    pub fn from_gpl(source: &str) -> Result<Self, PaletteError> {
+        let mut lines = source
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        if lines.next().map(|(_, line)| line) != Some("GIMP Palette") {
+            return Err(PaletteError::MissingGplHeader);
+        }
+
+        let mut palette = Palette::default();
+        for (line_number, line) in lines {
+            if line.starts_with('#') || line.starts_with("Columns:") {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix("Name:") {
+                palette.name = Some(name.trim().to_string());
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let mut component = || {
+                parts
+                    .next()
+                    .and_then(|part| part.parse::<u8>().ok())
+                    .ok_or(PaletteError::InvalidColor { line: line_number })
+            };
+            let color = Color::srgb_u8(component()?, component()?, component()?);
+            let name = parts.collect::<Vec<_>>().join(" ");
+            palette.colors.push(PaletteColor {
+                name: (!name.is_empty()).then_some(name),
+                color,
+            });
+        }
+        Ok(palette)
+    }
+
+    /// Parses a list of colors in hexadecimal notation, one on each line, see [`Srgba::hex`].
+    ///
+    /// ```
+    /// # use bevy_color::Color;
+    /// # use bevy_gizmos::palette::Palette;
+    /// let palette = Palette::from_hex("ff0000\n#00ff00\n0000ff").unwrap();
+    /// assert_eq!(palette.cycle(4), Color::srgb_u8(0, 255, 0));
+    /// ```
+    pub fn from_hex(source: &str) -> Result<Self, PaletteError> {
+        let colors = source
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line, hex)| {
+                Srgba::hex(hex)
+                    .map(|color| PaletteColor {
+                        name: None,
+                        color: color.into(),
+                    })
+                    .map_err(|source| PaletteError::InvalidHex { line, source })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Palette { name: None, colors })
+    }
+
+    /// Returns the first color called `name`.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors
+            .iter()
+            .find(|color| color.name.as_deref() == Some(name))
+            .map(|color| color.color)
+    }
+
+    /// Returns the color at `index`, starting over from the first color past the end of the
+    /// palette, or white if the palette is empty.
+    pub fn cycle(&self, index: usize) -> Color {
+        if self.colors.is_empty() {
+            return Color::WHITE;
+        }
+        self.colors[index % self.colors.len()].color
+    }
+
+    /// Returns the colors of the palette, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Color> + '_ {
+        self.colors.iter().map(|color| color.color)
+    }
+}
+
+/// An error when parsing or loading a [`Palette`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum PaletteError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The palette isn't valid UTF-8.
+    #[error(transparent)]
+    Utf8(#[from] std::string::FromUtf8Error),
+    /// A GIMP palette doesn't start with `GIMP Palette`.
+    #[error("a GIMP palette must start with `GIMP Palette`")]
+    MissingGplHeader,
+    /// A line of a GIMP palette doesn't start with three components from 0 to 255.
+    #[error("invalid color on line {line}, expected three components from 0 to 255")]
+    InvalidColor {
+        /// The line of the color, starting from 1.
+        line: usize,
+    },
+    /// A line of a hexadecimal palette isn't a valid color.
+    #[error("invalid color on line {line}: {source}")]
+    InvalidHex {
+        /// The line of the color, starting from 1.
+        line: usize,
+        /// Why the color is invalid.
+        source: HexColorError,
+    },
+}
+
+/// Loads [`Palette`]s from `.gpl` and `.hex` files.
+#[derive(Default)]
+pub struct PaletteLoader;
+
+impl AssetLoader for PaletteLoader {
+    type Asset = Palette;
+    type Settings = ();
+    type Error = PaletteError;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Palette, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = String::from_utf8(bytes)?;
+        match load_context.path().extension().and_then(|ext| ext.to_str()) {
+            Some("hex") => Palette::from_hex(&source),
+            _ => Palette::from_gpl(&source),
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["gpl", "hex"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gpl_needs_header() {
+        assert!(matches!(
+            Palette::from_gpl("255 0 0 Red"),
+            Err(PaletteError::MissingGplHeader)
+        ));
+        assert!(matches!(
+            Palette::from_gpl(""),
+            Err(PaletteError::MissingGplHeader)
+        ));
+    }
+
+    #[test]
+    fn gpl_reports_line_of_invalid_color() {
+        let source = "GIMP Palette\n255 0 0 Red\n\n0 256 0 Green";
+        assert!(matches!(
+            Palette::from_gpl(source),
+            Err(PaletteError::InvalidColor { line: 4 })
+        ));
+        // Too few components, or components that aren't numbers
+        assert!(matches!(
+            Palette::from_gpl("GIMP Palette\n255 0"),
+            Err(PaletteError::InvalidColor { line: 2 })
+        ));
+        assert!(matches!(
+            Palette::from_gpl("GIMP Palette\nred green blue"),
+            Err(PaletteError::InvalidColor { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn gpl_colors_without_names() {
+        let palette = Palette::from_gpl("GIMP Palette\n0 0 255\n255 255 255 Light  gray").unwrap();
+        assert_eq!(palette.name, None);
+        assert_eq!(palette.colors[0].name, None);
+        assert_eq!(palette.colors[1].name.as_deref(), Some("Light gray"));
+    }
+
+    #[test]
+    fn hex_reports_line_of_invalid_color() {
+        assert!(matches!(
+            Palette::from_hex("ff0000\n\n00ff0\n"),
+            Err(PaletteError::InvalidHex {
+                line: 3,
+                source: HexColorError::Length
+            })
+        ));
+        assert!(matches!(
+            Palette::from_hex("ff0000\n#00gg00"),
+            Err(PaletteError::InvalidHex {
+                line: 2,
+                source: HexColorError::Parse(_)
+            })
+        ));
+    }
+
+    #[test]
+    fn empty_hex_palette() {
+        let palette = Palette::from_hex("\n  \n").unwrap();
+        assert!(palette.colors.is_empty());
+        assert_eq!(palette.cycle(3), Color::WHITE);
+    }
+}
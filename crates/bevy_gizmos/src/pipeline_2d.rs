@@ -1,9 +1,10 @@
 use crate::{
     config::{GizmoLineJoint, GizmoLineStyle, GizmoMeshConfig},
-    line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo,
-    DrawLineJointGizmo, GizmoRenderSystem, GpuLineGizmo, LineGizmo,
+    line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts,
+    prepare_line_gizmo_buffers, triangle_gizmo_vertex_buffer_layouts, DrawLineGizmo,
+    DrawLineJointGizmo, DrawTriangleGizmo, GizmoRenderSystem, LineGizmo, LineGizmoBuffers,
     LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_JOINT_SHADER_HANDLE,
-    LINE_SHADER_HANDLE,
+    LINE_SHADER_HANDLE, TRIANGLE_SHADER_HANDLE,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
@@ -17,7 +18,6 @@ use bevy_ecs::{
 };
 use bevy_math::FloatOrd;
 use bevy_render::{
-    render_asset::{prepare_assets, RenderAssets},
     render_phase::{
         AddRenderCommand, DrawFunctions, PhaseItemExtraIndex, SetItemPipeline,
         ViewSortedRenderPhases,
@@ -41,8 +41,10 @@ impl Plugin for LineGizmo2dPlugin {
         render_app
             .add_render_command::<Transparent2d, DrawLineGizmo2d>()
             .add_render_command::<Transparent2d, DrawLineJointGizmo2d>()
+            .add_render_command::<Transparent2d, DrawTriangleGizmo2d>()
             .init_resource::<SpecializedRenderPipelines<LineGizmoPipeline>>()
             .init_resource::<SpecializedRenderPipelines<LineJointGizmoPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<TriangleGizmoPipeline>>()
             .configure_sets(
                 Render,
                 GizmoRenderSystem::QueueLineGizmos2d
@@ -54,9 +56,13 @@ impl Plugin for LineGizmo2dPlugin {
             )
             .add_systems(
                 Render,
-                (queue_line_gizmos_2d, queue_line_joint_gizmos_2d)
+                (
+                    queue_line_gizmos_2d,
+                    queue_line_joint_gizmos_2d,
+                    queue_triangle_gizmos_2d,
+                )
                     .in_set(GizmoRenderSystem::QueueLineGizmos2d)
-                    .after(prepare_assets::<GpuLineGizmo>),
+                    .after(prepare_line_gizmo_buffers),
             );
     }
 
@@ -67,6 +73,7 @@ impl Plugin for LineGizmo2dPlugin {
 
         render_app.init_resource::<LineGizmoPipeline>();
         render_app.init_resource::<LineJointGizmoPipeline>();
+        render_app.init_resource::<TriangleGizmoPipeline>();
     }
 }
 
@@ -236,6 +243,80 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
     }
 }
 
+#[derive(Clone, Resource)]
+struct TriangleGizmoPipeline {
+    mesh_pipeline: Mesh2dPipeline,
+    uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for TriangleGizmoPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        TriangleGizmoPipeline {
+            mesh_pipeline: render_world.resource::<Mesh2dPipeline>().clone(),
+            uniform_layout: render_world
+                .resource::<LineGizmoUniformBindgroupLayout>()
+                .layout
+                .clone(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct TriangleGizmoPipelineKey {
+    mesh_key: Mesh2dPipelineKey,
+}
+
+impl SpecializedRenderPipeline for TriangleGizmoPipeline {
+    type Key = TriangleGizmoPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.mesh_key.contains(Mesh2dPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let shader_defs = vec![
+            #[cfg(feature = "webgl")]
+            "SIXTEEN_BYTE_ALIGNMENT".into(),
+        ];
+
+        let layout = vec![
+            self.mesh_pipeline.view_layout.clone(),
+            self.uniform_layout.clone(),
+        ];
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TRIANGLE_SHADER_HANDLE,
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: triangle_gizmo_vertex_buffer_layouts(),
+            },
+            fragment: Some(FragmentState {
+                shader: TRIANGLE_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout,
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.mesh_key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("TriangleGizmo Pipeline 2D".into()),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
 type DrawLineGizmo2d = (
     SetItemPipeline,
     SetMesh2dViewBindGroup<0>,
@@ -248,6 +329,12 @@ type DrawLineJointGizmo2d = (
     SetLineGizmoBindGroup<1>,
     DrawLineJointGizmo,
 );
+type DrawTriangleGizmo2d = (
+    SetItemPipeline,
+    SetMesh2dViewBindGroup<0>,
+    SetLineGizmoBindGroup<1>,
+    DrawTriangleGizmo,
+);
 
 #[allow(clippy::too_many_arguments)]
 fn queue_line_gizmos_2d(
@@ -257,7 +344,7 @@ fn queue_line_gizmos_2d(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
-    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    line_gizmo_assets: Res<LineGizmoBuffers>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(Entity, &ExtractedView, Option<&RenderLayers>)>,
 ) {
@@ -281,6 +368,10 @@ fn queue_line_gizmos_2d(
                 continue;
             };
 
+            if line_gizmo.triangles {
+                continue;
+            }
+
             let pipeline = pipelines.specialize(
                 &pipeline_cache,
                 &pipeline,
@@ -311,7 +402,7 @@ fn queue_line_joint_gizmos_2d(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
-    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    line_gizmo_assets: Res<LineGizmoBuffers>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(Entity, &ExtractedView, Option<&RenderLayers>)>,
 ) {
@@ -361,3 +452,60 @@ fn queue_line_joint_gizmos_2d(
         }
     }
 }
+
+#[allow(clippy::too_many_arguments)]
+fn queue_triangle_gizmos_2d(
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    pipeline: Res<TriangleGizmoPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TriangleGizmoPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
+    line_gizmo_assets: Res<LineGizmoBuffers>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut views: Query<(Entity, &ExtractedView, Option<&RenderLayers>)>,
+) {
+    let draw_function = draw_functions
+        .read()
+        .get_id::<DrawTriangleGizmo2d>()
+        .unwrap();
+
+    for (view_entity, view, render_layers) in &mut views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+            | Mesh2dPipelineKey::from_hdr(view.hdr);
+
+        let render_layers = render_layers.unwrap_or_default();
+        for (entity, handle, config) in &line_gizmos {
+            if !config.render_layers.intersects(render_layers) {
+                continue;
+            }
+
+            let Some(line_gizmo) = line_gizmo_assets.get(handle) else {
+                continue;
+            };
+
+            if !line_gizmo.triangles {
+                continue;
+            }
+
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                TriangleGizmoPipelineKey { mesh_key },
+            );
+            // Draw filled shapes just before the lines, so outlines stay visible
+            transparent_phase.add(Transparent2d {
+                entity,
+                draw_function,
+                pipeline,
+                sort_key: FloatOrd(f32::MAX),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
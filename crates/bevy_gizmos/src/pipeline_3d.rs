@@ -1,9 +1,10 @@
 use crate::{
     config::{GizmoLineJoint, GizmoLineStyle, GizmoMeshConfig},
-    line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo,
-    DrawLineJointGizmo, GizmoRenderSystem, GpuLineGizmo, LineGizmo,
+    line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts,
+    prepare_line_gizmo_buffers, triangle_gizmo_vertex_buffer_layouts, DrawLineGizmo,
+    DrawLineJointGizmo, DrawTriangleGizmo, GizmoRenderSystem, LineGizmo, LineGizmoBuffers,
     LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_JOINT_SHADER_HANDLE,
-    LINE_SHADER_HANDLE,
+    LINE_SHADER_HANDLE, TRIANGLE_SHADER_HANDLE,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
@@ -21,7 +22,6 @@ use bevy_ecs::{
 };
 use bevy_pbr::{MeshPipeline, MeshPipelineKey, SetMeshViewBindGroup};
 use bevy_render::{
-    render_asset::{prepare_assets, RenderAssets},
     render_phase::{
         AddRenderCommand, DrawFunctions, PhaseItemExtraIndex, SetItemPipeline,
         ViewSortedRenderPhases,
@@ -43,8 +43,10 @@ impl Plugin for LineGizmo3dPlugin {
         render_app
             .add_render_command::<Transparent3d, DrawLineGizmo3d>()
             .add_render_command::<Transparent3d, DrawLineJointGizmo3d>()
+            .add_render_command::<Transparent3d, DrawTriangleGizmo3d>()
             .init_resource::<SpecializedRenderPipelines<LineGizmoPipeline>>()
             .init_resource::<SpecializedRenderPipelines<LineJointGizmoPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<TriangleGizmoPipeline>>()
             .configure_sets(
                 Render,
                 GizmoRenderSystem::QueueLineGizmos3d
@@ -53,9 +55,13 @@ impl Plugin for LineGizmo3dPlugin {
             )
             .add_systems(
                 Render,
-                (queue_line_gizmos_3d, queue_line_joint_gizmos_3d)
+                (
+                    queue_line_gizmos_3d,
+                    queue_line_joint_gizmos_3d,
+                    queue_triangle_gizmos_3d,
+                )
                     .in_set(GizmoRenderSystem::QueueLineGizmos3d)
-                    .after(prepare_assets::<GpuLineGizmo>),
+                    .after(prepare_line_gizmo_buffers),
             );
     }
 
@@ -66,6 +72,7 @@ impl Plugin for LineGizmo3dPlugin {
 
         render_app.init_resource::<LineGizmoPipeline>();
         render_app.init_resource::<LineJointGizmoPipeline>();
+        render_app.init_resource::<TriangleGizmoPipeline>();
     }
 }
 
@@ -93,6 +100,7 @@ struct LineGizmoPipelineKey {
     strip: bool,
     perspective: bool,
     line_style: GizmoLineStyle,
+    occluded: bool,
 }
 
 impl SpecializedRenderPipeline for LineGizmoPipeline {
@@ -108,6 +116,10 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             shader_defs.push("PERSPECTIVE".into());
         }
 
+        if key.occluded {
+            shader_defs.push("OCCLUDED".into());
+        }
+
         let format = if key.view_key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -145,13 +157,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             }),
             layout,
             primitive: PrimitiveState::default(),
-            depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
-                stencil: StencilState::default(),
-                bias: DepthBiasState::default(),
-            }),
+            depth_stencil: Some(line_gizmo_depth_stencil_state(key.occluded)),
             multisample: MultisampleState {
                 count: key.view_key.msaa_samples(),
                 mask: !0,
@@ -186,6 +192,7 @@ struct LineJointGizmoPipelineKey {
     view_key: MeshPipelineKey,
     perspective: bool,
     joints: GizmoLineJoint,
+    occluded: bool,
 }
 
 impl SpecializedRenderPipeline for LineJointGizmoPipeline {
@@ -201,6 +208,10 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             shader_defs.push("PERSPECTIVE".into());
         }
 
+        if key.occluded {
+            shader_defs.push("OCCLUDED".into());
+        }
+
         let format = if key.view_key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -243,13 +254,7 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             }),
             layout,
             primitive: PrimitiveState::default(),
-            depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
-                stencil: StencilState::default(),
-                bias: DepthBiasState::default(),
-            }),
+            depth_stencil: Some(line_gizmo_depth_stencil_state(key.occluded)),
             multisample: MultisampleState {
                 count: key.view_key.msaa_samples(),
                 mask: !0,
@@ -261,6 +266,104 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
     }
 }
 
+#[derive(Clone, Resource)]
+struct TriangleGizmoPipeline {
+    mesh_pipeline: MeshPipeline,
+    uniform_layout: BindGroupLayout,
+}
+
+impl FromWorld for TriangleGizmoPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        TriangleGizmoPipeline {
+            mesh_pipeline: render_world.resource::<MeshPipeline>().clone(),
+            uniform_layout: render_world
+                .resource::<LineGizmoUniformBindgroupLayout>()
+                .layout
+                .clone(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct TriangleGizmoPipelineKey {
+    view_key: MeshPipelineKey,
+    occluded: bool,
+}
+
+impl SpecializedRenderPipeline for TriangleGizmoPipeline {
+    type Key = TriangleGizmoPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = vec![
+            #[cfg(feature = "webgl")]
+            "SIXTEEN_BYTE_ALIGNMENT".into(),
+        ];
+
+        if key.occluded {
+            shader_defs.push("OCCLUDED".into());
+        }
+
+        let format = if key.view_key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let view_layout = self
+            .mesh_pipeline
+            .get_view_layout(key.view_key.into())
+            .clone();
+
+        let layout = vec![view_layout, self.uniform_layout.clone()];
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TRIANGLE_SHADER_HANDLE,
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: triangle_gizmo_vertex_buffer_layouts(),
+            },
+            fragment: Some(FragmentState {
+                shader: TRIANGLE_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout,
+            primitive: PrimitiveState::default(),
+            depth_stencil: Some(line_gizmo_depth_stencil_state(key.occluded)),
+            multisample: MultisampleState {
+                count: key.view_key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("TriangleGizmo Pipeline".into()),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+/// The depth state of gizmo lines, or of their occluded parts if `occluded` is set.
+///
+/// Occluded parts are drawn where the depth test of the regular pass fails, without writing depth.
+fn line_gizmo_depth_stencil_state(occluded: bool) -> DepthStencilState {
+    DepthStencilState {
+        format: CORE_3D_DEPTH_FORMAT,
+        depth_write_enabled: !occluded,
+        depth_compare: if occluded {
+            CompareFunction::Less
+        } else {
+            CompareFunction::Greater
+        },
+        stencil: StencilState::default(),
+        bias: DepthBiasState::default(),
+    }
+}
+
 type DrawLineGizmo3d = (
     SetItemPipeline,
     SetMeshViewBindGroup<0>,
@@ -273,6 +376,12 @@ type DrawLineJointGizmo3d = (
     SetLineGizmoBindGroup<1>,
     DrawLineJointGizmo,
 );
+type DrawTriangleGizmo3d = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetLineGizmoBindGroup<1>,
+    DrawTriangleGizmo,
+);
 
 #[allow(clippy::too_many_arguments)]
 fn queue_line_gizmos_3d(
@@ -282,7 +391,7 @@ fn queue_line_gizmos_3d(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
-    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    line_gizmo_assets: Res<LineGizmoBuffers>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
     mut views: Query<(
         Entity,
@@ -339,25 +448,38 @@ fn queue_line_gizmos_3d(
                 continue;
             };
 
-            let pipeline = pipelines.specialize(
-                &pipeline_cache,
-                &pipeline,
-                LineGizmoPipelineKey {
-                    view_key,
-                    strip: line_gizmo.strip,
-                    perspective: config.line_perspective,
-                    line_style: config.line_style,
-                },
-            );
+            if line_gizmo.triangles {
+                continue;
+            }
+
+            let passes: &[bool] = if config.occluded {
+                &[false, true]
+            } else {
+                &[false]
+            };
 
-            transparent_phase.add(Transparent3d {
-                entity,
-                draw_function,
-                pipeline,
-                distance: 0.,
-                batch_range: 0..1,
-                extra_index: PhaseItemExtraIndex::NONE,
-            });
+            for &occluded in passes {
+                let pipeline = pipelines.specialize(
+                    &pipeline_cache,
+                    &pipeline,
+                    LineGizmoPipelineKey {
+                        view_key,
+                        strip: line_gizmo.strip,
+                        perspective: config.line_perspective,
+                        line_style: config.line_style,
+                        occluded,
+                    },
+                );
+
+                transparent_phase.add(Transparent3d {
+                    entity,
+                    draw_function,
+                    pipeline,
+                    distance: 0.,
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            }
         }
     }
 }
@@ -370,7 +492,7 @@ fn queue_line_joint_gizmos_3d(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
-    line_gizmo_assets: Res<RenderAssets<GpuLineGizmo>>,
+    line_gizmo_assets: Res<LineGizmoBuffers>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
     mut views: Query<(
         Entity,
@@ -434,24 +556,131 @@ fn queue_line_joint_gizmos_3d(
                 continue;
             }
 
-            let pipeline = pipelines.specialize(
-                &pipeline_cache,
-                &pipeline,
-                LineJointGizmoPipelineKey {
-                    view_key,
-                    perspective: config.line_perspective,
-                    joints: line_gizmo.joints,
-                },
-            );
+            let passes: &[bool] = if config.occluded {
+                &[false, true]
+            } else {
+                &[false]
+            };
 
-            transparent_phase.add(Transparent3d {
-                entity,
-                draw_function,
-                pipeline,
-                distance: 0.,
-                batch_range: 0..1,
-                extra_index: PhaseItemExtraIndex::NONE,
-            });
+            for &occluded in passes {
+                let pipeline = pipelines.specialize(
+                    &pipeline_cache,
+                    &pipeline,
+                    LineJointGizmoPipelineKey {
+                        view_key,
+                        perspective: config.line_perspective,
+                        joints: line_gizmo.joints,
+                        occluded,
+                    },
+                );
+
+                transparent_phase.add(Transparent3d {
+                    entity,
+                    draw_function,
+                    pipeline,
+                    distance: 0.,
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_triangle_gizmos_3d(
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    pipeline: Res<TriangleGizmoPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TriangleGizmoPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
+    line_gizmo_assets: Res<LineGizmoBuffers>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    mut views: Query<(
+        Entity,
+        &ExtractedView,
+        Option<&RenderLayers>,
+        (
+            Has<NormalPrepass>,
+            Has<DepthPrepass>,
+            Has<MotionVectorPrepass>,
+            Has<DeferredPrepass>,
+        ),
+    )>,
+) {
+    let draw_function = draw_functions
+        .read()
+        .get_id::<DrawTriangleGizmo3d>()
+        .unwrap();
+
+    for (
+        view_entity,
+        view,
+        render_layers,
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+    ) in &mut views
+    {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view_entity) else {
+            continue;
+        };
+
+        let render_layers = render_layers.unwrap_or_default();
+
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+
+        if normal_prepass {
+            view_key |= MeshPipelineKey::NORMAL_PREPASS;
+        }
+
+        if depth_prepass {
+            view_key |= MeshPipelineKey::DEPTH_PREPASS;
+        }
+
+        if motion_vector_prepass {
+            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+        }
+
+        if deferred_prepass {
+            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+        }
+
+        for (entity, handle, config) in &line_gizmos {
+            if !config.render_layers.intersects(render_layers) {
+                continue;
+            }
+
+            let Some(line_gizmo) = line_gizmo_assets.get(handle) else {
+                continue;
+            };
+
+            if !line_gizmo.triangles {
+                continue;
+            }
+
+            let passes: &[bool] = if config.occluded {
+                &[false, true]
+            } else {
+                &[false]
+            };
+
+            for &occluded in passes {
+                let pipeline = pipelines.specialize(
+                    &pipeline_cache,
+                    &pipeline,
+                    TriangleGizmoPipelineKey { view_key, occluded },
+                );
+
+                transparent_phase.add(Transparent3d {
+                    entity,
+                    draw_function,
+                    pipeline,
+                    distance: 0.,
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                });
+            }
         }
     }
 }
@@ -0,0 +1,339 @@
+//! Additional [`Gizmos`] Functions -- Plots
+//!
+//! Includes the implementation of [`Gizmos::plot`],
+//! and assorted support items.
+
+use std::ops::Range;
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::{Alpha, Color};
+use bevy_math::{UVec2, Vec2};
+
+/// A line series drawn by [`Gizmos::plot`].
+#[derive(Clone, Copy, Debug)]
+pub struct PlotSeries<'a> {
+    /// The data points of this series, in data coordinates.
+    pub points: &'a [Vec2],
+    /// The color of this series.
+    pub color: Color,
+}
+
+impl<'a> PlotSeries<'a> {
+    /// Create a new series from data points and a color.
+    pub fn new(points: &'a [Vec2], color: impl Into<Color>) -> Self {
+        Self {
+            points,
+            color: color.into(),
+        }
+    }
+}
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a 2D plot of one or more line series, with axes and tick marks.
+    ///
+    /// This should be called for each frame the plot needs to be rendered.
+    ///
+    /// # Arguments
+    ///
+    /// - `origin`: the bottom left corner of the plot area.
+    /// - `size`: the size of the plot area.
+    /// - `series`: the data series to draw. Each series is drawn as a line strip in its own color.
+    ///
+    /// By default the data range of the axes is fitted to the data of all series.
+    ///
+    /// # Builder methods
+    ///
+    /// - The data range of the axes can be fixed with `.x_range(...)` and `.y_range(...)`.
+    /// Data outside of the range is clamped to the edges of the plot, and an empty range is widened
+    /// to a unit range around its value.
+    /// - The number of intervals between tick marks on each axis can be set with `.ticks(...)`.
+    /// - A grid through the tick marks can be enabled with `.grid()`.
+    /// - Numeric labels at the tick marks can be enabled with `.labels()`, and sized with `.label_size(...)`.
+    /// - The color of the axes, ticks and labels can be set with `.axis_color(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::{prelude::*, plot::PlotSeries};
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{RED, BLUE};
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let sine: Vec<Vec2> = (0..100)
+    ///         .map(|i| i as f32 / 10.)
+    ///         .map(|x| Vec2::new(x, x.sin()))
+    ///         .collect();
+    ///     let cosine: Vec<Vec2> = sine.iter().map(|p| Vec2::new(p.x, p.x.cos())).collect();
+    ///
+    ///     gizmos
+    ///         .plot(
+    ///             Vec2::new(-200., -100.),
+    ///             Vec2::new(400., 200.),
+    ///             &[PlotSeries::new(&sine, RED), PlotSeries::new(&cosine, BLUE)],
+    ///         )
+    ///         .y_range(-1.0..1.0)
+    ///         .ticks(UVec2::new(10, 4))
+    ///         .grid()
+    ///         .labels();
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn plot<'a>(
+        &'a mut self,
+        origin: Vec2,
+        size: Vec2,
+        series: &'a [PlotSeries<'a>],
+    ) -> PlotBuilder<'a, 'w, 's, Config, Clear> {
+        PlotBuilder {
+            gizmos: self,
+            origin,
+            size,
+            series,
+            x_range: None,
+            y_range: None,
+            ticks: UVec2::splat(DEFAULT_PLOT_TICKS),
+            grid: false,
+            labels: false,
+            label_size: None,
+            axis_color: Color::WHITE,
+        }
+    }
+}
+
+const DEFAULT_PLOT_TICKS: u32 = 5;
+
+/// A builder returned by [`Gizmos::plot`].
+pub struct PlotBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    origin: Vec2,
+    size: Vec2,
+    series: &'a [PlotSeries<'a>],
+    x_range: Option<Range<f32>>,
+    y_range: Option<Range<f32>>,
+    ticks: UVec2,
+    grid: bool,
+    labels: bool,
+    label_size: Option<f32>,
+    axis_color: Color,
+}
+
+impl<Config, Clear> PlotBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the range of data values shown along the x axis.
+    pub fn x_range(mut self, range: Range<f32>) -> Self {
+        self.x_range = Some(range);
+        self
+    }
+
+    /// Set the range of data values shown along the y axis.
+    pub fn y_range(mut self, range: Range<f32>) -> Self {
+        self.y_range = Some(range);
+        self
+    }
+
+    /// Set the number of intervals between tick marks along the x and y axes.
+    ///
+    /// Defaults to 5 intervals per axis.
+    pub fn ticks(mut self, ticks: UVec2) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Draw grid lines through the tick marks.
+    pub fn grid(mut self) -> Self {
+        self.grid = true;
+        self
+    }
+
+    /// Draw numeric labels at the tick marks.
+    pub fn labels(mut self) -> Self {
+        self.labels = true;
+        self
+    }
+
+    /// Set the cap height of the tick labels.
+    ///
+    /// Defaults to 4% of the plot height.
+    pub fn label_size(mut self, size: f32) -> Self {
+        self.label_size = Some(size);
+        self
+    }
+
+    /// Set the color of the axes, tick marks and labels.
+    ///
+    /// Defaults to white. The grid is drawn in the same color at a lower opacity.
+    pub fn axis_color(mut self, color: impl Into<Color>) -> Self {
+        self.axis_color = color.into();
+        self
+    }
+
+    fn data_bounds(&self) -> (Range<f32>, Range<f32>) {
+        let (min, max) = self
+            .series
+            .iter()
+            .flat_map(|series| series.points)
+            .filter(|point| point.is_finite())
+            .fold(
+                (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+                |(min, max), &point| (min.min(point), max.max(point)),
+            );
+
+        let x_range = self.x_range.clone().unwrap_or(min.x..max.x);
+        let y_range = self.y_range.clone().unwrap_or(min.y..max.y);
+        (fit_range(x_range), fit_range(y_range))
+    }
+}
+
+/// Makes `range` usable as the range of an axis, widening it if it is empty so that the data
+/// can be scaled to it.
+fn fit_range(range: Range<f32>) -> Range<f32> {
+    let (min, max) = (range.start, range.end);
+    if !min.is_finite() || !max.is_finite() {
+        0.0..1.0
+    } else if min == max {
+        min - 0.5..max + 0.5
+    } else {
+        min..max
+    }
+}
+
+impl<Config, Clear> Drop for PlotBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let (x_range, y_range) = self.data_bounds();
+        let (origin, size, axis_color) = (self.origin, self.size, self.axis_color);
+        let data_min = Vec2::new(x_range.start, y_range.start);
+        let data_size = Vec2::new(x_range.end - x_range.start, y_range.end - y_range.start);
+        let to_plot = |point: Vec2| {
+            let normalized = ((point - data_min) / data_size).clamp(Vec2::ZERO, Vec2::ONE);
+            origin + normalized * size
+        };
+
+        // Axes
+        self.gizmos.linestrip_2d(
+            [origin + Vec2::Y * size.y, origin, origin + Vec2::X * size.x],
+            axis_color,
+        );
+
+        // Ticks, grid lines and labels
+        let tick_length = 0.02 * size.min_element();
+        let label_size = self.label_size.unwrap_or(0.04 * size.y);
+        let grid_color = axis_color.with_alpha(axis_color.alpha() * 0.25);
+        let ticks = self.ticks.max(UVec2::ONE);
+        for i in 0..=ticks.x {
+            let t = i as f32 / ticks.x as f32;
+            let base = origin + Vec2::X * t * size.x;
+            self.gizmos
+                .line_2d(base, base - Vec2::Y * tick_length, axis_color);
+            if self.grid && i > 0 {
+                self.gizmos
+                    .line_2d(base, base + Vec2::Y * size.y, grid_color);
+            }
+            if self.labels {
+                let value = x_range.start + t * data_size.x;
+                let label = format_tick(value, data_size.x / ticks.x as f32);
+                self.gizmos
+                    .text_2d(base - Vec2::Y * 2. * tick_length, &label, axis_color)
+                    .size(label_size)
+                    .anchor(Vec2::new(0., 0.5));
+            }
+        }
+        for i in 0..=ticks.y {
+            let t = i as f32 / ticks.y as f32;
+            let base = origin + Vec2::Y * t * size.y;
+            self.gizmos
+                .line_2d(base, base - Vec2::X * tick_length, axis_color);
+            if self.grid && i > 0 {
+                self.gizmos
+                    .line_2d(base, base + Vec2::X * size.x, grid_color);
+            }
+            if self.labels {
+                let value = y_range.start + t * data_size.y;
+                let label = format_tick(value, data_size.y / ticks.y as f32);
+                self.gizmos
+                    .text_2d(base - Vec2::X * 2. * tick_length, &label, axis_color)
+                    .size(label_size)
+                    .anchor(Vec2::new(0.5, 0.));
+            }
+        }
+
+        // Data, with a gap wherever a point isn't finite
+        for series in self.series {
+            for segment in series.points.split(|point| !point.is_finite()) {
+                if segment.len() < 2 {
+                    continue;
+                }
+                self.gizmos
+                    .linestrip_2d(segment.iter().copied().map(to_plot), series.color);
+            }
+        }
+    }
+}
+
+/// Formats a tick value with just enough decimals to distinguish ticks `step` apart.
+pub(crate) fn format_tick(value: f32, step: f32) -> String {
+    let decimals = if step > 0. && step.is_finite() {
+        (-step.log10().floor()).clamp(0., 6.) as usize
+    } else {
+        0
+    };
+    // Avoid printing "-0"
+    let value = if value.abs() < step.abs() * 1e-3 {
+        0.
+    } else {
+        value
+    };
+    format!("{value:.decimals$}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_tick_decimals() {
+        assert_eq!(format_tick(3.0, 1.0), "3");
+        assert_eq!(format_tick(12.0, 5.0), "12");
+        assert_eq!(format_tick(0.25, 0.05), "0.25");
+        assert_eq!(format_tick(0.5, 0.1), "0.5");
+        assert_eq!(format_tick(1.0, 0.001), "1.000");
+        assert_eq!(format_tick(-2.5, 0.5), "-2.5");
+    }
+
+    #[test]
+    fn format_tick_avoids_negative_zero() {
+        assert_eq!(format_tick(-1e-7, 0.1), "0.0");
+        assert_eq!(format_tick(-1e-7, 1.0), "0");
+    }
+
+    #[test]
+    fn format_tick_invalid_step() {
+        assert_eq!(format_tick(1.25, 0.0), "1");
+        assert_eq!(format_tick(1.25, f32::NAN), "1");
+    }
+
+    #[test]
+    fn fit_range_widens_empty_ranges() {
+        assert_eq!(fit_range(1.0..1.0), 0.5..1.5);
+        assert_eq!(fit_range(f32::INFINITY..f32::NEG_INFINITY), 0.0..1.0);
+        assert_eq!(fit_range(0.0..f32::NAN), 0.0..1.0);
+        assert_eq!(fit_range(-1.0..2.0), -1.0..2.0);
+    }
+}
@@ -0,0 +1,379 @@
+//! A module for retained gizmos.
+//!
+//! Retained gizmos are recorded once and keep being drawn every frame until their
+//! [`GizmoHandle`] is dropped or a given duration elapses, so debug drawings don't have to be
+//! re-issued every frame.
+//!
+//! Gizmos drawn through [`Gizmos::timed`] additionally fade out over their duration, which is
+//! useful to visualize transient events such as hits or triggers.
+
+use std::{
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bevy_color::{Alpha, LinearRgba};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_math::Vec3;
+use bevy_time::Time;
+use bevy_utils::default;
+
+use crate::{
+    config::GizmoConfigGroup,
+    gizmos::{GizmoStorage, Gizmos},
+};
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw gizmos that persist until the returned [`GizmoHandle`] is dropped.
+    ///
+    /// Every gizmo drawn with the [`Gizmos`] passed to `draw` is retained, instead of only being
+    /// rendered for the current frame.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::{prelude::*, retained::GizmoHandle};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{GREEN, RED};
+    /// #[derive(Resource)]
+    /// struct Path(GizmoHandle);
+    ///
+    /// fn setup(mut commands: Commands, mut gizmos: Gizmos) {
+    ///     let handle = gizmos.retain(|gizmos| {
+    ///         gizmos.linestrip([Vec3::ZERO, Vec3::X, Vec3::Y], GREEN);
+    ///         gizmos.sphere(Vec3::Y, Quat::IDENTITY, 0.5, RED);
+    ///     });
+    ///     // The path is drawn for as long as this resource exists.
+    ///     commands.insert_resource(Path(handle));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(setup);
+    /// ```
+    pub fn retain(&mut self, draw: impl FnOnce(&mut Self)) -> GizmoHandle {
+        self.retain_inner(None, draw)
+    }
+
+    /// Draw gizmos that persist for `duration`, or until the returned [`GizmoHandle`] is dropped.
+    ///
+    /// Use [`GizmoHandle::detach`] to keep the gizmos around for the full duration without
+    /// holding on to the handle.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::RED;
+    /// # use std::time::Duration;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .retain_for(Duration::from_secs(2), |gizmos| {
+    ///             gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 1., RED);
+    ///         })
+    ///         .detach();
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn retain_for(&mut self, duration: Duration, draw: impl FnOnce(&mut Self)) -> GizmoHandle {
+        self.retain_inner(Some(duration), draw)
+    }
+
+    /// Draw gizmos that persist for `duration` while fading out.
+    ///
+    /// Every gizmo drawn through the returned [`TimedGizmos`] is retained, and its alpha
+    /// decreases linearly to zero over `duration`. Unlike [`Gizmos::retain_for`], no handle is
+    /// returned, so the gizmos are always kept for the full duration.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::{RED, YELLOW};
+    /// # use std::time::Duration;
+    /// #[derive(Event)]
+    /// struct Hit(Vec3);
+    ///
+    /// fn system(mut gizmos: Gizmos, mut hits: EventReader<Hit>) {
+    ///     for hit in hits.read() {
+    ///         gizmos
+    ///             .timed(Duration::from_secs(1))
+    ///             .sphere(hit.0, Quat::IDENTITY, 0.2, RED);
+    ///
+    ///         // Multiple gizmos can be drawn with the same duration
+    ///         let mut timed = gizmos.timed(Duration::from_millis(500));
+    ///         timed.line(hit.0, hit.0 + Vec3::Y, YELLOW);
+    ///         timed.line(hit.0, hit.0 + Vec3::X, YELLOW);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn timed(&mut self, duration: Duration) -> TimedGizmos<'_, 'w, 's, Config, Clear> {
+        TimedGizmos {
+            lens: self.buffer_lens(),
+            duration,
+            gizmos: self,
+        }
+    }
+
+    fn retain_inner(
+        &mut self,
+        remaining: Option<Duration>,
+        draw: impl FnOnce(&mut Self),
+    ) -> GizmoHandle {
+        let lens = self.buffer_lens();
+
+        draw(self);
+
+        let cleared = Arc::new(AtomicBool::new(false));
+        self.retain_since(lens, cleared.clone(), remaining, None);
+
+        GizmoHandle {
+            cleared,
+            detached: false,
+        }
+    }
+
+    /// Returns the current lengths of the line list, line strip and triangle buffers.
+    fn buffer_lens(&self) -> [usize; 3] {
+        [
+            self.buffer.list_positions.len(),
+            self.buffer.strip_positions.len(),
+            self.buffer.triangle_positions.len(),
+        ]
+    }
+
+    /// Moves everything drawn since the buffer had the given lengths to the retained gizmos.
+    fn retain_since(
+        &mut self,
+        [list_len, strip_len, triangle_len]: [usize; 3],
+        cleared: Arc<AtomicBool>,
+        remaining: Option<Duration>,
+        fade: Option<Duration>,
+    ) {
+        if self.enabled {
+            let buffer = &mut *self.buffer;
+            buffer.retained.push(RetainedGizmo {
+                cleared,
+                remaining,
+                fade,
+                list_positions: buffer.list_positions.split_off(list_len),
+                list_colors: buffer.list_colors.split_off(list_len),
+                list_widths: buffer.list_widths.split_off(list_len),
+                list_flags: buffer.list_flags.split_off(list_len),
+                strip_positions: buffer.strip_positions.split_off(strip_len),
+                strip_colors: buffer.strip_colors.split_off(strip_len),
+                strip_widths: buffer.strip_widths.split_off(strip_len),
+                strip_flags: buffer.strip_flags.split_off(strip_len),
+                triangle_positions: buffer.triangle_positions.split_off(triangle_len),
+                triangle_colors: buffer.triangle_colors.split_off(triangle_len),
+                triangle_flags: buffer.triangle_flags.split_off(triangle_len),
+            });
+        }
+    }
+}
+
+/// A [`Gizmos`] wrapper returned by [`Gizmos::timed`].
+///
+/// Gizmos drawn through it are retained once it is dropped, and fade out over the given
+/// duration.
+pub struct TimedGizmos<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    lens: [usize; 3],
+    duration: Duration,
+}
+
+impl<'w, 's, Config, Clear> Deref for TimedGizmos<'_, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    type Target = Gizmos<'w, 's, Config, Clear>;
+
+    fn deref(&self) -> &Self::Target {
+        self.gizmos
+    }
+}
+
+impl<Config, Clear> DerefMut for TimedGizmos<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.gizmos
+    }
+}
+
+impl<Config, Clear> Drop for TimedGizmos<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        self.gizmos.retain_since(
+            self.lens,
+            Arc::new(AtomicBool::new(false)),
+            Some(self.duration),
+            Some(self.duration),
+        );
+    }
+}
+
+/// A handle to gizmos drawn with [`Gizmos::retain`] or [`Gizmos::retain_for`].
+///
+/// The gizmos stop being drawn once this handle is dropped.
+#[must_use = "retained gizmos are cleared when their handle is dropped, use `GizmoHandle::detach` to keep them"]
+pub struct GizmoHandle {
+    cleared: Arc<AtomicBool>,
+    detached: bool,
+}
+
+impl GizmoHandle {
+    /// Stop drawing the gizmos associated with this handle.
+    ///
+    /// This is equivalent to dropping the handle.
+    pub fn clear(self) {}
+
+    /// Keep drawing the gizmos associated with this handle without holding on to it.
+    ///
+    /// Gizmos drawn with [`Gizmos::retain_for`] are still cleared once their duration elapses,
+    /// while gizmos drawn with [`Gizmos::retain`] are kept until [`RetainedGizmos::clear`] is called.
+    pub fn detach(mut self) {
+        self.detached = true;
+    }
+
+    /// Returns `true` if the gizmos associated with this handle have been cleared.
+    pub fn is_cleared(&self) -> bool {
+        self.cleared.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for GizmoHandle {
+    fn drop(&mut self) {
+        if !self.detached {
+            self.cleared.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+pub(crate) struct RetainedGizmo {
+    cleared: Arc<AtomicBool>,
+    remaining: Option<Duration>,
+    /// The total duration over which the gizmo fades out, if it does.
+    fade: Option<Duration>,
+    list_positions: Vec<Vec3>,
+    list_colors: Vec<LinearRgba>,
+    list_widths: Vec<f32>,
+    list_flags: Vec<u32>,
+    strip_positions: Vec<Vec3>,
+    strip_colors: Vec<LinearRgba>,
+    strip_widths: Vec<f32>,
+    strip_flags: Vec<u32>,
+    triangle_positions: Vec<Vec3>,
+    triangle_colors: Vec<LinearRgba>,
+    triangle_flags: Vec<u32>,
+}
+
+/// Storage of the retained gizmos of a [`GizmoConfigGroup`].
+///
+/// See [`Gizmos::retain`].
+#[derive(Resource)]
+pub struct RetainedGizmos<Config> {
+    pub(crate) gizmos: Vec<RetainedGizmo>,
+    marker: PhantomData<Config>,
+}
+
+impl<Config> Default for RetainedGizmos<Config> {
+    fn default() -> Self {
+        Self {
+            gizmos: default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Config> RetainedGizmos<Config> {
+    /// Returns the number of retained gizmo drawings.
+    pub fn len(&self) -> usize {
+        self.gizmos.len()
+    }
+
+    /// Returns `true` if there are no retained gizmo drawings.
+    pub fn is_empty(&self) -> bool {
+        self.gizmos.is_empty()
+    }
+
+    /// Clear all retained gizmos, including detached ones.
+    pub fn clear(&mut self) {
+        for gizmo in mem::take(&mut self.gizmos) {
+            gizmo.cleared.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Drop expired retained gizmos and add the remaining ones to the gizmos drawn this frame.
+///
+/// This should be before [`UpdateGizmoMeshes`](crate::UpdateGizmoMeshes).
+pub fn update_retained_gizmos<Config>(
+    mut retained: ResMut<RetainedGizmos<Config>>,
+    mut storage: ResMut<GizmoStorage<Config, ()>>,
+    time: Res<Time>,
+) where
+    Config: GizmoConfigGroup,
+{
+    let delta = time.delta();
+    retained.gizmos.retain_mut(|gizmo| {
+        if gizmo.cleared.load(Ordering::Relaxed) {
+            return false;
+        }
+        if let Some(remaining) = &mut gizmo.remaining {
+            if remaining.is_zero() {
+                gizmo.cleared.store(true, Ordering::Relaxed);
+                return false;
+            }
+            *remaining = remaining.saturating_sub(delta);
+        }
+        true
+    });
+
+    for gizmo in &retained.gizmos {
+        let alpha = match (gizmo.fade, gizmo.remaining) {
+            (Some(fade), Some(remaining)) if !fade.is_zero() => {
+                remaining.as_secs_f32() / fade.as_secs_f32()
+            }
+            _ => 1.,
+        };
+        let fade = |color: &LinearRgba| color.with_alpha(color.alpha * alpha);
+
+        storage.list_positions.extend(&gizmo.list_positions);
+        storage
+            .list_colors
+            .extend(gizmo.list_colors.iter().map(fade));
+        storage.list_widths.extend(&gizmo.list_widths);
+        storage.list_flags.extend(&gizmo.list_flags);
+        storage.strip_positions.extend(&gizmo.strip_positions);
+        storage
+            .strip_colors
+            .extend(gizmo.strip_colors.iter().map(fade));
+        storage.strip_widths.extend(&gizmo.strip_widths);
+        storage.strip_flags.extend(&gizmo.strip_flags);
+        storage.triangle_positions.extend(&gizmo.triangle_positions);
+        storage
+            .triangle_colors
+            .extend(gizmo.triangle_colors.iter().map(fade));
+        storage.triangle_flags.extend(&gizmo.triangle_flags);
+    }
+}
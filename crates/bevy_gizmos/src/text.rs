@@ -0,0 +1,515 @@
+//! Additional [`Gizmos`] Functions -- Text
+//!
+//! Includes the implementation of [`Gizmos::text`] and [`Gizmos::text_2d`],
+//! and assorted support items.
+//!
+//! Text is drawn with a simple built-in stroke font, so labels go through the same line
+//! rendering as every other gizmo and respect the [`GizmoConfig`](crate::config::GizmoConfig)
+//! of the group they are drawn with.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_transform::components::GlobalTransform;
+
+/// Default cap height of text drawn with [`Gizmos::text`], in world units.
+pub const DEFAULT_TEXT_SIZE_3D: f32 = 0.25;
+
+/// Default cap height of text drawn with [`Gizmos::text_2d`], in world units (pixels for a default 2D camera).
+pub const DEFAULT_TEXT_SIZE_2D: f32 = 16.;
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a text label in 3D.
+    ///
+    /// This should be called for each frame the text needs to be rendered.
+    ///
+    /// The text is laid out in the XY plane of `rotation` and is centered on `position` by
+    /// default. To make a label face the camera, pass the camera's transform to
+    /// [`TextBuilder::billboard`].
+    ///
+    /// Only printable ASCII characters (and `°`) are supported. Lowercase letters are drawn as
+    /// uppercase and any other character is drawn as a box. Newlines start a new line below the
+    /// current one.
+    ///
+    /// # Builder methods
+    ///
+    /// - The cap height of the text can be adjusted using `.size(...)`.
+    /// - The orientation of the text can be adjusted using `.rotation(...)`, or made to face a
+    ///   camera using `.billboard(...)`.
+    /// - The alignment of the text relative to `position` can be adjusted using `.anchor(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_transform::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_color::palettes::basic::{GREEN, WHITE};
+    /// fn system(mut gizmos: Gizmos, camera: Query<&GlobalTransform, With<Camera>>) {
+    ///     gizmos.text(Vec3::Y, "Hello, world!", GREEN);
+    ///
+    ///     // Make the label face the camera
+    ///     gizmos
+    ///         .text(Vec3::ZERO, "origin", WHITE)
+    ///         .billboard(camera.single())
+    ///         .size(0.5);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn text<'a>(
+        &'a mut self,
+        position: Vec3,
+        text: &'a str,
+        color: impl Into<Color>,
+    ) -> TextBuilder<'a, 'w, 's, Config, Clear> {
+        TextBuilder {
+            gizmos: self,
+            position,
+            rotation: Quat::IDENTITY,
+            text,
+            size: DEFAULT_TEXT_SIZE_3D,
+            anchor: Vec2::ZERO,
+            color: color.into(),
+        }
+    }
+
+    /// Draw a text label in 2D.
+    ///
+    /// This should be called for each frame the text needs to be rendered.
+    ///
+    /// See [`Gizmos::text`] for the supported characters.
+    ///
+    /// # Builder methods
+    ///
+    /// - The cap height of the text can be adjusted using `.size(...)`.
+    /// - The alignment of the text relative to `position` can be adjusted using `.anchor(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.text_2d(Vec2::ZERO, "42 Hz", GREEN);
+    ///
+    ///     // Left-align the label on the given position
+    ///     gizmos
+    ///         .text_2d(Vec2::new(100., 0.), "x axis", GREEN)
+    ///         .anchor(Vec2::new(-0.5, 0.))
+    ///         .size(24.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn text_2d<'a>(
+        &'a mut self,
+        position: Vec2,
+        text: &'a str,
+        color: impl Into<Color>,
+    ) -> TextBuilder<'a, 'w, 's, Config, Clear> {
+        TextBuilder {
+            gizmos: self,
+            position: position.extend(0.),
+            rotation: Quat::IDENTITY,
+            text,
+            size: DEFAULT_TEXT_SIZE_2D,
+            anchor: Vec2::ZERO,
+            color: color.into(),
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::text`] and [`Gizmos::text_2d`].
+pub struct TextBuilder<'a, 'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    position: Vec3,
+    rotation: Quat,
+    text: &'a str,
+    size: f32,
+    anchor: Vec2,
+    color: Color,
+}
+
+impl<Config, Clear> TextBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Set the cap height of the text.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the orientation of the text.
+    ///
+    /// Text is laid out along the local X axis, with the local Y axis pointing up.
+    pub fn rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Make the text face the camera at `camera_transform`.
+    ///
+    /// The text is laid out parallel to the camera's image plane and upright on screen, whatever
+    /// the position of the text on screen. This overrides [`rotation`](Self::rotation), so it
+    /// needs to be called every frame the camera moves.
+    pub fn billboard(mut self, camera_transform: &GlobalTransform) -> Self {
+        self.rotation = camera_transform.to_scale_rotation_translation().1;
+        self
+    }
+
+    /// Set the point of the text's bounding box that is placed at the text's position.
+    ///
+    /// The anchor is given relative to the size of the bounding box, so `(-0.5, -0.5)` is the
+    /// bottom left corner, `(0., 0.)` the center and `(0.5, 0.5)` the top right corner.
+    ///
+    /// Defaults to the center.
+    pub fn anchor(mut self, anchor: Vec2) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+impl<Config, Clear> Drop for TextBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled || self.text.is_empty() {
+            return;
+        }
+
+        let scale = self.size / GLYPH_HEIGHT;
+        let bounds = text_bounds(self.text);
+        let offset = -(self.anchor + Vec2::splat(0.5)) * bounds;
+        let (position, rotation, color) = (self.position, self.rotation, self.color);
+
+        let mut line_start = Vec2::new(offset.x, offset.y + bounds.y - GLYPH_HEIGHT);
+        let mut cursor = line_start;
+        for character in self.text.chars() {
+            if character == '\n' {
+                line_start.y -= LINE_HEIGHT;
+                cursor = line_start;
+                continue;
+            }
+
+            let to_world = |[x, y]: [i8; 2]| {
+                let local = (cursor + Vec2::new(x as f32, y as f32)) * scale;
+                position + rotation * local.extend(0.)
+            };
+            for &stroke in glyph(character) {
+                if let &[start, end] = stroke {
+                    self.gizmos.line(to_world(start), to_world(end), color);
+                } else {
+                    self.gizmos
+                        .linestrip(stroke.iter().copied().map(to_world), color);
+                }
+            }
+
+            cursor.x += GLYPH_ADVANCE;
+        }
+    }
+}
+
+/// Height of an uppercase glyph in font units.
+const GLYPH_HEIGHT: f32 = 6.;
+/// Horizontal distance between the origins of two consecutive glyphs in font units.
+const GLYPH_ADVANCE: f32 = 6.;
+/// Width of a glyph in font units.
+const GLYPH_WIDTH: f32 = 4.;
+/// Vertical distance between the baselines of two consecutive lines in font units.
+const LINE_HEIGHT: f32 = 10.;
+
+/// Returns the size of the bounding box of `text` in font units.
+fn text_bounds(text: &str) -> Vec2 {
+    let (lines, longest) = text.split('\n').fold((0, 0), |(lines, longest), line| {
+        (lines + 1, longest.max(line.chars().count()))
+    });
+    let width = if longest == 0 {
+        0.
+    } else {
+        (longest - 1) as f32 * GLYPH_ADVANCE + GLYPH_WIDTH
+    };
+    let height = (lines - 1) as f32 * LINE_HEIGHT + GLYPH_HEIGHT;
+    Vec2::new(width, height)
+}
+
+type Glyph = &'static [&'static [[i8; 2]]];
+
+/// Returns the strokes making up `character`.
+///
+/// Glyphs are defined on a grid [`GLYPH_WIDTH`] units wide and [`GLYPH_HEIGHT`] units tall, with
+/// the origin at the bottom left corner. Each stroke is a polyline.
+fn glyph(character: char) -> Glyph {
+    match character.to_ascii_uppercase() {
+        ' ' => &[],
+        '0' => &[
+            &[
+                [1, 0],
+                [3, 0],
+                [4, 1],
+                [4, 5],
+                [3, 6],
+                [1, 6],
+                [0, 5],
+                [0, 1],
+                [1, 0],
+            ],
+            &[[0, 1], [4, 5]],
+        ],
+        '1' => &[&[[1, 5], [2, 6], [2, 0]], &[[1, 0], [3, 0]]],
+        '2' => &[&[[0, 5], [1, 6], [3, 6], [4, 5], [4, 4], [0, 0], [4, 0]]],
+        '3' => &[&[
+            [0, 6],
+            [4, 6],
+            [2, 4],
+            [3, 4],
+            [4, 3],
+            [4, 1],
+            [3, 0],
+            [1, 0],
+            [0, 1],
+        ]],
+        '4' => &[&[[3, 0], [3, 6], [0, 2], [4, 2]]],
+        '5' => &[&[
+            [4, 6],
+            [0, 6],
+            [0, 4],
+            [3, 4],
+            [4, 3],
+            [4, 1],
+            [3, 0],
+            [0, 0],
+        ]],
+        '6' => &[&[
+            [3, 6],
+            [1, 6],
+            [0, 5],
+            [0, 1],
+            [1, 0],
+            [3, 0],
+            [4, 1],
+            [4, 3],
+            [3, 4],
+            [0, 4],
+        ]],
+        '7' => &[&[[0, 6], [4, 6], [1, 0]]],
+        '8' => &[
+            &[
+                [1, 3],
+                [0, 4],
+                [0, 5],
+                [1, 6],
+                [3, 6],
+                [4, 5],
+                [4, 4],
+                [3, 3],
+                [1, 3],
+            ],
+            &[
+                [1, 3],
+                [0, 2],
+                [0, 1],
+                [1, 0],
+                [3, 0],
+                [4, 1],
+                [4, 2],
+                [3, 3],
+            ],
+        ],
+        '9' => &[&[
+            [4, 3],
+            [1, 3],
+            [0, 4],
+            [0, 5],
+            [1, 6],
+            [3, 6],
+            [4, 5],
+            [4, 1],
+            [3, 0],
+            [1, 0],
+        ]],
+        'A' => &[&[[0, 0], [0, 4], [2, 6], [4, 4], [4, 0]], &[[0, 2], [4, 2]]],
+        'B' => &[
+            &[[0, 0], [0, 6], [3, 6], [4, 5], [4, 4], [3, 3], [0, 3]],
+            &[[3, 3], [4, 2], [4, 1], [3, 0], [0, 0]],
+        ],
+        'C' => &[&[
+            [4, 5],
+            [3, 6],
+            [1, 6],
+            [0, 5],
+            [0, 1],
+            [1, 0],
+            [3, 0],
+            [4, 1],
+        ]],
+        'D' => &[&[[0, 0], [0, 6], [2, 6], [4, 4], [4, 2], [2, 0], [0, 0]]],
+        'E' => &[&[[4, 6], [0, 6], [0, 0], [4, 0]], &[[0, 3], [3, 3]]],
+        'F' => &[&[[4, 6], [0, 6], [0, 0]], &[[0, 3], [3, 3]]],
+        'G' => &[&[
+            [4, 5],
+            [3, 6],
+            [1, 6],
+            [0, 5],
+            [0, 1],
+            [1, 0],
+            [3, 0],
+            [4, 1],
+            [4, 3],
+            [2, 3],
+        ]],
+        'H' => &[&[[0, 0], [0, 6]], &[[4, 0], [4, 6]], &[[0, 3], [4, 3]]],
+        'I' => &[&[[1, 6], [3, 6]], &[[2, 6], [2, 0]], &[[1, 0], [3, 0]]],
+        'J' => &[&[[4, 6], [4, 1], [3, 0], [1, 0], [0, 1]]],
+        'K' => &[&[[0, 0], [0, 6]], &[[4, 6], [0, 2]], &[[1, 3], [4, 0]]],
+        'L' => &[&[[0, 6], [0, 0], [4, 0]]],
+        'M' => &[&[[0, 0], [0, 6], [2, 3], [4, 6], [4, 0]]],
+        'N' => &[&[[0, 0], [0, 6], [4, 0], [4, 6]]],
+        'O' => &[&[
+            [1, 0],
+            [3, 0],
+            [4, 1],
+            [4, 5],
+            [3, 6],
+            [1, 6],
+            [0, 5],
+            [0, 1],
+            [1, 0],
+        ]],
+        'P' => &[&[[0, 0], [0, 6], [3, 6], [4, 5], [4, 4], [3, 3], [0, 3]]],
+        'Q' => &[
+            &[
+                [1, 0],
+                [3, 0],
+                [4, 1],
+                [4, 5],
+                [3, 6],
+                [1, 6],
+                [0, 5],
+                [0, 1],
+                [1, 0],
+            ],
+            &[[2, 2], [4, 0]],
+        ],
+        'R' => &[
+            &[[0, 0], [0, 6], [3, 6], [4, 5], [4, 4], [3, 3], [0, 3]],
+            &[[2, 3], [4, 0]],
+        ],
+        'S' => &[&[
+            [4, 5],
+            [3, 6],
+            [1, 6],
+            [0, 5],
+            [0, 4],
+            [1, 3],
+            [3, 3],
+            [4, 2],
+            [4, 1],
+            [3, 0],
+            [1, 0],
+            [0, 1],
+        ]],
+        'T' => &[&[[0, 6], [4, 6]], &[[2, 6], [2, 0]]],
+        'U' => &[&[[0, 6], [0, 1], [1, 0], [3, 0], [4, 1], [4, 6]]],
+        'V' => &[&[[0, 6], [2, 0], [4, 6]]],
+        'W' => &[&[[0, 6], [1, 0], [2, 3], [3, 0], [4, 6]]],
+        'X' => &[&[[0, 0], [4, 6]], &[[0, 6], [4, 0]]],
+        'Y' => &[&[[0, 6], [2, 3], [4, 6]], &[[2, 3], [2, 0]]],
+        'Z' => &[&[[0, 6], [4, 6], [0, 0], [4, 0]]],
+        '.' => &[&[[2, 0], [2, 1]]],
+        ',' => &[&[[2, 1], [1, -1]]],
+        ':' => &[&[[2, 1], [2, 2]], &[[2, 4], [2, 5]]],
+        ';' => &[&[[2, 1], [1, -1]], &[[2, 4], [2, 5]]],
+        '!' => &[&[[2, 6], [2, 2]], &[[2, 1], [2, 0]]],
+        '?' => &[
+            &[[0, 5], [1, 6], [3, 6], [4, 5], [4, 4], [2, 3], [2, 2]],
+            &[[2, 1], [2, 0]],
+        ],
+        '\'' => &[&[[2, 6], [2, 5]]],
+        '"' => &[&[[1, 6], [1, 5]], &[[3, 6], [3, 5]]],
+        '`' => &[&[[1, 6], [2, 5]]],
+        '-' => &[&[[1, 3], [3, 3]]],
+        '+' => &[&[[0, 3], [4, 3]], &[[2, 1], [2, 5]]],
+        '*' => &[&[[2, 1], [2, 5]], &[[0, 2], [4, 4]], &[[0, 4], [4, 2]]],
+        '/' => &[&[[0, 0], [4, 6]]],
+        '\\' => &[&[[0, 6], [4, 0]]],
+        '=' => &[&[[0, 2], [4, 2]], &[[0, 4], [4, 4]]],
+        '<' => &[&[[4, 5], [0, 3], [4, 1]]],
+        '>' => &[&[[0, 5], [4, 3], [0, 1]]],
+        '_' => &[&[[0, -1], [4, -1]]],
+        '|' => &[&[[2, -1], [2, 7]]],
+        '^' => &[&[[0, 4], [2, 6], [4, 4]]],
+        '~' => &[&[[0, 3], [1, 4], [3, 2], [4, 3]]],
+        '(' => &[&[[3, 6], [1, 4], [1, 2], [3, 0]]],
+        ')' => &[&[[1, 6], [3, 4], [3, 2], [1, 0]]],
+        '[' => &[&[[3, 6], [1, 6], [1, 0], [3, 0]]],
+        ']' => &[&[[1, 6], [3, 6], [3, 0], [1, 0]]],
+        '{' => &[&[[3, 6], [2, 5], [2, 4], [1, 3], [2, 2], [2, 1], [3, 0]]],
+        '}' => &[&[[1, 6], [2, 5], [2, 4], [3, 3], [2, 2], [2, 1], [1, 0]]],
+        '#' => &[
+            &[[1, 0], [1, 6]],
+            &[[3, 0], [3, 6]],
+            &[[0, 2], [4, 2]],
+            &[[0, 4], [4, 4]],
+        ],
+        '%' => &[
+            &[[0, 0], [4, 6]],
+            &[[0, 6], [1, 6], [1, 5], [0, 5], [0, 6]],
+            &[[3, 1], [4, 1], [4, 0], [3, 0], [3, 1]],
+        ],
+        '&' => &[&[
+            [4, 0],
+            [1, 5],
+            [2, 6],
+            [3, 5],
+            [0, 2],
+            [0, 1],
+            [1, 0],
+            [2, 0],
+            [4, 2],
+        ]],
+        '$' => &[
+            &[
+                [4, 5],
+                [1, 5],
+                [0, 4],
+                [1, 3],
+                [3, 3],
+                [4, 2],
+                [3, 1],
+                [0, 1],
+            ],
+            &[[2, 6], [2, 0]],
+        ],
+        '@' => &[&[
+            [3, 2],
+            [3, 4],
+            [1, 4],
+            [1, 2],
+            [4, 2],
+            [4, 5],
+            [3, 6],
+            [1, 6],
+            [0, 5],
+            [0, 1],
+            [1, 0],
+            [4, 0],
+        ]],
+        '°' => &[&[[1, 6], [2, 6], [2, 5], [1, 5], [1, 6]]],
+        _ => &[&[[0, 0], [4, 0], [4, 6], [0, 6], [0, 0]]],
+    }
+}
@@ -0,0 +1,168 @@
+//! A module adding debug visualization of the recent motion of entities.
+
+use crate as bevy_gizmos;
+
+use std::{collections::VecDeque, iter, time::Duration};
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_color::{Alpha, Color, Oklcha};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{config::GizmoConfigGroup, gizmos::Gizmos, AppGizmoBuilder};
+
+/// A [`Plugin`] that draws the trails of entities with a [`GizmoTrail`] component.
+pub struct TrailGizmoPlugin;
+
+impl Plugin for TrailGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<TrailGizmoConfigGroup>()
+            .register_type::<GizmoTrail>()
+            .init_gizmo_group::<TrailGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (record_trails, draw_trails)
+                    .chain()
+                    .after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to draw [`GizmoTrail`]s.
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+pub struct TrailGizmoConfigGroup {
+    /// The default color for trails.
+    ///
+    /// A random color is chosen per trail if `None`.
+    ///
+    /// Defaults to `None`.
+    pub default_color: Option<Color>,
+}
+
+/// Add this [`Component`] to an entity to draw a trail behind it.
+///
+/// The trail is a polyline through the recent positions of the entity, which fades out towards
+/// its oldest position.
+///
+/// # Example
+/// ```
+/// # use bevy_gizmos::trail::GizmoTrail;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// # use std::time::Duration;
+/// fn setup(mut commands: Commands) {
+///     // Draw the positions of the last 2 seconds
+///     commands.spawn((
+///         TransformBundle::default(),
+///         GizmoTrail::new(40, Duration::from_millis(50)),
+///     ));
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct GizmoTrail {
+    /// The maximum number of recorded positions.
+    ///
+    /// Defaults to `60`.
+    pub length: usize,
+    /// The time between two recorded positions.
+    ///
+    /// Defaults to 1/30th of a second.
+    pub interval: Duration,
+    /// The color of the trail.
+    ///
+    /// The default color from the [`TrailGizmoConfigGroup`] config is used if `None`.
+    pub color: Option<Color>,
+    #[reflect(ignore)]
+    positions: VecDeque<Vec3>,
+    #[reflect(ignore)]
+    elapsed: Duration,
+}
+
+impl GizmoTrail {
+    /// Create a new trail recording up to `length` positions, one every `interval`.
+    pub fn new(length: usize, interval: Duration) -> Self {
+        Self {
+            length,
+            interval,
+            ..Default::default()
+        }
+    }
+
+    /// Set the color of the trail.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Remove all recorded positions.
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+
+    /// Returns the recorded positions, from the oldest to the newest.
+    pub fn positions(&self) -> impl ExactSizeIterator<Item = Vec3> + '_ {
+        self.positions.iter().copied()
+    }
+}
+
+impl Default for GizmoTrail {
+    fn default() -> Self {
+        Self {
+            length: 60,
+            interval: Duration::from_secs_f64(1. / 30.),
+            color: None,
+            positions: VecDeque::new(),
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+fn record_trails(time: Res<Time>, mut query: Query<(&GlobalTransform, &mut GizmoTrail)>) {
+    for (transform, mut trail) in &mut query {
+        trail.elapsed += time.delta();
+        if trail.elapsed < trail.interval && !trail.positions.is_empty() {
+            continue;
+        }
+        trail.elapsed = Duration::ZERO;
+
+        trail.positions.push_back(transform.translation());
+        while trail.positions.len() > trail.length {
+            trail.positions.pop_front();
+        }
+    }
+}
+
+fn draw_trails(
+    query: Query<(Entity, &GlobalTransform, &GizmoTrail)>,
+    mut gizmos: Gizmos<TrailGizmoConfigGroup>,
+) {
+    for (entity, transform, trail) in &query {
+        let color = trail
+            .color
+            .or(gizmos.config_ext.default_color)
+            .unwrap_or_else(|| color_from_entity(entity));
+
+        // Connect the trail to the current position of the entity
+        let count = trail.positions.len() + 1;
+        let positions = trail.positions().chain(iter::once(transform.translation()));
+        gizmos.linestrip_gradient(positions.enumerate().map(|(i, position)| {
+            let t = (i + 1) as f32 / count as f32;
+            (position, color.with_alpha(color.alpha() * t))
+        }));
+    }
+}
+
+fn color_from_entity(entity: Entity) -> Color {
+    Oklcha::sequential_dispersed(entity.index()).into()
+}
@@ -0,0 +1,78 @@
+// TODO use common view binding
+#import bevy_render::view::View
+
+@group(0) @binding(0) var<uniform> view: View;
+
+
+struct LineGizmoUniform {
+    line_width: f32,
+    depth_bias: f32,
+    joints_resolution: u32,
+    occluded_opacity: f32,
+}
+
+@group(1) @binding(0) var<uniform> triangle_gizmo: LineGizmoUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) flags: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+const EPSILON: f32 = 4.88e-04;
+
+// Must match the flags in `gizmos.rs`
+const SCREEN_SPACE_FLAG: u32 = 1u;
+const X_RAY_FLAG: u32 = 2u;
+
+@vertex
+fn vertex(vertex: VertexInput) -> VertexOutput {
+    let screen_space = (vertex.flags & SCREEN_SPACE_FLAG) != 0u;
+    let clip = project(vertex.position, screen_space);
+
+    // X-ray gizmos are drawn as if their depth bias was -1, in front of everything else
+    let depth_bias = select(triangle_gizmo.depth_bias, -1., (vertex.flags & X_RAY_FLAG) != 0u);
+
+    // See `lines.wgsl` for how the depth bias is applied
+    var depth: f32;
+    if screen_space {
+        depth = clip.w;
+    } else if depth_bias >= 0. {
+        depth = clip.z * (1. - depth_bias);
+    } else {
+        depth = clip.z * exp2(-depth_bias * log2(clip.w / clip.z - EPSILON));
+    }
+
+    return VertexOutput(vec4(clip.xy, depth, clip.w), vertex.color);
+}
+
+// Screen space positions are given in pixels, with the origin at the top left of the viewport.
+fn project(position: vec3<f32>, screen_space: bool) -> vec4<f32> {
+    if screen_space {
+        let ndc = 2. * position.xy / view.viewport.zw - 1.;
+        return vec4(ndc.x, -ndc.y, 1., 1.);
+    }
+    return view.view_proj * vec4(position, 1.);
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+};
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+};
+
+@fragment
+fn fragment(in: FragmentInput) -> FragmentOutput {
+#ifdef OCCLUDED
+    return FragmentOutput(vec4(in.color.rgb, in.color.a * triangle_gizmo.occluded_opacity));
+#else
+    return FragmentOutput(in.color);
+#endif
+}
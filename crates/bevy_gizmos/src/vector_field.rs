@@ -0,0 +1,203 @@
+//! Additional [`Gizmos`] Functions -- Vector fields
+//!
+//! Includes the implementation of [`Gizmos::vector_field`] and [`Gizmos::vector_field_2d`],
+//! and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::{
+    bounding::{Aabb2d, Aabb3d},
+    UVec2, UVec3, Vec2, Vec3,
+};
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a vector field in 3D by sampling `field` on a grid and drawing an arrow for each sample.
+    ///
+    /// This should be called for each frame the vector field needs to be rendered.
+    ///
+    /// # Arguments
+    ///
+    /// - `bounds`: the box in which the field is sampled.
+    /// - `resolution`: the number of samples along each axis. Samples are taken at the centers of
+    /// the cells of the grid.
+    /// - `field`: returns the vector at a given position.
+    /// - `color`: the color of the arrows.
+    ///
+    /// By default, the arrows are scaled so that the longest one fits into a grid cell.
+    /// Samples where the field is zero or not finite are skipped.
+    ///
+    /// # Builder methods
+    ///
+    /// - The arrows can be given a fixed scale relative to the sampled vectors using `.scale(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::{prelude::*, bounding::Aabb3d};
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A vortex around the Y axis
+    ///     gizmos.vector_field(
+    ///         Aabb3d::new(Vec3::ZERO, Vec3::splat(2.)),
+    ///         UVec3::splat(8),
+    ///         |position| Vec3::Y.cross(position),
+    ///         GREEN,
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn vector_field<F: Fn(Vec3) -> Vec3>(
+        &mut self,
+        bounds: Aabb3d,
+        resolution: UVec3,
+        field: F,
+        color: impl Into<Color>,
+    ) -> VectorFieldBuilder<'_, 'w, 's, Config, Clear, F> {
+        VectorFieldBuilder {
+            gizmos: self,
+            min: bounds.min.into(),
+            max: bounds.max.into(),
+            resolution,
+            field,
+            color: color.into(),
+            scale: None,
+        }
+    }
+
+    /// Draw a vector field in 2D by sampling `field` on a grid and drawing an arrow for each sample.
+    ///
+    /// This should be called for each frame the vector field needs to be rendered.
+    ///
+    /// See [`Gizmos::vector_field`] for a description of the arguments.
+    ///
+    /// # Builder methods
+    ///
+    /// - The arrows can be given a fixed scale relative to the sampled vectors using `.scale(...)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::{prelude::*, bounding::Aabb2d};
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // The gradient of the distance to the origin
+    ///     gizmos
+    ///         .vector_field_2d(
+    ///             Aabb2d::new(Vec2::ZERO, Vec2::splat(200.)),
+    ///             UVec2::splat(10),
+    ///             |position| position.normalize_or_zero(),
+    ///             GREEN,
+    ///         )
+    ///         .scale(15.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn vector_field_2d(
+        &mut self,
+        bounds: Aabb2d,
+        resolution: UVec2,
+        field: impl Fn(Vec2) -> Vec2,
+        color: impl Into<Color>,
+    ) -> VectorFieldBuilder<'_, 'w, 's, Config, Clear, impl Fn(Vec3) -> Vec3> {
+        VectorFieldBuilder {
+            gizmos: self,
+            min: bounds.min.extend(0.),
+            max: bounds.max.extend(0.),
+            resolution: resolution.extend(1),
+            field: move |position: Vec3| field(position.truncate()).extend(0.),
+            color: color.into(),
+            scale: None,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::vector_field`] and [`Gizmos::vector_field_2d`].
+pub struct VectorFieldBuilder<'a, 'w, 's, Config, Clear, F>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+    F: Fn(Vec3) -> Vec3,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    min: Vec3,
+    max: Vec3,
+    resolution: UVec3,
+    field: F,
+    color: Color,
+    scale: Option<f32>,
+}
+
+impl<Config, Clear, F> VectorFieldBuilder<'_, '_, '_, Config, Clear, F>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+    F: Fn(Vec3) -> Vec3,
+{
+    /// Set the factor by which the sampled vectors are scaled to get the length of the arrows.
+    ///
+    /// By default, the scale is chosen so that the longest arrow fits into a grid cell.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+}
+
+impl<Config, Clear, F> Drop for VectorFieldBuilder<'_, '_, '_, Config, Clear, F>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+    F: Fn(Vec3) -> Vec3,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let resolution = self.resolution.max(UVec3::ONE);
+        let cell_size = (self.max - self.min) / resolution.as_vec3();
+
+        let mut samples = Vec::with_capacity((resolution.x * resolution.y * resolution.z) as usize);
+        for z in 0..resolution.z {
+            for y in 0..resolution.y {
+                for x in 0..resolution.x {
+                    let cell = UVec3::new(x, y, z).as_vec3() + 0.5;
+                    let position = self.min + cell * cell_size;
+                    let vector = (self.field)(position);
+                    if vector.is_finite() && vector != Vec3::ZERO {
+                        samples.push((position, vector));
+                    }
+                }
+            }
+        }
+
+        let scale = self.scale.unwrap_or_else(|| {
+            // Ignore flat axes, such as the Z axis of 2D vector fields
+            let cell_length = cell_size
+                .abs()
+                .to_array()
+                .into_iter()
+                .filter(|&length| length > 0.)
+                .fold(f32::INFINITY, f32::min);
+            let max_length = samples
+                .iter()
+                .map(|(_, vector)| vector.length())
+                .fold(0., f32::max);
+            if cell_length.is_finite() && max_length > 0. {
+                cell_length / max_length
+            } else {
+                1.
+            }
+        });
+
+        // Center the arrows on their sample positions
+        for (position, vector) in samples {
+            let offset = 0.5 * scale * vector;
+            self.gizmos
+                .arrow(position - offset, position + offset, self.color);
+        }
+    }
+}
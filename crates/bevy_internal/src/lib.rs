@@ -21,6 +21,8 @@ pub use bevy_app as app;
 pub use bevy_asset as asset;
 #[cfg(feature = "bevy_audio")]
 pub use bevy_audio as audio;
+#[cfg(feature = "bevy_camera_controller")]
+pub use bevy_camera_controller as camera_controller;
 #[cfg(feature = "bevy_color")]
 pub use bevy_color as color;
 pub use bevy_core as core;
@@ -59,6 +59,10 @@ pub use crate::dynamic_plugin::*;
 #[cfg(feature = "bevy_gizmos")]
 pub use crate::gizmos::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_camera_controller")]
+pub use crate::camera_controller::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_gilrs")]
 pub use crate::gilrs::*;
@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+use {
+    bevy_app::App,
+    bevy_ecs::system::Resource,
+    bevy_utils::synccell::SyncCell,
+    tracing_appender::{
+        non_blocking::WorkerGuard,
+        rolling::{RollingFileAppender, Rotation},
+    },
+    tracing_subscriber::{registry::LookupSpan, Layer},
+};
+
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+use crate::{format::fmt_layer, LogFormat};
+
+/// How often a new log file is started, see [`FileLogSettings::rotation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogRotation {
+    /// Start a new file every minute.
+    Minutely,
+    /// Start a new file every hour.
+    Hourly,
+    /// Start a new file every day.
+    #[default]
+    Daily,
+    /// Keep writing to a single file.
+    Never,
+}
+
+/// Settings for writing logs to files, used by [`LogPlugin::file`](crate::LogPlugin::file).
+///
+/// Log files are named after [`file_name_prefix`](Self::file_name_prefix) and the date and time
+/// the file was started, such as `bevy.2024-04-01-13.log` for hourly rotation. They are written
+/// on a background thread, so logging does not block on disk access.
+///
+/// File logging is not available on WASM and Android, where these settings are ignored.
+///
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup};
+/// # use bevy_log::{FileLogSettings, LogPlugin, LogRotation};
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins.set(LogPlugin {
+///             file: Some(FileLogSettings {
+///                 rotation: LogRotation::Hourly,
+///                 max_files: Some(24),
+///                 ..FileLogSettings::new("logs")
+///             }),
+///             ..Default::default()
+///         }))
+///         .run();
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileLogSettings {
+    /// The directory log files are written to. It is created if it does not exist.
+    pub directory: PathBuf,
+    /// The start of the name of each log file.
+    pub file_name_prefix: String,
+    /// How often a new log file is started.
+    pub rotation: LogRotation,
+    /// The number of log files to keep, if limited.
+    ///
+    /// When a new file is started, the oldest files in [`directory`](Self::directory) with the
+    /// same prefix are deleted to stay within the limit.
+    pub max_files: Option<usize>,
+    /// Whether logs are still written to `stderr` as well.
+    pub also_log_to_stderr: bool,
+}
+
+impl FileLogSettings {
+    /// Creates settings that write daily log files to `directory`, in addition to `stderr`.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_name_prefix: "bevy".to_string(),
+            rotation: LogRotation::Daily,
+            max_files: None,
+            also_log_to_stderr: true,
+        }
+    }
+}
+
+/// Wrapper resource for `tracing-appender`'s worker guard.
+/// When the guard is dropped the remaining logs are flushed to file.
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+#[allow(dead_code)]
+#[derive(Resource)]
+pub(crate) struct FileLogGuard(SyncCell<WorkerGuard>);
+
+/// Creates the layer writing logs in `format` to files, or `None` if the log directory could not
+/// be set up.
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub(crate) fn file_layer<S>(
+    app: &mut App,
+    settings: &FileLogSettings,
+    format: LogFormat,
+) -> Option<impl Layer<S>>
+where
+    S: bevy_utils::tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    let rotation = match settings.rotation {
+        LogRotation::Minutely => Rotation::MINUTELY,
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Never => Rotation::NEVER,
+    };
+    let mut builder = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(&settings.file_name_prefix)
+        .filename_suffix("log");
+    if let Some(max_files) = settings.max_files {
+        builder = builder.max_log_files(max_files);
+    }
+
+    // The subscriber is not set up yet, so this can't be logged
+    let appender = match builder.build(&settings.directory) {
+        Ok(appender) => appender,
+        Err(err) => {
+            eprintln!(
+                "Could not write logs to {}: {err}",
+                settings.directory.display()
+            );
+            return None;
+        }
+    };
+
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    app.insert_resource(FileLogGuard(SyncCell::new(guard)));
+
+    Some(fmt_layer(format, writer, false))
+}
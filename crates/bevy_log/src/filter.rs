@@ -0,0 +1,63 @@
+use bevy_ecs::system::Resource;
+use thiserror::Error;
+use tracing_subscriber::{
+    filter::ParseError, layer::Layered, registry::Registry, reload, EnvFilter,
+};
+
+use crate::BoxedLayer;
+
+/// The subscriber the filter of the [`LogPlugin`](crate::LogPlugin) is layered on.
+pub(crate) type FilteredSubscriber = Layered<Option<BoxedLayer>, Registry>;
+
+/// Changes the log filter of the [`LogPlugin`](crate::LogPlugin) while the app is running.
+///
+/// This makes it possible to turn on verbose logging of a single subsystem while investigating a
+/// problem, without restarting the app.
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_log::LogSettings;
+/// fn debug_rendering(log_settings: Res<LogSettings>) {
+///     log_settings
+///         .set_filter("info,bevy_render=debug,wgpu=warn")
+///         .unwrap();
+/// }
+/// # bevy_ecs::system::assert_is_system(debug_rendering);
+/// ```
+#[derive(Resource, Clone)]
+pub struct LogSettings {
+    handle: reload::Handle<EnvFilter, FilteredSubscriber>,
+}
+
+/// An error returned when the log filter could not be changed.
+#[derive(Error, Debug)]
+pub enum LogFilterError {
+    /// The directives are not in the [`EnvFilter`] format.
+    #[error("invalid log filter directives: {0}")]
+    InvalidDirectives(#[from] ParseError),
+    /// The filter is not in use, for example because another subscriber was already set.
+    #[error("the log filter could not be replaced: {0}")]
+    Reload(#[from] reload::Error),
+}
+
+impl LogSettings {
+    pub(crate) fn new(handle: reload::Handle<EnvFilter, FilteredSubscriber>) -> Self {
+        Self { handle }
+    }
+
+    /// Replaces the log filter with `directives` in the [`EnvFilter`] format, such as
+    /// `info,bevy_render=debug`.
+    ///
+    /// Unlike [`LogPlugin::filter`](crate::LogPlugin::filter), the directives replace the level
+    /// as well, so it should be included.
+    pub fn set_filter(&self, directives: &str) -> Result<(), LogFilterError> {
+        let filter = EnvFilter::try_new(directives)?;
+        self.handle.reload(filter)?;
+        Ok(())
+    }
+
+    /// Returns the directives of the current log filter.
+    pub fn filter(&self) -> Result<String, LogFilterError> {
+        Ok(self.handle.with_current(ToString::to_string)?)
+    }
+}
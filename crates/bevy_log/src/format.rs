@@ -0,0 +1,41 @@
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+use {
+    bevy_utils::tracing::Subscriber,
+    tracing_subscriber::{fmt::MakeWriter, registry::LookupSpan, Layer},
+};
+
+/// How the [`LogPlugin`](crate::LogPlugin) formats the logs it writes to `stderr` and files.
+///
+/// The format is not used on WASM and Android, where logs go to the platform's own log viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines, colored when written to a terminal.
+    #[default]
+    Text,
+    /// Newline-delimited JSON, with one object per record, for ingestion by log tooling.
+    ///
+    /// Each object has the `timestamp`, `level` and `target` of the record, its `fields` including
+    /// the `message`, and the current `span` and list of `spans` it was logged in with their
+    /// fields.
+    Json,
+}
+
+/// Creates a layer writing records in `format` to `writer`.
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub(crate) fn fmt_layer<S, W>(
+    format: LogFormat,
+    writer: W,
+    ansi: bool,
+) -> Box<dyn Layer<S> + Send + Sync + 'static>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::Layer::default()
+        .with_ansi(ansi)
+        .with_writer(writer);
+    match format {
+        LogFormat::Text => Box::new(layer),
+        LogFormat::Json => Box::new(layer.json()),
+    }
+}
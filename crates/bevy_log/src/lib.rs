@@ -21,6 +21,19 @@ use std::panic;
 
 #[cfg(target_os = "android")]
 mod android_tracing;
+mod file;
+mod filter;
+mod format;
+mod log_events;
+mod recent_logs;
+mod span_timings;
+
+pub use file::{FileLogSettings, LogRotation};
+pub use filter::{LogFilterError, LogSettings};
+pub use format::LogFormat;
+pub use log_events::LogEvent;
+pub use recent_logs::{LogRecord, RecentLogs};
+pub use span_timings::{SpanTiming, SpanTimings};
 
 #[cfg(feature = "trace_tracy_memory")]
 #[global_allocator]
@@ -36,25 +49,33 @@ pub mod prelude {
 
     #[doc(hidden)]
     pub use bevy_utils::{debug_once, error_once, info_once, once, trace_once, warn_once};
+
+    #[doc(hidden)]
+    pub use bevy_utils::{
+        debug_throttled, error_throttled, info_throttled, throttled, trace_throttled,
+        warn_throttled,
+    };
 }
 
 pub use bevy_utils::{
-    debug_once, error_once, info_once, once, trace_once,
+    debug_once, debug_throttled, error_once, error_throttled, info_once, info_throttled, once,
+    throttled, trace_once, trace_throttled,
     tracing::{
         debug, debug_span, error, error_span, info, info_span, trace, trace_span, warn, warn_span,
         Level,
     },
-    warn_once,
+    warn_once, warn_throttled,
 };
 pub use tracing_subscriber;
 
-use bevy_app::{App, Plugin};
+use bevy_app::{App, First, Plugin};
+#[cfg(feature = "tracing-chrome")]
+use bevy_ecs::system::Resource;
+use bevy_utils::synccell::SyncCell;
 use tracing_log::LogTracer;
 #[cfg(feature = "tracing-chrome")]
 use tracing_subscriber::fmt::{format::DefaultFields, FormattedFields};
 use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter, Layer};
-#[cfg(feature = "tracing-chrome")]
-use {bevy_ecs::system::Resource, bevy_utils::synccell::SyncCell};
 
 /// Wrapper resource for `tracing-chrome`'s flush guard.
 /// When the guard is dropped the chrome log is written to file.
@@ -75,7 +96,7 @@ pub(crate) struct FlushGuard(SyncCell<tracing_chrome::FlushGuard>);
 /// You can configure this plugin.
 /// ```no_run
 /// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup};
-/// # use bevy_log::LogPlugin;
+/// # use bevy_log::{LogFormat, LogPlugin};
 /// # use bevy_utils::tracing::Level;
 /// fn main() {
 ///     App::new()
@@ -83,6 +104,11 @@ pub(crate) struct FlushGuard(SyncCell<tracing_chrome::FlushGuard>);
 ///             level: Level::DEBUG,
 ///             filter: "wgpu=error,bevy_render=info,bevy_ecs=trace".to_string(),
 ///             custom_layer: |_| None,
+///             file: None,
+///             recent_records: 500,
+///             log_events: Some(Level::WARN),
+///             format: LogFormat::Text,
+///             span_timings: false,
 ///         }))
 ///         .run();
 /// }
@@ -95,6 +121,8 @@ pub(crate) struct FlushGuard(SyncCell<tracing_chrome::FlushGuard>);
 /// If you define the `RUST_LOG` environment variable, the [`LogPlugin`] settings
 /// will be ignored.
 ///
+/// The filter can be changed while the app is running through the [`LogSettings`] resource.
+///
 /// If you want to setup your own tracing collector, you should disable this
 /// plugin from `DefaultPlugins`:
 /// ```no_run
@@ -131,6 +159,39 @@ pub struct LogPlugin {
     ///
     /// Please see the `examples/log_layers.rs` for a complete example.
     pub custom_layer: fn(app: &mut App) -> Option<BoxedLayer>,
+
+    /// Optionally write logs to rotating files, see [`FileLogSettings`].
+    ///
+    /// Logs are still filtered by [`level`](Self::level) and [`filter`](Self::filter).
+    pub file: Option<FileLogSettings>,
+
+    /// The number of recent log records kept in the [`RecentLogs`] resource, for in-app log
+    /// viewers such as the log console of `bevy_dev_tools`.
+    ///
+    /// Records that don't pass [`level`](Self::level) and [`filter`](Self::filter) are not kept.
+    /// Capturing formats every record and takes a lock, so this defaults to `0`, which doesn't
+    /// capture records or add the resource at all.
+    pub recent_records: usize,
+
+    /// Optionally send a [`LogEvent`] for each log record at this level or above, such as
+    /// [`Level::WARN`] for warnings and errors.
+    ///
+    /// Records that don't pass [`level`](Self::level) and [`filter`](Self::filter) are not sent.
+    pub log_events: Option<Level>,
+
+    /// How logs written to `stderr` and to [`file`](Self::file) are formatted.
+    ///
+    /// Use [`LogFormat::Json`] for headless servers and CI runs whose logs are collected by other
+    /// tools.
+    pub format: LogFormat,
+
+    /// Whether to measure the time spent in each span, and make the timings available through
+    /// the [`SpanTimings`] resource.
+    ///
+    /// With the `trace` feature, Bevy emits a span for each system, so this gives a per-system
+    /// breakdown of the frame time. Spans that don't pass [`level`](Self::level) and
+    /// [`filter`](Self::filter) are not measured.
+    pub span_timings: bool,
 }
 
 /// A boxed [`Layer`] that can be used with [`LogPlugin`].
@@ -142,12 +203,16 @@ impl Default for LogPlugin {
             filter: "wgpu=error,naga=warn".to_string(),
             level: Level::INFO,
             custom_layer: |_| None,
+            file: None,
+            recent_records: 0,
+            log_events: None,
+            format: LogFormat::Text,
+            span_timings: false,
         }
     }
 }
 
 impl Plugin for LogPlugin {
-    #[cfg_attr(not(feature = "tracing-chrome"), allow(unused_variables))]
     fn build(&self, app: &mut App) {
         #[cfg(feature = "trace")]
         {
@@ -168,8 +233,33 @@ impl Plugin for LogPlugin {
         let filter_layer = EnvFilter::try_from_default_env()
             .or_else(|_| EnvFilter::try_new(&default_filter))
             .unwrap();
+        let (filter_layer, filter_handle) = tracing_subscriber::reload::Layer::new(filter_layer);
+        app.insert_resource(LogSettings::new(filter_handle));
         let subscriber = subscriber.with(filter_layer);
 
+        let recent_logs_layer = (self.recent_records > 0).then(|| {
+            let recent_logs = RecentLogs::new(self.recent_records);
+            app.insert_resource(recent_logs.clone());
+            recent_logs::RecentLogsLayer(recent_logs)
+        });
+        let subscriber = subscriber.with(recent_logs_layer);
+
+        let log_events_layer = self.log_events.map(|level| {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            app.insert_resource(log_events::CapturedLogEvents(SyncCell::new(receiver)))
+                .add_event::<LogEvent>()
+                .add_systems(First, log_events::send_log_events);
+            log_events::LogEventsLayer { sender, level }
+        });
+        let subscriber = subscriber.with(log_events_layer);
+
+        let span_timings_layer = self.span_timings.then(|| {
+            let span_timings = SpanTimings::default();
+            app.insert_resource(span_timings.clone());
+            span_timings::SpanTimingsLayer(span_timings)
+        });
+        let subscriber = subscriber.with(span_timings_layer);
+
         #[cfg(feature = "trace")]
         let subscriber = subscriber.with(tracing_error::ErrorLayer::default());
 
@@ -202,7 +292,7 @@ impl Plugin for LogPlugin {
             #[cfg(feature = "tracing-tracy")]
             let tracy_layer = tracing_tracy::TracyLayer::default();
 
-            let fmt_layer = tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr);
+            let fmt_layer = format::fmt_layer(self.format, std::io::stderr, true);
 
             // bevy_render::renderer logs a `tracy.frame_mark` event every frame
             // at Level::INFO. Formatted logs should omit it.
@@ -212,7 +302,22 @@ impl Plugin for LogPlugin {
                     meta.fields().field("tracy.frame_mark").is_none()
                 }));
 
-            let subscriber = subscriber.with(fmt_layer);
+            let log_to_stderr = self
+                .file
+                .as_ref()
+                .map_or(true, |file| file.also_log_to_stderr);
+            let subscriber = subscriber.with(log_to_stderr.then_some(fmt_layer));
+
+            let file_layer = self
+                .file
+                .as_ref()
+                .and_then(|file| file::file_layer(app, file, self.format));
+            #[cfg(feature = "tracing-tracy")]
+            let file_layer =
+                file_layer.with_filter(tracing_subscriber::filter::FilterFn::new(|meta| {
+                    meta.fields().field("tracy.frame_mark").is_none()
+                }));
+            let subscriber = subscriber.with(file_layer);
 
             #[cfg(feature = "tracing-chrome")]
             let subscriber = subscriber.with(chrome_layer);
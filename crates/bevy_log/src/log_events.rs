@@ -0,0 +1,79 @@
+use std::sync::mpsc;
+
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::{ResMut, Resource},
+};
+use bevy_utils::{
+    synccell::SyncCell,
+    tracing::{self, Level, Subscriber},
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+use crate::recent_logs::MessageRecorder;
+
+/// An [`Event`] sent for each log record at or above [`LogPlugin::log_events`](crate::LogPlugin::log_events).
+///
+/// This lets systems react to problems reported through logging, for example by showing a toast
+/// for each error.
+///
+/// Records are collected while the app runs and sent in [`First`](bevy_app::First), so records
+/// logged during a frame are read by systems in the next frame.
+///
+/// ```
+/// # use bevy_ecs::event::EventReader;
+/// # use bevy_log::{Level, LogEvent};
+/// fn show_errors(mut log_events: EventReader<LogEvent>) {
+///     for event in log_events.read() {
+///         if event.level == Level::ERROR {
+///             // Show a toast with `event.message`
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(show_errors);
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct LogEvent {
+    /// The severity of the record.
+    pub level: Level,
+    /// The module path the record was logged from, or the target it was logged with.
+    pub target: String,
+    /// The formatted message, followed by any other fields as `name=value`.
+    pub message: String,
+}
+
+/// Holds the records captured by the [`LogEventsLayer`] until they are sent as events.
+#[derive(Resource)]
+pub(crate) struct CapturedLogEvents(pub(crate) SyncCell<mpsc::Receiver<LogEvent>>);
+
+/// The layer that captures records at or above a level, to be sent as [`LogEvent`]s.
+pub(crate) struct LogEventsLayer {
+    pub(crate) sender: mpsc::Sender<LogEvent>,
+    pub(crate) level: Level,
+}
+
+impl<S: Subscriber> Layer<S> for LogEventsLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        // More verbose levels compare greater
+        if *meta.level() > self.level {
+            return;
+        }
+        let mut recorder = MessageRecorder::default();
+        event.record(&mut recorder);
+        // The receiver is gone once the app is dropped
+        let _ = self.sender.send(LogEvent {
+            level: *meta.level(),
+            target: meta.target().to_string(),
+            message: recorder.finish(),
+        });
+    }
+}
+
+/// Sends the records captured since the last frame as [`LogEvent`]s.
+pub(crate) fn send_log_events(
+    mut captured: ResMut<CapturedLogEvents>,
+    mut log_events: EventWriter<LogEvent>,
+) {
+    log_events.send_batch(captured.0.get().try_iter());
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Write},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_ecs::system::Resource;
+use bevy_utils::tracing::{field::Field, Event, Level, Subscriber};
+use tracing_subscriber::{field::Visit, layer::Context, Layer};
+
+/// A log record kept in [`RecentLogs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// The severity of the record.
+    pub level: Level,
+    /// The module path the record was logged from, or the target it was logged with.
+    pub target: String,
+    /// The formatted message, followed by any other fields as `name=value`.
+    pub message: String,
+}
+
+/// The most recent log records of the app, captured by the [`LogPlugin`](crate::LogPlugin).
+///
+/// The number of records kept is set by [`LogPlugin::recent_records`](crate::LogPlugin::recent_records).
+/// Records are numbered in the order they were logged, so a reader that remembers the number of
+/// the next record can pick up new records each frame with [`read_since`](Self::read_since).
+///
+/// ```
+/// # use bevy_ecs::system::{Local, Res};
+/// # use bevy_log::RecentLogs;
+/// fn print_errors(recent_logs: Res<RecentLogs>, mut next: Local<u64>) {
+///     *next = recent_logs.read_since(*next, |record| {
+///         if record.level == bevy_log::Level::ERROR {
+///             println!("{}: {}", record.target, record.message);
+///         }
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(print_errors);
+/// ```
+#[derive(Resource, Clone)]
+pub struct RecentLogs {
+    inner: Arc<Mutex<RecentLogsInner>>,
+}
+
+struct RecentLogsInner {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    /// The number of records pushed since creation, which is the number of the next record.
+    total: u64,
+}
+
+impl RecentLogs {
+    /// Creates an empty buffer that keeps up to `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RecentLogsInner {
+                records: VecDeque::with_capacity(capacity),
+                capacity,
+                total: 0,
+            })),
+        }
+    }
+
+    /// Returns the maximum number of records kept.
+    pub fn capacity(&self) -> usize {
+        self.lock(|inner| inner.capacity)
+    }
+
+    /// Returns the number the next record will get, which is the number of records pushed so far.
+    pub fn total(&self) -> u64 {
+        self.lock(|inner| inner.total)
+    }
+
+    /// Adds a record, dropping the oldest one if the buffer is full.
+    pub fn push(&self, record: LogRecord) {
+        self.lock(|inner| {
+            if inner.capacity == 0 {
+                return;
+            }
+            if inner.records.len() == inner.capacity {
+                inner.records.pop_front();
+            }
+            inner.records.push_back(record);
+            inner.total += 1;
+        });
+    }
+
+    /// Calls `f` with each record still kept whose number is at least `since`, oldest first,
+    /// and returns the number of the next record.
+    ///
+    /// Pass `0` to read all records kept.
+    pub fn read_since(&self, since: u64, mut f: impl FnMut(&LogRecord)) -> u64 {
+        self.lock(|inner| {
+            let first = inner.total - inner.records.len() as u64;
+            let skip = since.saturating_sub(first) as usize;
+            inner.records.iter().skip(skip).for_each(&mut f);
+            inner.total
+        })
+    }
+
+    /// Removes all records kept.
+    pub fn clear(&self) {
+        self.lock(|inner| inner.records.clear());
+    }
+
+    fn lock<T>(&self, f: impl FnOnce(&mut RecentLogsInner) -> T) -> T {
+        // A panic while holding the lock can't leave the records in an invalid state
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut inner)
+    }
+}
+
+/// The layer that pushes every log record it receives to [`RecentLogs`].
+pub(crate) struct RecentLogsLayer(pub(crate) RecentLogs);
+
+impl<S: Subscriber> Layer<S> for RecentLogsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut recorder = MessageRecorder::default();
+        event.record(&mut recorder);
+        let meta = event.metadata();
+        self.0.push(LogRecord {
+            level: *meta.level(),
+            target: meta.target().to_string(),
+            message: recorder.finish(),
+        });
+    }
+}
+
+/// Formats the fields of an event like the default `stderr` output does.
+#[derive(Default)]
+pub(crate) struct MessageRecorder {
+    message: String,
+    fields: String,
+}
+
+impl MessageRecorder {
+    pub(crate) fn finish(mut self) -> String {
+        if self.message.is_empty() {
+            self.fields
+        } else {
+            if !self.fields.is_empty() {
+                self.message.push(' ');
+                self.message.push_str(&self.fields);
+            }
+            self.message
+        }
+    }
+}
+
+impl Visit for MessageRecorder {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            write!(self.message, "{value:?}").unwrap();
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            write!(self.fields, "{}={value:?}", field.name()).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(message: &str) -> LogRecord {
+        LogRecord {
+            level: Level::INFO,
+            target: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn messages_since(logs: &RecentLogs, since: u64) -> (Vec<String>, u64) {
+        let mut messages = Vec::new();
+        let next = logs.read_since(since, |record| messages.push(record.message.clone()));
+        (messages, next)
+    }
+
+    #[test]
+    fn oldest_records_are_evicted() {
+        let logs = RecentLogs::new(3);
+        for message in ["a", "b", "c", "d", "e"] {
+            logs.push(record(message));
+        }
+
+        assert_eq!(logs.total(), 5);
+        assert_eq!(
+            messages_since(&logs, 0),
+            (vec!["c".into(), "d".into(), "e".into()], 5)
+        );
+    }
+
+    #[test]
+    fn zero_capacity_keeps_nothing() {
+        let logs = RecentLogs::new(0);
+        logs.push(record("a"));
+
+        assert_eq!(logs.total(), 0);
+        assert_eq!(messages_since(&logs, 0), (vec![], 0));
+    }
+
+    #[test]
+    fn read_since_returns_new_records() {
+        let logs = RecentLogs::new(4);
+        logs.push(record("a"));
+        logs.push(record("b"));
+
+        let (messages, next) = messages_since(&logs, 0);
+        assert_eq!(messages, ["a", "b"]);
+        assert_eq!(next, 2);
+
+        // Nothing new since the last read
+        assert_eq!(messages_since(&logs, next), (vec![], 2));
+
+        logs.push(record("c"));
+        assert_eq!(messages_since(&logs, next), (vec!["c".into()], 3));
+    }
+
+    #[test]
+    fn read_since_evicted_record() {
+        let logs = RecentLogs::new(2);
+        logs.push(record("a"));
+        let (_, next) = messages_since(&logs, 0);
+        for message in ["b", "c", "d"] {
+            logs.push(record(message));
+        }
+
+        // Records evicted since the last read are skipped
+        assert_eq!(
+            messages_since(&logs, next),
+            (vec!["c".into(), "d".into()], 4)
+        );
+
+        logs.clear();
+        assert_eq!(messages_since(&logs, 4), (vec![], 4));
+        logs.push(record("e"));
+        assert_eq!(messages_since(&logs, 4), (vec!["e".into()], 5));
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_ecs::system::Resource;
+use bevy_utils::{
+    tracing::{
+        field::Field,
+        span::{Attributes, Id},
+        Subscriber,
+    },
+    Duration, HashMap, Instant,
+};
+use tracing_subscriber::{field::Visit, layer::Context, registry::LookupSpan, Layer};
+
+/// The time spent in a span since the timings were last taken from [`SpanTimings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanTiming {
+    /// The name of the span, followed by its `name` field if it has one, such as
+    /// `system: my_game::move_player` for systems.
+    pub name: String,
+    /// The total time spent in the span, including any spans entered within it.
+    pub total: Duration,
+    /// The number of times the span was entered.
+    pub count: u32,
+}
+
+/// The time spent in each span, captured by the [`LogPlugin`](crate::LogPlugin) when
+/// [`LogPlugin::span_timings`](crate::LogPlugin::span_timings) is enabled.
+///
+/// Bevy emits a span for each system, and for other parts of a frame, when the `trace` feature is
+/// enabled. Taking the timings once per frame gives a breakdown of the frame time, without an
+/// external profiler.
+///
+/// ```
+/// # use bevy_ecs::system::Res;
+/// # use bevy_log::SpanTimings;
+/// fn print_slowest_span(span_timings: Res<SpanTimings>) {
+///     let timings = span_timings.take();
+///     if let Some(slowest) = timings.iter().max_by_key(|timing| timing.total) {
+///         println!("{} took {:?}", slowest.name, slowest.total);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_slowest_span);
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct SpanTimings {
+    timings: Arc<Mutex<HashMap<Arc<str>, (Duration, u32)>>>,
+}
+
+impl SpanTimings {
+    /// Returns the time spent in each span since the last call, and starts over.
+    ///
+    /// Spans that were not exited since the last call are not included.
+    pub fn take(&self) -> Vec<SpanTiming> {
+        let timings = std::mem::take(&mut *self.lock());
+        timings
+            .into_iter()
+            .map(|(name, (total, count))| SpanTiming {
+                name: name.to_string(),
+                total,
+                count,
+            })
+            .collect()
+    }
+
+    fn add(&self, name: &Arc<str>, elapsed: Duration) {
+        let mut timings = self.lock();
+        let (total, count) = timings.entry(name.clone()).or_default();
+        *total += elapsed;
+        *count += 1;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Arc<str>, (Duration, u32)>> {
+        // A panic while holding the lock can't leave the timings in an invalid state
+        self.timings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The name a span is timed under, stored in its extensions.
+struct SpanTimingName(Arc<str>);
+
+/// When the span was last entered, stored in its extensions.
+struct SpanEntered(Instant);
+
+/// The layer that adds the time spent in each span to [`SpanTimings`].
+pub(crate) struct SpanTimingsLayer(pub(crate) SpanTimings);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanTimingsLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = NameFieldVisitor(None);
+        attrs.record(&mut visitor);
+        let name = match visitor.0 {
+            Some(field) => format!("{}: {field}", attrs.metadata().name()),
+            None => attrs.metadata().name().to_string(),
+        };
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTimingName(name.into()));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().replace(SpanEntered(Instant::now()));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(SpanEntered(entered)) = extensions.remove::<SpanEntered>() else {
+            return;
+        };
+        if let Some(SpanTimingName(name)) = extensions.get_mut::<SpanTimingName>() {
+            self.0.add(name, entered.elapsed());
+        }
+    }
+}
+
+/// Records the `name` field of a span, which Bevy uses for the name of systems.
+struct NameFieldVisitor(Option<String>);
+
+impl Visit for NameFieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
@@ -79,9 +79,12 @@ impl Plugin for ScreenSpaceAmbientOcclusionPlugin {
         if !render_app
             .world()
             .resource::<RenderAdapter>()
-            .get_texture_format_features(TextureFormat::R16Float)
-            .allowed_usages
-            .contains(TextureUsages::STORAGE_BINDING)
+            .supports_texture_format(
+                render_app.world().resource::<RenderDevice>(),
+                TextureFormat::R16Float,
+                TextureUsages::STORAGE_BINDING,
+                TextureFormatFeatureFlags::empty(),
+            )
         {
             warn!("ScreenSpaceAmbientOcclusionPlugin not loaded. GPU lacks support: TextureFormat::R16Float does not support TextureUsages::STORAGE_BINDING.");
             return;
@@ -0,0 +1,96 @@
+use bevy_ecs::{
+    entity::Entity,
+    prelude::With,
+    schedule::SystemSet,
+    system::{Query, ResMut, Resource},
+};
+use bevy_math::Ray3d;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::EntityHashMap;
+use bevy_window::{PrimaryWindow, Window};
+
+use super::{Camera, NormalizedRenderTarget};
+
+/// The rays going from each active [`Camera`] rendering to a window through the cursor, in world
+/// space.
+///
+/// A camera only has a ray while the cursor is inside its viewport. The rays are updated in
+/// `PreUpdate`, in [`CursorRaySystem`], from the cursor position of the current frame and the
+/// camera transforms and projections of the previous frame.
+///
+/// This is enough for the picking needs of many applications, such as intersecting the cursor
+/// with a ground plane or testing it against bounding volumes.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{primitives::InfinitePlane3d, Vec3};
+/// # use bevy_render::camera::{Camera, CursorRay};
+/// // Logs where the cursor points at on the ground, for each camera
+/// fn cursor_on_ground(cursor_ray: Res<CursorRay>, cameras: Query<Entity, With<Camera>>) {
+///     for camera in &cameras {
+///         let Some(ray) = cursor_ray.get(camera) else {
+///             continue;
+///         };
+///         if let Some(distance) = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y)) {
+///             println!("{:?} points at {}", camera, ray.get_point(distance));
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(cursor_on_ground);
+/// ```
+#[derive(Resource, Default, Debug, Clone)]
+pub struct CursorRay {
+    rays: EntityHashMap<Entity, Ray3d>,
+}
+
+impl CursorRay {
+    /// Returns the ray of `camera` through the cursor, if the cursor is inside its viewport.
+    pub fn get(&self, camera: Entity) -> Option<Ray3d> {
+        self.rays.get(&camera).copied()
+    }
+
+    /// Returns the cameras the cursor is over, along with their ray through the cursor.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Ray3d)> + '_ {
+        self.rays.iter().map(|(camera, ray)| (*camera, *ray))
+    }
+}
+
+/// Label for the system that updates [`CursorRay`].
+#[derive(SystemSet, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct CursorRaySystem;
+
+/// Updates [`CursorRay`] from the cursor position of each window.
+pub fn update_cursor_ray(
+    mut cursor_ray: ResMut<CursorRay>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+) {
+    cursor_ray.rays.clear();
+    let primary_window = primary_window.get_single().ok();
+    for (entity, camera, transform) in &cameras {
+        if !camera.is_active {
+            continue;
+        }
+        let Some(NormalizedRenderTarget::Window(window)) = camera.target.normalize(primary_window)
+        else {
+            continue;
+        };
+        let Some(cursor_position) = windows
+            .get(window.entity())
+            .ok()
+            .and_then(Window::cursor_position)
+        else {
+            continue;
+        };
+        let Some(viewport) = camera.logical_viewport_rect() else {
+            continue;
+        };
+        if !viewport.contains(cursor_position) {
+            continue;
+        }
+        if let Some(ray) = camera.viewport_to_world(transform, cursor_position - viewport.min) {
+            cursor_ray.rays.insert(entity, ray);
+        }
+    }
+}
@@ -2,20 +2,24 @@
 mod camera;
 mod camera_driver_node;
 mod clear_color;
+mod cursor_ray;
 mod manual_texture_view;
 mod projection;
+mod render_to_texture;
 
 pub use camera::*;
 pub use camera_driver_node::*;
 pub use clear_color::*;
+pub use cursor_ray::*;
 pub use manual_texture_view::*;
 pub use projection::*;
+pub use render_to_texture::*;
 
 use crate::{
     extract_component::ExtractComponentPlugin, extract_resource::ExtractResourcePlugin,
     render_graph::RenderGraph, ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PreUpdate};
 use bevy_ecs::schedule::IntoSystemConfigs;
 
 #[derive(Default)]
@@ -32,6 +36,8 @@ impl Plugin for CameraPlugin {
             .register_type::<MipBias>()
             .init_resource::<ManualTextureViews>()
             .init_resource::<ClearColor>()
+            .init_resource::<CursorRay>()
+            .add_systems(PreUpdate, update_cursor_ray.in_set(CursorRaySystem))
             .add_plugins((
                 CameraProjectionPlugin::<Projection>::default(),
                 CameraProjectionPlugin::<OrthographicProjection>::default(),
@@ -39,6 +45,7 @@ impl Plugin for CameraPlugin {
                 ExtractResourcePlugin::<ManualTextureViews>::default(),
                 ExtractResourcePlugin::<ClearColor>::default(),
                 ExtractComponentPlugin::<CameraMainTextureUsages>::default(),
+                RenderToTexturePlugin,
             ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
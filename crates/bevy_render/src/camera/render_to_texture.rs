@@ -0,0 +1,188 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::Component,
+    entity::Entity,
+    query::Changed,
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::{Commands, Query, ResMut},
+    world::Ref,
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_math::UVec2;
+
+use super::{Camera, CameraUpdateSystem, RenderTarget};
+use crate::{
+    render_resource::{
+        Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    },
+    texture::{BevyDefault, Image},
+};
+
+/// Manages the cameras with a [`RenderToTexture`].
+pub struct RenderToTexturePlugin;
+
+impl Plugin for RenderToTexturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                despawn_orphaned_render_to_texture,
+                update_render_to_texture_targets,
+                update_render_to_texture_activity,
+            )
+                .chain()
+                .in_set(RenderToTextureSystem)
+                .before(CameraUpdateSystem),
+        );
+    }
+}
+
+/// Label for the systems that manage cameras with a [`RenderToTexture`].
+#[derive(SystemSet, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RenderToTextureSystem;
+
+/// Renders a camera to an [`Image`] owned by this component, for minimaps, portals,
+/// picture-in-picture views and the like.
+///
+/// The camera's [`target`](Camera::target) is set to the image, which is resized whenever
+/// [`size`](Self::size) changes. The camera renders every [`update_interval`](Self::update_interval)
+/// frames, which is done by toggling [`Camera::is_active`], so that should not be changed by
+/// anything else. If the camera has an [`owner`](Self::owner), it is despawned along with its
+/// children once the owner is despawned.
+///
+/// ```
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::UVec2;
+/// # use bevy_render::{camera::{Camera, RenderToTexture}, texture::Image};
+/// #[derive(Component)]
+/// struct Minimap;
+///
+/// fn spawn_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+///     let minimap = commands.spawn(Minimap).id();
+///     let render_to_texture = RenderToTexture::new(UVec2::splat(256), &mut images)
+///         .with_owner(minimap)
+///         // The minimap doesn't need to be updated every frame
+///         .with_update_interval(4);
+///     // Show `render_to_texture.image()` in the minimap, for example with a `UiImage`
+///     commands.spawn((Camera::default(), render_to_texture));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_minimap);
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct RenderToTexture {
+    /// The entity the camera belongs to. The camera is despawned when its owner is.
+    pub owner: Option<Entity>,
+    /// The size of the image in pixels.
+    pub size: UVec2,
+    /// The number of frames between two renders of the camera. `1` renders the camera every
+    /// frame, and `0` doesn't render it at all.
+    pub update_interval: u32,
+    image: Handle<Image>,
+    frame: u32,
+}
+
+impl RenderToTexture {
+    /// Creates an image of `size` pixels to render to, which is rendered to every frame.
+    pub fn new(size: UVec2, images: &mut Assets<Image>) -> Self {
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: None,
+                size: Extent3d::default(),
+                dimension: TextureDimension::D2,
+                format: TextureFormat::bevy_default(),
+                mip_level_count: 1,
+                sample_count: 1,
+                // COPY_SRC allows the image to be read back, for example by `ReadbackTexture`
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_SRC
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+            ..Default::default()
+        };
+        image.resize(extent(size));
+
+        Self {
+            owner: None,
+            size,
+            update_interval: 1,
+            image: images.add(image),
+            frame: 0,
+        }
+    }
+
+    /// Despawns the camera along with `owner`.
+    pub fn with_owner(mut self, owner: Entity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Renders the camera every `update_interval` frames.
+    pub fn with_update_interval(mut self, update_interval: u32) -> Self {
+        self.update_interval = update_interval;
+        self
+    }
+
+    /// Returns the image the camera renders to.
+    pub fn image(&self) -> &Handle<Image> {
+        &self.image
+    }
+}
+
+fn extent(size: UVec2) -> Extent3d {
+    Extent3d {
+        width: size.x.max(1),
+        height: size.y.max(1),
+        depth_or_array_layers: 1,
+    }
+}
+
+fn despawn_orphaned_render_to_texture(
+    mut commands: Commands,
+    cameras: Query<(Entity, &RenderToTexture)>,
+    entities: Query<()>,
+) {
+    for (entity, render_to_texture) in &cameras {
+        if let Some(owner) = render_to_texture.owner {
+            if entities.get(owner).is_err() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn update_render_to_texture_targets(
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<(Ref<RenderToTexture>, &mut Camera), Changed<RenderToTexture>>,
+) {
+    for (render_to_texture, mut camera) in &mut cameras {
+        if render_to_texture.is_added() {
+            camera.target = RenderTarget::Image(render_to_texture.image.clone());
+        }
+        let size = extent(render_to_texture.size);
+        if let Some(image) = images.get(&render_to_texture.image) {
+            if image.texture_descriptor.size != size {
+                images
+                    .get_mut(&render_to_texture.image)
+                    .unwrap()
+                    .resize(size);
+            }
+        }
+    }
+}
+
+fn update_render_to_texture_activity(mut cameras: Query<(&mut RenderToTexture, &mut Camera)>) {
+    for (mut render_to_texture, mut camera) in &mut cameras {
+        let render_to_texture = render_to_texture.bypass_change_detection();
+        let is_active = render_to_texture.update_interval != 0
+            && render_to_texture.frame % render_to_texture.update_interval == 0;
+        render_to_texture.frame = render_to_texture.frame.wrapping_add(1);
+        if camera.is_active != is_active {
+            camera.is_active = is_active;
+        }
+    }
+}
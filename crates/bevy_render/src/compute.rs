@@ -0,0 +1,294 @@
+//! Dispatch compute shaders from the main world.
+//!
+//! A [`ComputeShader`] declares its bindings with [`AsBindGroup`], like a material does, and is
+//! dispatched each frame on every entity that also has a [`ComputeDispatch`], before any camera
+//! is rendered. To get the results back to the CPU, add a
+//! [`ReadbackBuffer`](crate::gpu_readback::ReadbackBuffer) or
+//! [`ReadbackTexture`](crate::gpu_readback::ReadbackTexture) for the buffer or texture the shader
+//! writes to on the same entity, which is then read back after the dispatch.
+
+use std::{any::TypeId, borrow::Cow, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetServer;
+use bevy_ecs::{prelude::*, query::QueryState};
+use bevy_math::UVec3;
+
+use crate::{
+    diagnostic::RecordDiagnostics,
+    graph::CameraDriverLabel,
+    render_asset::RenderAssets,
+    render_graph::{self, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel},
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroup, BindGroupLayout, CachedComputePipelineId,
+        ComputePassDescriptor, ComputePipelineDescriptor, PipelineCache, ShaderRef,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{FallbackImage, GpuImage},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+/// A compute shader that can be dispatched from the main world, see [`ComputePlugin`].
+///
+/// The bindings of the shader are declared with [`AsBindGroup`], and bound to group 0. Storage
+/// buffers need `visibility(compute)`, which storage textures have by default.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::UVec3;
+/// # use bevy_render::{
+/// #     compute::*,
+/// #     gpu_readback::ReadbackTexture,
+/// #     render_resource::{AsBindGroup, ShaderRef},
+/// #     texture::Image,
+/// # };
+/// #[derive(Component, AsBindGroup, Clone)]
+/// struct Blur {
+///     #[texture(0, visibility(compute))]
+///     input: Handle<Image>,
+///     #[storage_texture(1)]
+///     output: Handle<Image>,
+/// }
+///
+/// impl ComputeShader for Blur {
+///     fn shader() -> ShaderRef {
+///         "shaders/blur.wgsl".into()
+///     }
+/// }
+///
+/// #[derive(Resource)]
+/// struct Images {
+///     input: Handle<Image>,
+///     output: Handle<Image>,
+/// }
+///
+/// fn blur(mut commands: Commands, images: Res<Images>) {
+///     commands.spawn((
+///         Blur {
+///             input: images.input.clone(),
+///             output: images.output.clone(),
+///         },
+///         // A 512 by 512 image, with a workgroup size of 8 by 8
+///         ComputeDispatch::new(UVec3::new(64, 64, 1)),
+///         // Send the blurred image back to the main world
+///         ReadbackTexture(images.output.clone()),
+///     ));
+/// }
+///
+/// App::new().add_plugins(ComputePlugin::<Blur>::default());
+/// # bevy_ecs::system::assert_is_system(blur);
+/// ```
+pub trait ComputeShader: AsBindGroup + Component + Clone + Sized {
+    /// Returns the shader to dispatch.
+    ///
+    /// There is no default compute shader, so this must not return [`ShaderRef::Default`].
+    fn shader() -> ShaderRef;
+
+    /// Returns the name of the entry point of the shader.
+    fn entry_point() -> Cow<'static, str> {
+        "main".into()
+    }
+}
+
+/// Adds support for dispatching the compute shader `T`.
+///
+/// The shader is dispatched by a render graph node labeled [`ComputeShaderLabel::of::<T>`], which
+/// runs before [`CameraDriverLabel`].
+pub struct ComputePlugin<T: ComputeShader>(PhantomData<T>);
+
+impl<T: ComputeShader> Default for ComputePlugin<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T: ComputeShader> Plugin for ComputePlugin<T> {
+    fn build(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .add_systems(ExtractSchedule, extract_compute_dispatches::<T>)
+            .add_systems(
+                Render,
+                prepare_compute_bind_groups::<T>.in_set(RenderSet::PrepareBindGroups),
+            );
+
+        let node = ComputeShaderNode::<T>::new(render_app.world_mut());
+        let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        render_graph.add_node(ComputeShaderLabel::of::<T>(), node);
+        render_graph.add_node_edge(ComputeShaderLabel::of::<T>(), CameraDriverLabel);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<ComputeShaderPipeline<T>>();
+    }
+}
+
+/// Dispatches the [`ComputeShader`] on the same entity each frame.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComputeDispatch {
+    /// The number of workgroups dispatched in each dimension.
+    pub workgroups: UVec3,
+}
+
+impl ComputeDispatch {
+    /// Creates a dispatch of `workgroups` workgroups in each dimension.
+    pub fn new(workgroups: UVec3) -> Self {
+        Self { workgroups }
+    }
+}
+
+/// The label of the render graph node that dispatches a [`ComputeShader`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct ComputeShaderLabel(pub TypeId);
+
+impl ComputeShaderLabel {
+    /// Returns the label of the node that dispatches `T`.
+    pub fn of<T: ComputeShader>() -> Self {
+        Self(TypeId::of::<T>())
+    }
+}
+
+/// The bind group layout and pipeline of a [`ComputeShader`].
+#[derive(Resource)]
+pub struct ComputeShaderPipeline<T: ComputeShader> {
+    pub layout: BindGroupLayout,
+    pub pipeline_id: CachedComputePipelineId,
+    marker: PhantomData<T>,
+}
+
+impl<T: ComputeShader> FromWorld for ComputeShaderPipeline<T> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let layout = T::bind_group_layout(render_device);
+        let shader = match T::shader() {
+            ShaderRef::Default => panic!(
+                "{} must return a shader from ComputeShader::shader",
+                std::any::type_name::<T>()
+            ),
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => asset_server.load(path),
+        };
+        let pipeline_id =
+            world
+                .resource::<PipelineCache>()
+                .queue_compute_pipeline(ComputePipelineDescriptor {
+                    label: Some(std::any::type_name::<T>().into()),
+                    layout: vec![layout.clone()],
+                    push_constant_ranges: Vec::new(),
+                    shader,
+                    shader_defs: vec![],
+                    entry_point: T::entry_point(),
+                });
+
+        ComputeShaderPipeline {
+            layout,
+            pipeline_id,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The bind group of a [`ComputeShader`] in the render world.
+#[derive(Component)]
+pub struct ComputeShaderBindGroup<T: ComputeShader> {
+    pub bind_group: BindGroup,
+    marker: PhantomData<T>,
+}
+
+fn extract_compute_dispatches<T: ComputeShader>(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    query: Extract<Query<(Entity, &T, &ComputeDispatch)>>,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, shader, dispatch) in &query {
+        values.push((entity, (shader.clone(), *dispatch)));
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+fn prepare_compute_bind_groups<T: ComputeShader>(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<GpuImage>>,
+    fallback_image: Res<FallbackImage>,
+    pipeline: Res<ComputeShaderPipeline<T>>,
+    shaders: Query<(Entity, &T)>,
+) {
+    for (entity, shader) in &shaders {
+        match shader.as_bind_group(&pipeline.layout, &render_device, &images, &fallback_image) {
+            Ok(prepared) => {
+                commands.entity(entity).insert(ComputeShaderBindGroup::<T> {
+                    bind_group: prepared.bind_group,
+                    marker: PhantomData,
+                });
+            }
+            // An image isn't loaded yet, the shader will be dispatched once it is
+            Err(AsBindGroupError::RetryNextUpdate) => {}
+        }
+    }
+}
+
+/// Dispatches each entity with a [`ComputeShader`] `T` and a [`ComputeDispatch`].
+pub struct ComputeShaderNode<T: ComputeShader> {
+    query: QueryState<(&'static ComputeShaderBindGroup<T>, &'static ComputeDispatch)>,
+}
+
+impl<T: ComputeShader> ComputeShaderNode<T> {
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            query: QueryState::new(world),
+        }
+    }
+}
+
+impl<T: ComputeShader> render_graph::Node for ComputeShaderNode<T> {
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
+    }
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let pipeline = world.resource::<ComputeShaderPipeline<T>>();
+        let Some(compute_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+
+        let diagnostics = render_context.diagnostic_recorder();
+        let mut pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: T::label(),
+                    timestamp_writes: None,
+                });
+        let pass_span = diagnostics.pass_span(&mut pass, std::any::type_name::<T>());
+        pass.set_pipeline(compute_pipeline);
+        for (bind_group, dispatch) in self.query.iter_manual(world) {
+            pass.set_bind_group(0, &bind_group.bind_group, &[]);
+            pass.dispatch_workgroups(
+                dispatch.workgroups.x,
+                dispatch.workgroups.y,
+                dispatch.workgroups.z,
+            );
+        }
+        pass_span.end(&mut pass);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1010 @@
+//! Asynchronous readback of textures and buffers from the GPU to the main world.
+//!
+//! Add a [`ReadbackTexture`] or [`ReadbackBuffer`] component to an entity, and its contents will
+//! be copied to the CPU after each frame it is rendered, and sent to the main world as a
+//! [`ReadbackComplete`] event, or a [`ReadbackFailed`] event if the copy failed. The data arrives
+//! a few frames later, as the GPU has to finish the frame first. Remove the component to stop
+//! reading back.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_render::gpu_readback::ReadbackComplete;
+//! fn print_readbacks(mut readbacks: EventReader<ReadbackComplete>) {
+//!     for readback in readbacks.read() {
+//!         println!("{:?} read back {} bytes", readback.entity, readback.data.len());
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(print_readbacks);
+//! ```
+//!
+//! Render world systems can read back [`StorageBuffer`]s and [`BufferVec`]s as typed values with
+//! [`ReadbackValues`], which are stored in a [`ReadbackValue`] component in the main world.
+//!
+//! Each readback stalls the GPU a little and takes memory bandwidth, so the bytes read back each
+//! frame can be limited with a [`ReadbackBudget`]. Readbacks over budget are skipped for the
+//! frame, starting with those of the lowest [`ReadbackPriority`], and go first the next frame.
+//! A skipped readback fails with [`ReadbackError::Deferred`], so it isn't lost silently.
+
+use std::{hash::Hash, marker::PhantomData};
+
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, First, Plugin};
+use bevy_asset::Handle;
+use bevy_core::FrameCount;
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{URect, UVec2};
+use bevy_utils::{tracing::warn, warn_once, HashMap, HashSet};
+use thiserror::Error;
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder, Extent3d, ImageCopyTexture,
+    ImageDataLayout, MapMode, Origin3d, TextureAspect, TextureFormat,
+};
+
+use crate::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        encase::{
+            self,
+            internal::{CreateFrom, WriteInto},
+        },
+        Buffer, BufferId, BufferVec, ShaderType, StorageBuffer, Texture, TextureId,
+    },
+    renderer::{render_system, RenderDevice},
+    texture::{GpuImage, Image},
+    view::screenshot::{align_byte_size, get_aligned_size},
+    Render, RenderApp, RenderSet,
+};
+
+/// Adds support for [`ReadbackTexture`] and [`ReadbackBuffer`].
+pub struct GpuReadbackPlugin;
+
+impl Plugin for GpuReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        let (completed_tx, completed_rx) = async_channel::unbounded();
+        app.add_plugins((
+            ExtractComponentPlugin::<ReadbackTexture>::default(),
+            ExtractComponentPlugin::<ReadbackBuffer>::default(),
+            ExtractComponentPlugin::<ReadbackRegion>::default(),
+            ExtractComponentPlugin::<ReadbackPriority>::default(),
+            ExtractResourcePlugin::<ReadbackBudget>::default(),
+        ))
+        .init_resource::<ReadbackBudget>()
+        .add_event::<ReadbackComplete>()
+        .add_event::<ReadbackFailed>()
+        .insert_resource(CompletedReadbacks(completed_rx))
+        .add_systems(First, send_readback_events);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            let (returned_tx, returned_rx) = async_channel::unbounded();
+            render_app
+                .insert_resource(GpuReadbacks {
+                    requested: Vec::new(),
+                    pool: HashMap::default(),
+                    priority: ReadbackPriority::default(),
+                    entity: None,
+                    skipped: HashSet::default(),
+                    completed_tx,
+                    returned_tx,
+                    returned_rx,
+                })
+                .add_systems(
+                    Render,
+                    (
+                        prepare_readbacks.in_set(RenderSet::PrepareResources),
+                        apply_readback_budget
+                            .in_set(RenderSet::Render)
+                            .before(render_system),
+                    ),
+                );
+        }
+    }
+}
+
+/// Reads back the contents of an [`Image`] each frame, see the [module docs](self).
+///
+/// Only the first mip level of the first layer of the image is read back. The rows of the
+/// [`ReadbackComplete::data`] are tightly packed. Compressed texture formats, and depth-stencil
+/// formats with both aspects, are not supported.
+///
+/// Add a [`ReadbackRegion`] to the same entity to only read back part of the image.
+#[derive(Component, ExtractComponent, Clone, Debug)]
+pub struct ReadbackTexture(pub Handle<Image>);
+
+/// The part of a [`ReadbackTexture`] that is read back, in pixels.
+///
+/// The region is clamped to the size of the image, and nothing is read back if it is empty.
+#[derive(Component, ExtractComponent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadbackRegion(pub URect);
+
+/// Reads back the contents of a [`Buffer`] each frame, see the [module docs](self).
+///
+/// The buffer must be created with [`BufferUsages::COPY_SRC`].
+#[derive(Component, ExtractComponent, Clone, Debug)]
+pub struct ReadbackBuffer(pub Buffer);
+
+/// The priority of the readbacks of an entity, or of the readbacks requested in
+/// [`GpuReadbacks::with_priority`], when they don't fit in the [`ReadbackBudget`].
+#[derive(
+    Component, ExtractComponent, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum ReadbackPriority {
+    /// Skipped first when over budget.
+    Low,
+    /// Skipped when over budget, after all [`Low`](Self::Low) readbacks.
+    #[default]
+    Normal,
+    /// Never skipped, but still counts toward the budget.
+    High,
+}
+
+/// Limits the number of bytes read back from the GPU each frame, see the
+/// [module docs](self).
+///
+/// Once a readback of a priority is skipped, all readbacks of lower priorities are skipped too,
+/// even if they would still fit. The first readback of a frame is never skipped, even if it's
+/// larger than the budget, so that large readbacks still happen.
+///
+/// Only the size of the readbacks is limited, not the time they take, as that is only known once
+/// the GPU has finished the frame.
+#[derive(Resource, ExtractResource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadbackBudget {
+    /// The largest number of bytes to read back each frame, or `None` to read back everything.
+    pub max_bytes_per_frame: Option<u64>,
+}
+
+/// Sent when the data of a [`ReadbackTexture`] or [`ReadbackBuffer`] has arrived from the GPU.
+#[derive(Event, Clone, Debug)]
+pub struct ReadbackComplete {
+    /// The entity the readback component is on.
+    pub entity: Entity,
+    /// The [`FrameCount`] of the frame the data was read back in.
+    pub frame: u32,
+    /// The bytes read back.
+    pub data: Vec<u8>,
+}
+
+/// Sent instead of a [`ReadbackComplete`] when the data of a [`ReadbackTexture`] or
+/// [`ReadbackBuffer`] couldn't be read back.
+#[derive(Event, Clone, Debug)]
+pub struct ReadbackFailed {
+    /// The entity the readback component is on.
+    pub entity: Entity,
+    /// The [`FrameCount`] of the frame the data was to be read back in.
+    pub frame: u32,
+    /// Why the data couldn't be read back.
+    pub error: ReadbackError,
+}
+
+/// The reason data couldn't be read back from the GPU.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReadbackError {
+    /// The staging buffer the data was copied to couldn't be mapped, such as when the device was
+    /// lost.
+    #[error("the staging buffer couldn't be mapped: {0}")]
+    MapFailed(BufferAsyncError),
+    /// The readback was skipped this frame, as it was over the [`ReadbackBudget`].
+    #[error("the readback was skipped, as it was over the readback budget")]
+    Deferred,
+    /// The data read back isn't a valid value of the requested type.
+    #[error("the data read back can't be read as {type_name}: {reason}")]
+    InvalidData {
+        /// The type the data was read as.
+        type_name: &'static str,
+        /// Why the data isn't a valid value.
+        reason: String,
+    },
+}
+
+/// Receives the data read back in the render world.
+#[derive(Resource)]
+struct CompletedReadbacks(Receiver<(Entity, u32, Result<Vec<u8>, ReadbackError>)>);
+
+fn send_readback_events(
+    completed: Res<CompletedReadbacks>,
+    mut events: EventWriter<ReadbackComplete>,
+    mut failed: EventWriter<ReadbackFailed>,
+) {
+    while let Ok((entity, frame, result)) = completed.0.try_recv() {
+        match result {
+            Ok(data) => {
+                events.send(ReadbackComplete {
+                    entity,
+                    frame,
+                    data,
+                });
+            }
+            Err(error) => {
+                failed.send(ReadbackFailed {
+                    entity,
+                    frame,
+                    error,
+                });
+            }
+        }
+    }
+}
+
+/// Where a readback copies its data from.
+enum ReadbackSource {
+    Texture {
+        texture: Texture,
+        aspect: TextureAspect,
+        origin: UVec2,
+        width: u32,
+        height: u32,
+        pixel_size: u32,
+    },
+    Buffer(Buffer),
+}
+
+impl ReadbackSource {
+    /// Returns the key of readbacks of this source that aren't for an entity.
+    fn key(&self) -> ReadbackKey {
+        match self {
+            Self::Texture { texture, .. } => ReadbackKey::Texture(texture.id()),
+            Self::Buffer(buffer) => ReadbackKey::Buffer(buffer.id()),
+        }
+    }
+}
+
+/// Identifies the readbacks that were skipped last frame, so they can go first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum ReadbackKey {
+    /// A readback requested for an entity, see [`GpuReadbacks::for_entity`].
+    Entity(Entity),
+    /// A readback of a texture that isn't for an entity.
+    Texture(TextureId),
+    /// A readback of a buffer that isn't for an entity.
+    Buffer(BufferId),
+}
+
+/// A copy to a staging buffer, which is mapped once the frame has been submitted.
+struct GpuReadback {
+    source: ReadbackSource,
+    staging: Buffer,
+    priority: ReadbackPriority,
+    key: ReadbackKey,
+    on_complete: Box<dyn FnOnce(Result<Vec<u8>, ReadbackError>) + Send + Sync>,
+}
+
+/// The reason a texture can't be read back by [`GpuReadbacks::read_texture`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureReadbackError {
+    /// Compressed formats, and depth-stencil formats read without picking an aspect, can't be
+    /// copied to a buffer.
+    #[error("the texture format {0:?} can't be read back")]
+    UnsupportedFormat(TextureFormat),
+    /// Multisampled textures have to be resolved before they can be read back.
+    #[error("multisampled textures can't be read back")]
+    Multisampled,
+}
+
+/// The readbacks requested in the render world this frame.
+///
+/// This is used for [`ReadbackTexture`] and [`ReadbackBuffer`], and can be used by render world
+/// systems to read back resources that only exist there, such as the textures of a view. The
+/// requests must be made before [`RenderSet::Render`], and the data is read back once the frame
+/// has been submitted. Requests over the [`ReadbackBudget`] are skipped, in which case their
+/// callback is called with [`ReadbackError::Deferred`].
+#[derive(Resource)]
+pub struct GpuReadbacks {
+    requested: Vec<GpuReadback>,
+    /// Unused staging buffers, by size.
+    pool: HashMap<u64, Vec<Buffer>>,
+    completed_tx: Sender<(Entity, u32, Result<Vec<u8>, ReadbackError>)>,
+    /// Staging buffers are sent back here once their data has been copied out.
+    returned_tx: Sender<Buffer>,
+    returned_rx: Receiver<Buffer>,
+    /// The priority of new requests.
+    priority: ReadbackPriority,
+    /// The entity new requests are for.
+    entity: Option<Entity>,
+    /// The keys of the readbacks skipped last frame.
+    skipped: HashSet<ReadbackKey>,
+}
+
+impl GpuReadbacks {
+    /// Requests readbacks in `f` with `priority` instead of [`ReadbackPriority::Normal`].
+    pub fn with_priority<R>(
+        &mut self,
+        priority: ReadbackPriority,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let previous = std::mem::replace(&mut self.priority, priority);
+        let result = f(self);
+        self.priority = previous;
+        result
+    }
+
+    /// Requests readbacks for `entity` in `f`.
+    ///
+    /// When readbacks are skipped for being over the [`ReadbackBudget`], those of the same
+    /// entity go first within their priority the next frame. Readbacks requested outside of this
+    /// are recognized by the texture or buffer they read instead, which may change every frame.
+    pub fn for_entity<R>(&mut self, entity: Entity, f: impl FnOnce(&mut Self) -> R) -> R {
+        let previous = self.entity.replace(entity);
+        let result = f(self);
+        self.entity = previous;
+        result
+    }
+
+    /// Reads back `region` of the first mip level and layer of `texture`, and calls
+    /// `on_complete` with its tightly packed rows, or with the error if it couldn't be read back.
+    ///
+    /// The region is clamped to the size of the texture, and nothing is read back if it is empty.
+    /// The texture must have been created with
+    /// [`TextureUsages::COPY_SRC`](wgpu::TextureUsages::COPY_SRC). `on_complete` is called from
+    /// the render thread when the device is polled, so it should hand the data off quickly, such
+    /// as by sending it through a channel.
+    pub fn read_texture(
+        &mut self,
+        device: &RenderDevice,
+        texture: &Texture,
+        region: URect,
+        aspect: TextureAspect,
+        on_complete: impl FnOnce(Result<Vec<u8>, ReadbackError>) + Send + Sync + 'static,
+    ) -> Result<(), TextureReadbackError> {
+        let format = texture.format();
+        let Some(pixel_size) = format
+            .block_copy_size(Some(aspect))
+            .filter(|_| format.block_dimensions() == (1, 1))
+        else {
+            return Err(TextureReadbackError::UnsupportedFormat(format));
+        };
+        if texture.sample_count() > 1 {
+            return Err(TextureReadbackError::Multisampled);
+        }
+
+        let full = URect::new(0, 0, texture.width(), texture.height());
+        let region = region.intersect(full);
+        if region.is_empty() {
+            return Ok(());
+        }
+        let size = region.size();
+        let source = ReadbackSource::Texture {
+            texture: texture.clone(),
+            aspect,
+            origin: region.min,
+            width: size.x,
+            height: size.y,
+            pixel_size,
+        };
+        let staging_size = get_aligned_size(size.x, size.y, pixel_size);
+        self.request(device, source, staging_size as u64, Box::new(on_complete));
+        Ok(())
+    }
+
+    /// Reads back the whole of `buffer`, and calls `on_complete` with its contents.
+    ///
+    /// The buffer must have been created with [`BufferUsages::COPY_SRC`]. See
+    /// [`read_texture`](Self::read_texture) for when `on_complete` is called.
+    pub fn read_buffer(
+        &mut self,
+        device: &RenderDevice,
+        buffer: &Buffer,
+        on_complete: impl FnOnce(Result<Vec<u8>, ReadbackError>) + Send + Sync + 'static,
+    ) {
+        let source = ReadbackSource::Buffer(buffer.clone());
+        self.request(device, source, buffer.size(), Box::new(on_complete));
+    }
+
+    /// Reads back the value of a [`StorageBuffer`], and calls `on_complete` with it.
+    ///
+    /// Nothing is read back if the buffer hasn't been written to the GPU yet. The buffer needs
+    /// [`BufferUsages::COPY_SRC`], see [`StorageBuffer::add_usages`]. See
+    /// [`read_texture`](Self::read_texture) for when `on_complete` is called.
+    pub fn read_storage_buffer<T: ShaderType + WriteInto + CreateFrom>(
+        &mut self,
+        device: &RenderDevice,
+        buffer: &StorageBuffer<T>,
+        on_complete: impl FnOnce(Result<T, ReadbackError>) + Send + Sync + 'static,
+    ) {
+        let Some(buffer) = buffer.buffer() else {
+            return;
+        };
+        self.read_buffer(device, buffer, move |data| {
+            on_complete(data.and_then(|data| {
+                encase::StorageBuffer::new(&data)
+                    .create()
+                    .map_err(invalid_data::<T>)
+            }));
+        });
+    }
+
+    /// Reads back the elements of a [`BufferVec`], and calls `on_complete` with them.
+    ///
+    /// The elements pushed when this is called are read back, and nothing is read back if the
+    /// buffer hasn't been written to the GPU yet. The buffer needs [`BufferUsages::COPY_SRC`],
+    /// see [`BufferVec::new`]. See [`read_texture`](Self::read_texture) for when `on_complete`
+    /// is called.
+    pub fn read_buffer_vec<T: ShaderType + WriteInto + CreateFrom>(
+        &mut self,
+        device: &RenderDevice,
+        buffer: &BufferVec<T>,
+        on_complete: impl FnOnce(Result<Vec<T>, ReadbackError>) + Send + Sync + 'static,
+    ) {
+        let len = buffer.len();
+        let Some(buffer) = buffer.buffer() else {
+            return;
+        };
+        self.read_buffer(device, buffer, move |data| {
+            on_complete(data.and_then(|data| {
+                // Elements are pushed `min_size` apart, see `BufferVec::push`
+                let element_size = u64::from(T::min_size()) as usize;
+                data.chunks_exact(element_size)
+                    .take(len)
+                    .map(|element| encase::StorageBuffer::new(element).create())
+                    .collect::<Result<_, _>>()
+                    .map_err(invalid_data::<T>)
+            }));
+        });
+    }
+
+    fn request(
+        &mut self,
+        device: &RenderDevice,
+        source: ReadbackSource,
+        size: u64,
+        on_complete: Box<dyn FnOnce(Result<Vec<u8>, ReadbackError>) + Send + Sync>,
+    ) {
+        let staging = self
+            .pool
+            .get_mut(&size)
+            .and_then(Vec::pop)
+            .unwrap_or_else(|| {
+                device.create_buffer(&BufferDescriptor {
+                    label: Some("readback_staging_buffer"),
+                    size,
+                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            });
+        let key = self
+            .entity
+            .map_or_else(|| source.key(), ReadbackKey::Entity);
+        self.requested.push(GpuReadback {
+            source,
+            staging,
+            priority: self.priority,
+            key,
+            on_complete,
+        });
+    }
+
+    /// Sends the data read back for `entity` in `frame` to the main world as a
+    /// [`ReadbackComplete`].
+    fn send_to_main_world(
+        &self,
+        entity: Entity,
+        frame: u32,
+    ) -> impl FnOnce(Result<Vec<u8>, ReadbackError>) + Send + Sync {
+        let completed_tx = self.completed_tx.clone();
+        move |result| {
+            // The main world may have been dropped during shutdown
+            let _ = completed_tx.try_send((entity, frame, result));
+        }
+    }
+}
+
+fn invalid_data<T>(err: impl std::fmt::Display) -> ReadbackError {
+    ReadbackError::InvalidData {
+        type_name: std::any::type_name::<T>(),
+        reason: err.to_string(),
+    }
+}
+
+/// Adds support for reading back values of type `T` into a [`ReadbackValue<T>`] component.
+///
+/// The values are read back in the render world with [`ReadbackValues`].
+pub struct ReadbackValuePlugin<T>(PhantomData<T>);
+
+impl<T> Default for ReadbackValuePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Send + Sync + 'static> Plugin for ReadbackValuePlugin<T> {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = async_channel::unbounded();
+        app.insert_resource(ReadbackValueReceiver::<T>(rx))
+            .add_systems(First, receive_readback_values::<T>);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(ReadbackValueSender::<T>(tx));
+        }
+    }
+}
+
+/// The latest value read back for an entity with [`ReadbackValues`].
+///
+/// This is inserted on the entity when the first value arrives, and replaced by newer values.
+#[derive(Component, Clone, Debug)]
+pub struct ReadbackValue<T: Send + Sync + 'static> {
+    /// The value read back.
+    pub value: T,
+    /// The [`FrameCount`] of the frame the value was read back in.
+    ///
+    /// Values arrive a few frames after they were read back, as the GPU has to finish the frame
+    /// first.
+    pub frame: u32,
+}
+
+/// Reads back [`StorageBuffer`]s and [`BufferVec`]s in the render world, and sends their values to
+/// a [`ReadbackValue`] component in the main world.
+///
+/// The values are sent to the main world entity the render world entity was extracted from.
+/// Needs a [`ReadbackValuePlugin<T>`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{
+/// #     gpu_readback::{ReadbackValue, ReadbackValues},
+/// #     render_resource::BufferVec,
+/// # };
+/// // In the render world
+/// #[derive(Component)]
+/// struct Histogram(BufferVec<u32>);
+///
+/// fn read_back_histograms(
+///     mut readbacks: ReadbackValues<Vec<u32>>,
+///     histograms: Query<(Entity, &Histogram)>,
+/// ) {
+///     for (entity, histogram) in &histograms {
+///         readbacks.read_buffer_vec(entity, &histogram.0);
+///     }
+/// }
+///
+/// // In the main world
+/// fn print_histograms(histograms: Query<&ReadbackValue<Vec<u32>>>) {
+///     for histogram in &histograms {
+///         println!("Histogram of frame {}: {:?}", histogram.frame, histogram.value);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(read_back_histograms);
+/// # bevy_ecs::system::assert_is_system(print_histograms);
+/// ```
+#[derive(SystemParam)]
+pub struct ReadbackValues<'w, T: Send + Sync + 'static> {
+    readbacks: ResMut<'w, GpuReadbacks>,
+    device: Res<'w, RenderDevice>,
+    frame_count: Res<'w, FrameCount>,
+    sender: Res<'w, ReadbackValueSender<T>>,
+}
+
+impl<'w, T: Send + Sync + 'static> ReadbackValues<'w, T> {
+    /// Reads back the value of `buffer` into the [`ReadbackValue`] of `entity`, see
+    /// [`GpuReadbacks::read_storage_buffer`].
+    pub fn read_storage_buffer(&mut self, entity: Entity, buffer: &StorageBuffer<T>)
+    where
+        T: ShaderType + WriteInto + CreateFrom,
+    {
+        let on_complete = self.send_to(entity);
+        let device = &self.device;
+        self.readbacks.for_entity(entity, |readbacks| {
+            readbacks.read_storage_buffer(device, buffer, on_complete);
+        });
+    }
+
+    fn send_to(&self, entity: Entity) -> impl FnOnce(Result<T, ReadbackError>) + Send + Sync {
+        let sender = self.sender.0.clone();
+        let frame = self.frame_count.0;
+        move |result| match result {
+            Ok(value) => {
+                // The main world may have been dropped during shutdown
+                let _ = sender.try_send((entity, frame, value));
+            }
+            // The previous value is kept
+            Err(err @ ReadbackError::Deferred) => {
+                warn_once!("Can't read back the value of {entity:?}: {err}");
+            }
+            Err(err) => warn!("Can't read back the value of {entity:?}: {err}"),
+        }
+    }
+}
+
+impl<'w, T: ShaderType + WriteInto + CreateFrom + Send + Sync> ReadbackValues<'w, Vec<T>> {
+    /// Reads back the elements of `buffer` into the [`ReadbackValue`] of `entity`, see
+    /// [`GpuReadbacks::read_buffer_vec`].
+    pub fn read_buffer_vec(&mut self, entity: Entity, buffer: &BufferVec<T>) {
+        let on_complete = self.send_to(entity);
+        let device = &self.device;
+        self.readbacks.for_entity(entity, |readbacks| {
+            readbacks.read_buffer_vec(device, buffer, on_complete);
+        });
+    }
+}
+
+/// Receives the values read back by [`ReadbackValues`].
+#[derive(Resource)]
+struct ReadbackValueReceiver<T>(Receiver<(Entity, u32, T)>);
+
+/// Sends the values read back by [`ReadbackValues`] to the main world.
+#[derive(Resource)]
+pub struct ReadbackValueSender<T>(Sender<(Entity, u32, T)>);
+
+fn receive_readback_values<T: Send + Sync + 'static>(
+    mut commands: Commands,
+    receiver: Res<ReadbackValueReceiver<T>>,
+    mut values: Query<&mut ReadbackValue<T>>,
+) {
+    while let Ok((entity, frame, value)) = receiver.0.try_recv() {
+        if let Ok(mut current) = values.get_mut(entity) {
+            // Never replace a value with an older one
+            if frame >= current.frame {
+                *current = ReadbackValue { value, frame };
+            }
+        } else if let Some(mut entity) = commands.get_entity(entity) {
+            entity.insert(ReadbackValue { value, frame });
+        }
+    }
+}
+
+fn prepare_readbacks(
+    mut readbacks: ResMut<GpuReadbacks>,
+    device: Res<RenderDevice>,
+    frame_count: Res<FrameCount>,
+    images: Res<RenderAssets<GpuImage>>,
+    textures: Query<(
+        Entity,
+        &ReadbackTexture,
+        Option<&ReadbackRegion>,
+        Option<&ReadbackPriority>,
+    )>,
+    buffers: Query<(Entity, &ReadbackBuffer, Option<&ReadbackPriority>)>,
+) {
+    let readbacks = &mut *readbacks;
+    while let Ok(buffer) = readbacks.returned_rx.try_recv() {
+        readbacks
+            .pool
+            .entry(buffer.size())
+            .or_default()
+            .push(buffer);
+    }
+
+    for (entity, ReadbackTexture(image), region, priority) in &textures {
+        // The image may not be prepared yet
+        let Some(image) = images.get(image) else {
+            continue;
+        };
+        let region = region.map_or(URect::from_corners(UVec2::ZERO, image.size), |r| r.0);
+        let on_complete = readbacks.send_to_main_world(entity, frame_count.0);
+        let priority = priority.copied().unwrap_or_default();
+        let result = readbacks.with_priority(priority, |readbacks| {
+            readbacks.for_entity(entity, |readbacks| {
+                readbacks.read_texture(
+                    &device,
+                    &image.texture,
+                    region,
+                    TextureAspect::All,
+                    on_complete,
+                )
+            })
+        });
+        if let Err(err) = result {
+            warn!("Can't read back {entity:?}: {err}");
+        }
+    }
+
+    for (entity, ReadbackBuffer(buffer), priority) in &buffers {
+        let on_complete = readbacks.send_to_main_world(entity, frame_count.0);
+        let priority = priority.copied().unwrap_or_default();
+        readbacks.with_priority(priority, |readbacks| {
+            readbacks.for_entity(entity, |readbacks| {
+                readbacks.read_buffer(&device, buffer, on_complete);
+            });
+        });
+    }
+}
+
+/// Skips the readbacks over the [`ReadbackBudget`] this frame.
+fn apply_readback_budget(mut readbacks: ResMut<GpuReadbacks>, budget: Res<ReadbackBudget>) {
+    let readbacks = &mut *readbacks;
+    let Some(max_bytes) = budget.max_bytes_per_frame else {
+        readbacks.skipped.clear();
+        return;
+    };
+
+    let skipped = std::mem::take(&mut readbacks.skipped);
+    let (requested, over_budget) = split_by_budget(
+        std::mem::take(&mut readbacks.requested),
+        |readback| {
+            (
+                readback.priority,
+                Some(readback.key),
+                readback.staging.size(),
+            )
+        },
+        &skipped,
+        max_bytes,
+    );
+    readbacks.requested = requested;
+    for readback in over_budget {
+        readbacks.skipped.insert(readback.key);
+        readbacks
+            .pool
+            .entry(readback.staging.size())
+            .or_default()
+            .push(readback.staging);
+        (readback.on_complete)(Err(ReadbackError::Deferred));
+    }
+}
+
+/// Splits `readbacks` into those within `max_bytes` and those over it, given the priority, key
+/// and size of each readback from `key`.
+///
+/// Higher priorities go first, and readbacks with a key in `skipped` go first within a priority.
+/// Once a readback is over budget, all readbacks of lower priorities are too. The first readback
+/// and [`ReadbackPriority::High`] readbacks are never over budget.
+fn split_by_budget<T, K: Eq + Hash>(
+    mut readbacks: Vec<T>,
+    key: impl Fn(&T) -> (ReadbackPriority, Option<K>, u64),
+    skipped: &HashSet<K>,
+    max_bytes: u64,
+) -> (Vec<T>, Vec<T>) {
+    readbacks.sort_by_key(|readback| {
+        let (priority, key, _) = key(readback);
+        let was_skipped = key.is_some_and(|key| skipped.contains(&key));
+        (std::cmp::Reverse(priority), !was_skipped)
+    });
+
+    let mut bytes = 0;
+    // The highest priority with a readback over budget, as the readbacks are sorted
+    let mut over_budget_priority = None;
+    let mut within_budget = Vec::with_capacity(readbacks.len());
+    let mut over_budget = Vec::new();
+    for readback in readbacks {
+        let (priority, _, size) = key(&readback);
+        let fits = bytes == 0 || bytes + size <= max_bytes;
+        let lower_skipped = over_budget_priority.is_some_and(|skipped| priority < skipped);
+        if priority == ReadbackPriority::High || (fits && !lower_skipped) {
+            bytes += size;
+            within_budget.push(readback);
+        } else {
+            over_budget_priority.get_or_insert(priority);
+            over_budget.push(readback);
+        }
+    }
+    (within_budget, over_budget)
+}
+
+/// Copies the requested textures and buffers to their staging buffers.
+pub(crate) fn submit_readback_commands(world: &World, encoder: &mut CommandEncoder) {
+    let Some(readbacks) = world.get_resource::<GpuReadbacks>() else {
+        return;
+    };
+    for readback in &readbacks.requested {
+        match &readback.source {
+            ReadbackSource::Texture {
+                texture,
+                aspect,
+                origin,
+                width,
+                height,
+                pixel_size,
+            } => {
+                encoder.copy_texture_to_buffer(
+                    ImageCopyTexture {
+                        texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: origin.x,
+                            y: origin.y,
+                            z: 0,
+                        },
+                        aspect: *aspect,
+                    },
+                    wgpu::ImageCopyBuffer {
+                        buffer: &readback.staging,
+                        layout: ImageDataLayout {
+                            bytes_per_row: (*height > 1)
+                                .then(|| align_byte_size(width * pixel_size)),
+                            ..Default::default()
+                        },
+                    },
+                    Extent3d {
+                        width: *width,
+                        height: *height,
+                        ..Default::default()
+                    },
+                );
+            }
+            ReadbackSource::Buffer(buffer) => {
+                encoder.copy_buffer_to_buffer(buffer, 0, &readback.staging, 0, buffer.size());
+            }
+        }
+    }
+}
+
+/// Maps the staging buffers of the readbacks submitted this frame.
+///
+/// The data is handed over once the GPU is done with the frame, when the device is polled.
+pub(crate) fn map_readbacks(world: &mut World) {
+    let Some(mut readbacks) = world.get_resource_mut::<GpuReadbacks>() else {
+        return;
+    };
+    let requested = std::mem::take(&mut readbacks.requested);
+    for readback in requested {
+        let GpuReadback {
+            source,
+            staging,
+            on_complete,
+            ..
+        } = readback;
+        // Textures rows are padded to the copy alignment, which is removed again
+        let rows = match source {
+            ReadbackSource::Texture {
+                width,
+                height,
+                pixel_size,
+                ..
+            } => Some((width * pixel_size, height)),
+            ReadbackSource::Buffer(_) => None,
+        };
+        let returned_tx = readbacks.returned_tx.clone();
+        let buffer = staging.clone();
+        staging.slice(..).map_async(MapMode::Read, move |result| {
+            let result = result.map_err(ReadbackError::MapFailed).map(|()| {
+                let mapped = buffer.slice(..).get_mapped_range();
+                let data = match rows {
+                    Some((row_bytes, height)) => unpad_rows(&mapped, row_bytes, height),
+                    None => mapped.to_vec(),
+                };
+                drop(mapped);
+                buffer.unmap();
+                data
+            });
+            // The render world may have been dropped during shutdown
+            let _ = returned_tx.try_send(buffer);
+            on_complete(result);
+        });
+    }
+}
+
+/// Removes the padding at the end of each row of a texture copied to a buffer.
+fn unpad_rows(padded: &[u8], row_bytes: u32, height: u32) -> Vec<u8> {
+    let padded_row_bytes = if height > 1 {
+        padded.len() / height as usize
+    } else {
+        padded.len()
+    };
+    padded
+        .chunks(padded_row_bytes.max(1))
+        .take(height as usize)
+        .flat_map(|row| &row[..row_bytes as usize])
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lays out `rows` the way a texture is copied to a buffer, padding each row with `0xff`.
+    fn pad_rows(rows: &[Vec<u8>]) -> Vec<u8> {
+        let row_bytes = rows[0].len() as u32;
+        let padded_row_bytes = align_byte_size(row_bytes) as usize;
+        let mut padded = Vec::new();
+        for row in rows {
+            padded.extend_from_slice(row);
+            padded.resize(padded.len() + padded_row_bytes - row.len(), 0xff);
+        }
+        assert_eq!(
+            padded.len(),
+            get_aligned_size(row_bytes, rows.len() as u32, 1) as usize
+        );
+        padded
+    }
+
+    #[test]
+    fn unpad_padded_rows() {
+        let rows = vec![vec![1; 12], vec![2; 12]];
+        let padded = pad_rows(&rows);
+
+        assert_eq!(unpad_rows(&padded, 12, 2), rows.concat());
+    }
+
+    #[test]
+    fn unpad_unpadded_rows() {
+        let data: Vec<u8> = (0..24).collect();
+
+        assert_eq!(unpad_rows(&data, 8, 3), data);
+        assert_eq!(unpad_rows(&data, 24, 1), data);
+    }
+
+    #[test]
+    fn unpad_rows_not_multiple_of_alignment() {
+        // 100 RGBA8 pixels are 400 bytes, which is padded to 512
+        let rows: Vec<Vec<u8>> = (0..3).map(|row| vec![row; 400]).collect();
+        let padded = pad_rows(&rows);
+        assert_eq!(padded.len(), 3 * 512);
+
+        assert_eq!(unpad_rows(&padded, 400, 3), rows.concat());
+        // A single row keeps only its pixels
+        assert_eq!(unpad_rows(&padded[..512], 400, 1), rows[0]);
+    }
+
+    #[test]
+    fn readbacks_over_budget_are_deferred() {
+        let [a, b, c, d, e] = [0, 1, 2, 3, 4].map(Entity::from_raw);
+        let readbacks = vec![
+            (ReadbackPriority::Low, Some(a), 40),
+            (ReadbackPriority::Normal, Some(b), 40),
+            (ReadbackPriority::High, Some(c), 80),
+            (ReadbackPriority::Normal, Some(d), 40),
+            (ReadbackPriority::Low, None, 10),
+            (ReadbackPriority::Low, Some(e), 10),
+        ];
+        let key = |readback: &(ReadbackPriority, Option<Entity>, u64)| *readback;
+
+        let (within_budget, over_budget) =
+            split_by_budget(readbacks.clone(), key, &HashSet::default(), 100);
+        // The high priority readback goes first, and no normal priority readback fits after it,
+        // so no low priority readback goes either, even though the small ones would fit
+        assert_eq!(within_budget, vec![(ReadbackPriority::High, Some(c), 80)]);
+        assert_eq!(
+            over_budget,
+            vec![
+                (ReadbackPriority::Normal, Some(b), 40),
+                (ReadbackPriority::Normal, Some(d), 40),
+                (ReadbackPriority::Low, Some(a), 40),
+                (ReadbackPriority::Low, None, 10),
+                (ReadbackPriority::Low, Some(e), 10),
+            ]
+        );
+
+        // Readbacks skipped last frame go first within their priority
+        let skipped = [d, a].into_iter().collect();
+        let (within_budget, _) = split_by_budget(readbacks, key, &skipped, 130);
+        assert_eq!(
+            within_budget,
+            vec![
+                (ReadbackPriority::High, Some(c), 80),
+                (ReadbackPriority::Normal, Some(d), 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn smaller_readbacks_of_the_same_priority_still_fit() {
+        let readbacks = vec![
+            (ReadbackPriority::Normal, None, 90),
+            (ReadbackPriority::Normal, None, 20),
+            (ReadbackPriority::Normal, None, 10),
+        ];
+
+        let (within_budget, over_budget) = split_by_budget(
+            readbacks,
+            |readback| *readback,
+            &HashSet::<Entity>::default(),
+            100,
+        );
+        assert_eq!(
+            within_budget,
+            vec![
+                (ReadbackPriority::Normal, None, 90),
+                (ReadbackPriority::Normal, None, 10),
+            ]
+        );
+        assert_eq!(over_budget, vec![(ReadbackPriority::Normal, None, 20)]);
+    }
+
+    #[test]
+    fn first_readback_is_never_deferred() {
+        let readbacks = vec![
+            (ReadbackPriority::Low, None, 500),
+            (ReadbackPriority::Low, None, 1),
+        ];
+
+        let (within_budget, over_budget) = split_by_budget(
+            readbacks,
+            |readback| *readback,
+            &HashSet::<Entity>::default(),
+            100,
+        );
+        assert_eq!(within_budget, vec![(ReadbackPriority::Low, None, 500)]);
+        assert_eq!(over_budget, vec![(ReadbackPriority::Low, None, 1)]);
+    }
+}
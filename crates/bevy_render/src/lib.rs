@@ -15,12 +15,14 @@ extern crate core;
 pub mod alpha;
 pub mod batching;
 pub mod camera;
+pub mod compute;
 pub mod diagnostic;
 pub mod extract_component;
 pub mod extract_instances;
 mod extract_param;
 pub mod extract_resource;
 pub mod globals;
+pub mod gpu_readback;
 pub mod gpu_component_array_buffer;
 pub mod mesh;
 #[cfg(not(target_arch = "wasm32"))]
@@ -61,6 +63,7 @@ use bevy_hierarchy::ValidParentCheckPlugin;
 use bevy_window::{PrimaryWindow, RawHandleWrapper};
 use extract_resource::ExtractResourcePlugin;
 use globals::GlobalsPlugin;
+use gpu_readback::GpuReadbackPlugin;
 use render_asset::RenderAssetBytesPerFrame;
 use renderer::{RenderAdapter, RenderAdapterInfo, RenderDevice, RenderQueue};
 
@@ -336,6 +339,7 @@ impl Plugin for RenderPlugin {
             ViewPlugin,
             MeshPlugin,
             GlobalsPlugin,
+            GpuReadbackPlugin,
             MorphPlugin,
             BatchingPlugin,
         ));
@@ -21,6 +21,7 @@ use bevy_utils::Instant;
 use std::sync::Arc;
 use wgpu::{
     Adapter, AdapterInfo, CommandBuffer, CommandEncoder, Instance, Queue, RequestAdapterOptions,
+    TextureFormat, TextureFormatFeatureFlags, TextureFormatFeatures, TextureUsages,
 };
 
 /// Updates the [`RenderGraph`] with all of its nodes and then runs it to render the entire frame.
@@ -45,6 +46,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
         world,
         |encoder| {
             crate::view::screenshot::submit_screenshot_commands(world, encoder);
+            crate::gpu_readback::submit_readback_commands(world, encoder);
         },
     );
 
@@ -102,6 +104,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
     }
 
     crate::view::screenshot::collect_screenshots(world);
+    crate::gpu_readback::map_readbacks(world);
 
     // update the time and send it to the app world
     let time_sender = world.resource::<TimeSender>();
@@ -157,6 +160,71 @@ pub struct RenderQueue(pub Arc<WgpuWrapper<Queue>>);
 #[derive(Resource, Clone, Debug, Deref, DerefMut)]
 pub struct RenderAdapter(pub Arc<WgpuWrapper<Adapter>>);
 
+impl RenderAdapter {
+    /// Returns the features of `format` that can be used on `device`.
+    ///
+    /// Unless `device` was created with
+    /// [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`], only the features guaranteed by
+    /// WebGPU can be used, even if the adapter supports more.
+    pub fn texture_format_features(
+        &self,
+        device: &RenderDevice,
+        format: TextureFormat,
+    ) -> TextureFormatFeatures {
+        let features = device.features();
+        if features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+            self.get_texture_format_features(format)
+        } else {
+            format.guaranteed_format_features(features)
+        }
+    }
+
+    /// Returns whether `format` can be used on `device` with all of `usages` and `flags`.
+    pub fn supports_texture_format(
+        &self,
+        device: &RenderDevice,
+        format: TextureFormat,
+        usages: TextureUsages,
+        flags: TextureFormatFeatureFlags,
+    ) -> bool {
+        let format_features = self.texture_format_features(device, format);
+        format_features.allowed_usages.contains(usages) && format_features.flags.contains(flags)
+    }
+
+    /// Returns the first of `formats` that can be used on `device` with all of `usages` and
+    /// `flags`, so that a format can be picked on any backend from a list of preferences.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_render::{
+    /// #     render_resource::{TextureFormat, TextureFormatFeatureFlags, TextureUsages},
+    /// #     renderer::{RenderAdapter, RenderDevice},
+    /// # };
+    /// fn select_id_format(adapter: Res<RenderAdapter>, device: Res<RenderDevice>) {
+    ///     // Fall back to a float format on adapters that can't render to integer textures
+    ///     let format = adapter.select_texture_format(
+    ///         &device,
+    ///         &[TextureFormat::R32Uint, TextureFormat::Rgba16Float],
+    ///         TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+    ///         TextureFormatFeatureFlags::empty(),
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(select_id_format);
+    /// ```
+    pub fn select_texture_format(
+        &self,
+        device: &RenderDevice,
+        formats: &[TextureFormat],
+        usages: TextureUsages,
+        flags: TextureFormatFeatureFlags,
+    ) -> Option<TextureFormat> {
+        formats
+            .iter()
+            .copied()
+            .find(|format| self.supports_texture_format(device, *format, usages, flags))
+    }
+}
+
 /// The GPU instance is used to initialize the [`RenderQueue`] and [`RenderDevice`],
 /// as well as to create [`WindowSurfaces`](crate::view::window::WindowSurfaces).
 #[derive(Resource, Clone, Deref, DerefMut)]
@@ -0,0 +1,340 @@
+//! Record the output of a camera as a sequence of frames.
+
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    thread,
+};
+
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_core::FrameCount;
+use bevy_ecs::prelude::*;
+use bevy_math::URect;
+use bevy_utils::tracing::{error, warn};
+
+use crate::texture::Image;
+
+use super::screenshot::{
+    request_camera_screenshots, send_screenshot_events, CameraScreenshot, ScreenshotCaptured,
+};
+
+/// Adds support for [`FrameRecorder`].
+pub struct FrameRecorderPlugin;
+
+impl Plugin for FrameRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StartRecording>()
+            .add_event::<StopRecording>()
+            .add_systems(
+                PreUpdate,
+                record_captured_frames.after(send_screenshot_events),
+            )
+            .add_systems(
+                PostUpdate,
+                (handle_recording_events, request_recorded_frames)
+                    .chain()
+                    .before(request_camera_screenshots),
+            );
+    }
+}
+
+/// Where a [`FrameRecorder`] writes its frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordingOutput {
+    /// Save each frame as a numbered PNG in a directory, such as `frame_00042.png`.
+    ///
+    /// The directory is created if it does not exist.
+    Png(PathBuf),
+    /// Start a process, such as a video encoder, and write the frames to its standard input.
+    ///
+    /// Each frame is written as raw RGBA pixels with 8 bits per channel, row by row. For example,
+    /// `ffmpeg -f rawvideo -pix_fmt rgba -s 1280x720 -i - recording.mp4` encodes the frames of a
+    /// 1280 by 720 camera. The process is started when the recording starts, and its standard
+    /// input is closed when it stops.
+    Process {
+        /// The program to start.
+        program: String,
+        /// The arguments passed to the program.
+        args: Vec<String>,
+    },
+}
+
+/// Records the output of a camera while started, see [`StartRecording`] and [`StopRecording`].
+///
+/// Frames are captured with [`CameraScreenshot`], so each one is also sent as a
+/// [`ScreenshotCaptured`] event. They are encoded and written on a background thread. If the
+/// GPU or the background thread can't keep up, so that more than
+/// [`max_frames_in_flight`](Self::max_frames_in_flight) frames are waiting, frames are skipped
+/// and counted in [`dropped_frames`](Self::dropped_frames).
+///
+/// Frame recording is not available on WASM.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::Camera, view::window::frame_recorder::*};
+/// fn start_recording(
+///     mut commands: Commands,
+///     camera: Query<Entity, With<Camera>>,
+///     mut start: EventWriter<StartRecording>,
+/// ) {
+///     let camera = camera.single();
+///     let mut recorder = FrameRecorder::new(RecordingOutput::Png("recording".into()));
+///     recorder.every_nth_frame = 2;
+///     commands.entity(camera).insert(recorder);
+///     start.send(StartRecording { camera });
+/// }
+/// # bevy_ecs::system::assert_is_system(start_recording);
+/// ```
+#[derive(Component)]
+pub struct FrameRecorder {
+    /// Where the frames are written.
+    pub output: RecordingOutput,
+    /// Record one in every this many frames, `1` records every frame.
+    pub every_nth_frame: u32,
+    /// The number of frames that can be waiting on the GPU or the background thread before
+    /// frames are skipped.
+    pub max_frames_in_flight: usize,
+    /// The part of the camera's viewport to record, see [`CameraScreenshot::region`].
+    pub region: Option<URect>,
+    recording: Option<Recording>,
+    recorded_frames: u64,
+    dropped_frames: u64,
+}
+
+impl FrameRecorder {
+    /// Creates a recorder that records every frame to `output` once started.
+    pub fn new(output: RecordingOutput) -> Self {
+        Self {
+            output,
+            every_nth_frame: 1,
+            max_frames_in_flight: 8,
+            region: None,
+            recording: None,
+            recorded_frames: 0,
+            dropped_frames: 0,
+        }
+    }
+
+    /// Returns whether the recorder has been started and not stopped since.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// The number of frames handed to the background thread in the current or last recording.
+    pub fn recorded_frames(&self) -> u64 {
+        self.recorded_frames
+    }
+
+    /// The number of frames skipped in the current or last recording, because too many frames
+    /// were in flight.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+}
+
+/// Starts recording on a camera with a [`FrameRecorder`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct StartRecording {
+    /// The camera to record.
+    pub camera: Entity,
+}
+
+/// Stops recording on a camera with a [`FrameRecorder`].
+///
+/// Frames that already arrived from the GPU are still written, while frames that were still
+/// being captured are discarded.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct StopRecording {
+    /// The camera to stop recording.
+    pub camera: Entity,
+}
+
+/// The state of a started [`FrameRecorder`].
+struct Recording {
+    /// The number of frames since the recording started.
+    frame: u64,
+    /// The index of the next frame written.
+    next_index: u64,
+    /// The [`FrameCount`] each frame waiting on the GPU was requested in, with the index it is
+    /// written at, oldest first.
+    captures: VecDeque<(u32, u64)>,
+    /// Sends the captured frames to the background thread.
+    frames: SyncSender<(u64, Image)>,
+}
+
+impl Recording {
+    fn start(output: RecordingOutput, max_frames_in_flight: usize) -> Self {
+        let (frames, receiver) = mpsc::sync_channel(max_frames_in_flight);
+        thread::Builder::new()
+            .name("frame recorder".to_string())
+            .spawn(move || write_frames(output, receiver))
+            .expect("Failed to start the frame recorder thread");
+        Self {
+            frame: 0,
+            next_index: 0,
+            captures: VecDeque::new(),
+            frames,
+        }
+    }
+}
+
+fn handle_recording_events(
+    mut start: EventReader<StartRecording>,
+    mut stop: EventReader<StopRecording>,
+    mut recorders: Query<&mut FrameRecorder>,
+) {
+    for StopRecording { camera } in stop.read() {
+        if let Ok(mut recorder) = recorders.get_mut(*camera) {
+            // Dropping the sender lets the background thread finish
+            recorder.recording = None;
+        }
+    }
+    for StartRecording { camera } in start.read() {
+        let Ok(mut recorder) = recorders.get_mut(*camera) else {
+            warn!("Can't record {camera:?}, as it has no FrameRecorder");
+            continue;
+        };
+        if recorder.is_recording() {
+            continue;
+        }
+        let max_frames_in_flight = recorder.max_frames_in_flight.max(1);
+        recorder.recording = Some(Recording::start(
+            recorder.output.clone(),
+            max_frames_in_flight,
+        ));
+        recorder.recorded_frames = 0;
+        recorder.dropped_frames = 0;
+    }
+}
+
+fn request_recorded_frames(
+    mut commands: Commands,
+    mut recorders: Query<(Entity, &mut FrameRecorder, Has<CameraScreenshot>)>,
+    frame_count: Res<FrameCount>,
+) {
+    for (entity, mut recorder, capture_requested) in &mut recorders {
+        let every_nth_frame = u64::from(recorder.every_nth_frame.max(1));
+        let max_frames_in_flight = recorder.max_frames_in_flight.max(1);
+        let region = recorder.region;
+        let recorder = &mut *recorder;
+        let Some(recording) = &mut recorder.recording else {
+            continue;
+        };
+        let frame = recording.frame;
+        recording.frame += 1;
+        if frame % every_nth_frame != 0 {
+            continue;
+        }
+        // A previous capture of this camera has not been requested yet, or too many are in flight
+        if capture_requested || recording.captures.len() >= max_frames_in_flight {
+            recorder.dropped_frames += 1;
+            continue;
+        }
+        recording
+            .captures
+            .push_back((frame_count.0, recording.next_index));
+        recording.next_index += 1;
+        commands.entity(entity).insert(CameraScreenshot { region });
+    }
+}
+
+fn record_captured_frames(
+    mut screenshots: EventReader<ScreenshotCaptured>,
+    mut recorders: Query<&mut FrameRecorder>,
+) {
+    for screenshot in screenshots.read() {
+        let Ok(mut recorder) = recorders.get_mut(screenshot.entity) else {
+            continue;
+        };
+        let recorder = &mut *recorder;
+        let Some(recording) = &mut recorder.recording else {
+            continue;
+        };
+        // Match the screenshot to the capture requested in the same frame. Window screenshots may
+        // be taken a frame late, and go to the last capture requested before them, while the
+        // captures before that were lost, such as when their readback failed.
+        let mut matched = None;
+        while let Some(&(frame, index)) = recording.captures.front() {
+            if frame > screenshot.frame {
+                break;
+            }
+            recording.captures.pop_front();
+            if matched.replace(index).is_some() {
+                recorder.dropped_frames += 1;
+            }
+        }
+        // Screenshots of this camera that weren't requested by the recorder are ignored
+        let Some(index) = matched else {
+            continue;
+        };
+        match recording.frames.try_send((index, screenshot.image.clone())) {
+            Ok(()) => recorder.recorded_frames += 1,
+            Err(TrySendError::Full(_)) => recorder.dropped_frames += 1,
+            Err(TrySendError::Disconnected(_)) => {
+                // The background thread failed, and has logged why
+                recorder.recording = None;
+            }
+        }
+    }
+}
+
+/// Writes the frames received to `output`, until the recording is stopped.
+fn write_frames(output: RecordingOutput, frames: Receiver<(u64, Image)>) {
+    match output {
+        RecordingOutput::Png(directory) => {
+            if let Err(e) = std::fs::create_dir_all(&directory) {
+                error!("Cannot record frames to {}: {e}", directory.display());
+                return;
+            }
+            for (index, image) in frames {
+                let path = directory.join(format!("frame_{index:05}.png"));
+                match image.try_into_dynamic() {
+                    // Discard the alpha channel, like screenshots do
+                    Ok(image) => {
+                        if let Err(e) = image.to_rgb8().save(&path) {
+                            error!("Cannot save recorded frame, IO error: {e}");
+                        }
+                    }
+                    Err(e) => error!("Cannot save recorded frame: {e}"),
+                }
+            }
+        }
+        RecordingOutput::Process { program, args } => {
+            let mut child = match Command::new(&program)
+                .args(&args)
+                .stdin(Stdio::piped())
+                .spawn()
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    error!("Cannot start {program} to record frames: {e}");
+                    return;
+                }
+            };
+            let Some(mut stdin) = child.stdin.take() else {
+                return;
+            };
+            for (_, image) in frames {
+                let image = match image.try_into_dynamic() {
+                    Ok(image) => image.to_rgba8(),
+                    Err(e) => {
+                        error!("Cannot write recorded frame: {e}");
+                        continue;
+                    }
+                };
+                if let Err(e) = stdin.write_all(image.as_raw()) {
+                    error!("Cannot write recorded frame to {program}: {e}");
+                    break;
+                }
+            }
+            // Closing the standard input tells the process that the recording is done
+            drop(stdin);
+            if let Err(e) = child.wait() {
+                error!("Cannot wait for {program} to finish recording: {e}");
+            }
+        }
+    }
+}
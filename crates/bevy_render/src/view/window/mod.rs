@@ -24,6 +24,8 @@ use wgpu::{
     TextureViewDescriptor,
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod frame_recorder;
 pub mod screenshot;
 
 use screenshot::{
@@ -37,6 +39,8 @@ pub struct WindowRenderPlugin;
 impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(ScreenshotPlugin);
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_plugins(frame_recorder::FrameRecorderPlugin);
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -1,10 +1,17 @@
 use std::{borrow::Cow, path::Path, sync::PoisonError};
 
-use bevy_app::Plugin;
-use bevy_asset::{load_internal_asset, Handle};
+use async_channel::{Receiver, Sender};
+use bevy_app::{Plugin, PostUpdate, PreUpdate};
+use bevy_asset::{load_internal_asset, Assets, Handle};
+use bevy_core::FrameCount;
 use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_math::{URect, UVec2};
 use bevy_tasks::AsyncComputeTaskPool;
-use bevy_utils::tracing::{error, info, info_span};
+use bevy_utils::{
+    tracing::{error, info, info_span, warn},
+    Duration, Instant,
+};
+use bevy_window::PrimaryWindow;
 use std::sync::Mutex;
 use thiserror::Error;
 use wgpu::{
@@ -12,6 +19,11 @@ use wgpu::{
 };
 
 use crate::{
+    camera::{Camera, CameraUpdateSystem, NormalizedRenderTarget},
+    gpu_readback::{
+        ReadbackComplete, ReadbackError, ReadbackFailed, ReadbackPriority, ReadbackRegion,
+        ReadbackTexture,
+    },
     prelude::{Image, Shader},
     render_asset::RenderAssetUsages,
     render_resource::{
@@ -122,13 +134,489 @@ impl ScreenshotManager {
     }
 }
 
+/// Captures the output of a camera once, including cameras rendering to an [`Image`], and sends
+/// it as a [`ScreenshotCaptured`] event.
+///
+/// The component is removed when the capture is requested, and a [`ScreenshotFailed`] event is
+/// sent instead if the capture fails. Cameras rendering to an image read it back with a
+/// [`ReadbackTexture`] on an entity of its own, so the image needs
+/// [`TextureUsages::COPY_SRC`](wgpu::TextureUsages::COPY_SRC). Cameras
+/// rendering to a window use [`ScreenshotManager`], and capture the whole window before it is
+/// cropped to the camera's viewport, so only one of them can be captured per frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::URect;
+/// # use bevy_render::{camera::Camera, view::screenshot::{CameraScreenshot, ScreenshotCaptured}};
+/// fn capture_corner(mut commands: Commands, camera: Query<Entity, With<Camera>>) {
+///     commands.entity(camera.single()).insert(CameraScreenshot {
+///         region: Some(URect::new(0, 0, 64, 64)),
+///     });
+/// }
+///
+/// fn print_screenshots(mut screenshots: EventReader<ScreenshotCaptured>) {
+///     for screenshot in screenshots.read() {
+///         println!("{:?} captured {:?}", screenshot.entity, screenshot.image.size());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(capture_corner);
+/// # bevy_ecs::system::assert_is_system(print_screenshots);
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct CameraScreenshot {
+    /// The part of the camera's viewport to capture, in physical pixels relative to the top left
+    /// of the viewport, or the whole viewport if `None`.
+    pub region: Option<URect>,
+}
+
+/// Sent when a [`CameraScreenshot`] has been captured.
+#[derive(Event, Clone, Debug)]
+pub struct ScreenshotCaptured {
+    /// The camera the screenshot was taken of.
+    pub entity: Entity,
+    /// The [`FrameCount`] of the frame the [`CameraScreenshot`] was requested in.
+    ///
+    /// The image shows the first frame rendered after the request, which is usually the frame it
+    /// was requested in.
+    pub frame: u32,
+    /// The captured pixels.
+    pub image: Image,
+}
+
+/// Sent instead of a [`ScreenshotCaptured`] when a [`CameraScreenshot`] couldn't be captured.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ScreenshotFailed {
+    /// The camera the screenshot was to be taken of.
+    pub entity: Entity,
+    /// The [`FrameCount`] of the frame the [`CameraScreenshot`] was requested in.
+    pub frame: u32,
+}
+
+/// Reads back the image a camera renders to for its [`CameraScreenshot`]s.
+///
+/// This is on an entity of its own, so that any [`ReadbackTexture`] of the camera itself is left
+/// alone.
+#[derive(Component)]
+pub(crate) struct CameraScreenshotReadback {
+    /// The camera the screenshots are taken of.
+    camera: Entity,
+    /// The [`FrameCount`] of the frame each screenshot was requested in, oldest first.
+    frames: Vec<u32>,
+}
+
+/// Receives the window screenshots taken for a [`CameraScreenshot`], or `None` for those that
+/// couldn't be taken.
+#[derive(Resource)]
+pub(crate) struct CapturedWindowScreenshots {
+    tx: Sender<(Entity, u32, Option<Image>)>,
+    rx: Receiver<(Entity, u32, Option<Image>)>,
+}
+
+/// Sends the window screenshot taken for a [`CameraScreenshot`] to [`CapturedWindowScreenshots`].
+///
+/// If it is dropped without sending, such as when the window is closed before the screenshot is
+/// taken, it sends that the screenshot couldn't be taken instead.
+struct WindowScreenshotSender {
+    tx: Sender<(Entity, u32, Option<Image>)>,
+    camera: Entity,
+    frame: u32,
+    region: URect,
+    sent: bool,
+}
+
+impl WindowScreenshotSender {
+    fn send(mut self, image: &Image) {
+        let image = crop_image(image, self.region);
+        // The main world may have been dropped during shutdown
+        let _ = self.tx.try_send((self.camera, self.frame, Some(image)));
+        self.sent = true;
+    }
+}
+
+impl Drop for WindowScreenshotSender {
+    fn drop(&mut self) {
+        if !self.sent {
+            let _ = self.tx.try_send((self.camera, self.frame, None));
+        }
+    }
+}
+
+pub(crate) fn request_camera_screenshots(
+    mut commands: Commands,
+    cameras: Query<(Entity, &Camera, &CameraScreenshot)>,
+    mut readbacks: Query<(Entity, &mut CameraScreenshotReadback)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    captured: Res<CapturedWindowScreenshots>,
+    frame_count: Res<FrameCount>,
+    mut failed: EventWriter<ScreenshotFailed>,
+) {
+    let primary_window = primary_window.get_single().ok();
+    let frame = frame_count.0;
+    for (entity, camera, screenshot) in &cameras {
+        // The target is not known until the camera has been updated once
+        let Some(viewport) = camera.physical_viewport_rect() else {
+            continue;
+        };
+        let region = screenshot.region.map_or(viewport, |region| {
+            URect::from_corners(viewport.min + region.min, viewport.min + region.max)
+                .intersect(viewport)
+        });
+
+        match camera.target.normalize(primary_window) {
+            Some(NormalizedRenderTarget::Window(window)) => {
+                // Try again next frame if the window is already being captured
+                let callbacks = screenshot_manager
+                    .callbacks
+                    .get_mut()
+                    .unwrap_or_else(PoisonError::into_inner);
+                if callbacks.contains_key(&window.entity()) {
+                    continue;
+                }
+                let sender = WindowScreenshotSender {
+                    tx: captured.tx.clone(),
+                    camera: entity,
+                    frame,
+                    region,
+                    sent: false,
+                };
+                // This can't fail, as no screenshot of the window was requested yet
+                let _ = screenshot_manager
+                    .take_screenshot(window.entity(), move |image| sender.send(&image));
+            }
+            Some(NormalizedRenderTarget::Image(image)) => {
+                let readback = (
+                    ReadbackTexture(image),
+                    ReadbackRegion(region),
+                    ReadbackPriority::High,
+                );
+                // Earlier screenshots may still be waiting for their readback
+                let pending = readbacks
+                    .iter_mut()
+                    .find(|(_, pending)| pending.camera == entity);
+                match pending {
+                    Some((readback_entity, mut pending)) => {
+                        pending.frames.push(frame);
+                        commands.entity(readback_entity).insert(readback);
+                    }
+                    None => {
+                        commands.spawn((
+                            readback,
+                            CameraScreenshotReadback {
+                                camera: entity,
+                                frames: vec![frame],
+                            },
+                        ));
+                    }
+                }
+            }
+            _ => {
+                warn!("Can't capture {entity:?}, as its render target is not supported");
+                failed.send(ScreenshotFailed { entity, frame });
+            }
+        }
+        commands.entity(entity).remove::<CameraScreenshot>();
+    }
+}
+
+pub(crate) fn send_screenshot_events(
+    mut commands: Commands,
+    captured: Res<CapturedWindowScreenshots>,
+    mut readbacks: EventReader<ReadbackComplete>,
+    mut failed: EventReader<ReadbackFailed>,
+    mut pending: Query<(
+        Entity,
+        &ReadbackTexture,
+        &ReadbackRegion,
+        &mut CameraScreenshotReadback,
+    )>,
+    images: Res<Assets<Image>>,
+    mut events: EventWriter<ScreenshotCaptured>,
+    mut failed_events: EventWriter<ScreenshotFailed>,
+) {
+    while let Ok((entity, frame, image)) = captured.rx.try_recv() {
+        match image {
+            Some(image) => {
+                events.send(ScreenshotCaptured {
+                    entity,
+                    frame,
+                    image,
+                });
+            }
+            None => {
+                warn!("Can't capture {entity:?}, as its window wasn't captured");
+                failed_events.send(ScreenshotFailed { entity, frame });
+            }
+        }
+    }
+
+    // A readback is of the frame before its `FrameCount`, so it captures the screenshots requested
+    // in earlier frames, while screenshots requested since are still waiting for a later one
+    for failed in failed.read() {
+        // Readbacks over budget are requested again next frame
+        if failed.error == ReadbackError::Deferred {
+            continue;
+        }
+        let Ok((.., mut pending)) = pending.get_mut(failed.entity) else {
+            continue;
+        };
+        let lost = pending
+            .frames
+            .iter()
+            .filter(|&&frame| frame < failed.frame)
+            .count();
+        if lost == 0 {
+            continue;
+        }
+        let camera = pending.camera;
+        warn!("Can't capture {camera:?}: {}", failed.error);
+        failed_events.send_batch(pending.frames.drain(..lost).map(|frame| ScreenshotFailed {
+            entity: camera,
+            frame,
+        }));
+        if pending.frames.is_empty() {
+            commands.entity(failed.entity).despawn();
+        }
+    }
+
+    for readback in readbacks.read() {
+        let Ok((_, ReadbackTexture(image), ReadbackRegion(region), mut pending)) =
+            pending.get_mut(readback.entity)
+        else {
+            continue;
+        };
+        // Readbacks of screenshots that were already captured may still be in flight, and are
+        // ignored
+        let captured = pending
+            .frames
+            .iter()
+            .filter(|&&frame| frame < readback.frame)
+            .count();
+        if captured == 0 {
+            continue;
+        }
+        let camera = pending.camera;
+        let frames: Vec<u32> = pending.frames.drain(..captured).collect();
+        if pending.frames.is_empty() {
+            commands.entity(readback.entity).despawn();
+        }
+        let size = region.size();
+        let format = images
+            .get(image)
+            .map(|image| image.texture_descriptor.format)
+            // The region may have changed since the data was read back
+            .filter(|format| {
+                readback.data.len() == size.x as usize * size.y as usize * format.pixel_size()
+            });
+        let Some(format) = format else {
+            warn!("Can't capture {camera:?}, as its image or region changed");
+            failed_events.send_batch(frames.into_iter().map(|frame| ScreenshotFailed {
+                entity: camera,
+                frame,
+            }));
+            continue;
+        };
+        let image = Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            wgpu::TextureDimension::D2,
+            readback.data.clone(),
+            format,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        for frame in frames {
+            events.send(ScreenshotCaptured {
+                entity: camera,
+                frame,
+                image: image.clone(),
+            });
+        }
+    }
+
+    // The image of a camera may be removed before it is read back, and then never is
+    for (entity, ReadbackTexture(image), _, mut pending) in &mut pending {
+        if pending.frames.is_empty() || images.contains(image) {
+            continue;
+        }
+        let camera = pending.camera;
+        warn!("Can't capture {camera:?}, as its image was removed");
+        failed_events.send_batch(pending.frames.drain(..).map(|frame| ScreenshotFailed {
+            entity: camera,
+            frame,
+        }));
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Copies the pixels of `region` out of `image`.
+fn crop_image(image: &Image, region: URect) -> Image {
+    let format = image.texture_descriptor.format;
+    let pixel_size = format.pixel_size();
+    let width = image.width() as usize;
+    let region = region.intersect(URect::from_corners(UVec2::ZERO, image.size()));
+    let size = region.size();
+
+    let mut data = Vec::with_capacity(size.x as usize * size.y as usize * pixel_size);
+    for y in region.min.y..region.max.y {
+        let start = (y as usize * width + region.min.x as usize) * pixel_size;
+        data.extend_from_slice(&image.data[start..start + size.x as usize * pixel_size]);
+    }
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        wgpu::TextureDimension::D2,
+        data,
+        format,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Keeps an [`Image`] asset updated with the output of a camera, for use on the CPU.
+///
+/// The camera is captured like with [`CameraScreenshot`], at most once per
+/// [`interval`](Self::interval), and each capture is also sent as a [`ScreenshotCaptured`]
+/// event. The mirrored image is only kept in the main world, so it can be read on the CPU but is
+/// not uploaded back to the GPU.
+///
+/// ```
+/// # use bevy_asset::{Assets, Handle};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::Camera, texture::Image, view::screenshot::MirrorToCpu};
+/// # use bevy_utils::Duration;
+/// #[derive(Resource)]
+/// struct Preview(Handle<Image>);
+///
+/// fn mirror_camera(
+///     mut commands: Commands,
+///     camera: Query<Entity, With<Camera>>,
+///     preview: Res<Preview>,
+/// ) {
+///     commands.entity(camera.single()).insert(MirrorToCpu {
+///         interval: Duration::from_millis(100),
+///         ..MirrorToCpu::new(preview.0.clone())
+///     });
+/// }
+///
+/// fn read_preview(images: Res<Assets<Image>>, preview: Res<Preview>) {
+///     if let Some(image) = images.get(&preview.0) {
+///         println!("The preview is {:?}", image.size());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(mirror_camera);
+/// # bevy_ecs::system::assert_is_system(read_preview);
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct MirrorToCpu {
+    /// The image that is updated with each capture.
+    pub image: Handle<Image>,
+    /// The shortest time between two captures, or zero to capture as often as possible.
+    ///
+    /// A new capture is only requested once the previous one has arrived, or has failed with a
+    /// [`ScreenshotFailed`].
+    pub interval: Duration,
+    /// The part of the camera's viewport to capture, see [`CameraScreenshot::region`].
+    pub region: Option<URect>,
+}
+
+impl MirrorToCpu {
+    /// Mirrors the whole viewport of the camera to `image`, as often as possible.
+    pub fn new(image: Handle<Image>) -> Self {
+        Self {
+            image,
+            interval: Duration::ZERO,
+            region: None,
+        }
+    }
+}
+
+/// When a [`MirrorToCpu`] was last captured, and whether that capture is still in flight.
+#[derive(Component)]
+struct MirrorToCpuState {
+    last_capture: Instant,
+    pending: bool,
+}
+
+fn request_mirror_captures(
+    mut commands: Commands,
+    mut mirrors: Query<
+        (Entity, &MirrorToCpu, Option<&mut MirrorToCpuState>),
+        Without<CameraScreenshot>,
+    >,
+) {
+    let now = Instant::now();
+    for (entity, mirror, state) in &mut mirrors {
+        match state {
+            Some(mut state) => {
+                if state.pending || now.duration_since(state.last_capture) < mirror.interval {
+                    continue;
+                }
+                state.last_capture = now;
+                state.pending = true;
+            }
+            None => {
+                commands.entity(entity).insert(MirrorToCpuState {
+                    last_capture: now,
+                    pending: true,
+                });
+            }
+        }
+        commands.entity(entity).insert(CameraScreenshot {
+            region: mirror.region,
+        });
+    }
+}
+
+fn update_mirrored_images(
+    mut screenshots: EventReader<ScreenshotCaptured>,
+    mut failed: EventReader<ScreenshotFailed>,
+    mut mirrors: Query<(&MirrorToCpu, &mut MirrorToCpuState)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // Capture again after the next interval
+    for failed in failed.read() {
+        if let Ok((_, mut state)) = mirrors.get_mut(failed.entity) {
+            state.pending = false;
+        }
+    }
+
+    for screenshot in screenshots.read() {
+        let Ok((mirror, mut state)) = mirrors.get_mut(screenshot.entity) else {
+            continue;
+        };
+        state.pending = false;
+        let mut image = screenshot.image.clone();
+        image.asset_usage = RenderAssetUsages::MAIN_WORLD;
+        images.insert(&mirror.image, image);
+    }
+}
+
 pub struct ScreenshotPlugin;
 
 const SCREENSHOT_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(11918575842344596158);
 
 impl Plugin for ScreenshotPlugin {
     fn build(&self, app: &mut bevy_app::App) {
-        app.init_resource::<ScreenshotManager>();
+        let (tx, rx) = async_channel::unbounded();
+        app.init_resource::<ScreenshotManager>()
+            .insert_resource(CapturedWindowScreenshots { tx, rx })
+            .add_event::<ScreenshotCaptured>()
+            .add_event::<ScreenshotFailed>()
+            .add_systems(
+                PreUpdate,
+                (send_screenshot_events, update_mirrored_images).chain(),
+            )
+            .add_systems(
+                PostUpdate,
+                (request_mirror_captures, request_camera_screenshots)
+                    .chain()
+                    .after(CameraUpdateSystem),
+            );
 
         load_internal_asset!(
             app,
@@ -9,6 +9,7 @@ use bevy_ecs::{
 use bevy_math::FloatOrd;
 use bevy_render::{
     camera::ExtractedCamera,
+    diagnostic::RecordDiagnostics,
     render_graph::*,
     render_phase::*,
     render_resource::{CachedRenderPipelineId, RenderPassDescriptor},
@@ -70,6 +71,7 @@ impl Node for UiPassNode {
         } else {
             input_view_entity
         };
+        let diagnostics = render_context.diagnostic_recorder();
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
             label: Some("ui_pass"),
             color_attachments: &[Some(target.get_unsampled_color_attachment())],
@@ -77,10 +79,12 @@ impl Node for UiPassNode {
             timestamp_writes: None,
             occlusion_query_set: None,
         });
+        let pass_span = diagnostics.pass_span(&mut render_pass, "ui_pass");
         if let Some(viewport) = camera.viewport.as_ref() {
             render_pass.set_camera_viewport(viewport);
         }
         transparent_phase.render(&mut render_pass, world, view_entity);
+        pass_span.end(&mut render_pass);
 
         Ok(())
     }
@@ -1,13 +1,7 @@
-#import bevy_render::{
-    view::View,
-    globals::Globals,
+#import bevy_ui::{
+    ui_material_bindings::view,
+    ui_vertex_output::UiVertexOutput,
 }
-#import bevy_ui::ui_vertex_output::UiVertexOutput
-
-@group(0) @binding(0)
-var<uniform> view: View;
-@group(0) @binding(1)
-var<uniform> globals: Globals;
 
 @vertex
 fn vertex(
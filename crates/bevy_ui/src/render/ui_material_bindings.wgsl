@@ -0,0 +1,15 @@
+#define_import_path bevy_ui::ui_material_bindings
+
+#import bevy_render::{
+    view::View,
+    globals::Globals,
+}
+
+// The view the UI is rendered to. Its viewport holds the resolution of the render target in
+// pixels, as `view.viewport.zw`.
+@group(0) @binding(0)
+var<uniform> view: View;
+// The time since startup, the delta time and the frame count, for animating UI materials without
+// changing them on the CPU every frame.
+@group(0) @binding(1)
+var<uniform> globals: Globals;
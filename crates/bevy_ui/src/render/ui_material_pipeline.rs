@@ -30,6 +30,9 @@ pub const UI_MATERIAL_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(100
 
 const UI_VERTEX_OUTPUT_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(10123618247720234751);
 
+const UI_MATERIAL_BINDINGS_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(15830459134812739571);
+
 /// Adds the necessary ECS resources and render logic to enable rendering entities using the given
 /// [`UiMaterial`] asset type (which includes [`UiMaterial`] types).
 pub struct UiMaterialPlugin<M: UiMaterial>(PhantomData<M>);
@@ -51,6 +54,12 @@ where
             "ui_vertex_output.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            UI_MATERIAL_BINDINGS_SHADER_HANDLE,
+            "ui_material_bindings.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(
             app,
             UI_MATERIAL_SHADER_HANDLE,
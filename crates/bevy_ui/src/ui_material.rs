@@ -69,10 +69,16 @@ use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, Shader
 /// If you only use the fragment shader make sure to import `UiVertexOutput` from
 /// `bevy_ui::ui_vertex_output` in your wgsl shader.
 /// Also note that bind group 0 is always bound to the [`View Uniform`](bevy_render::view::ViewUniform)
-/// and the [`Globals Uniform`](bevy_render::globals::GlobalsUniform).
+/// and the [`Globals Uniform`](bevy_render::globals::GlobalsUniform), which can be imported from
+/// `bevy_ui::ui_material_bindings`. They can be used to animate a material without changing it
+/// every frame: `globals.time` and `globals.delta_time` hold the elapsed and delta time in
+/// seconds, and `view.viewport.zw` holds the resolution of the render target in pixels.
 ///
 /// ```wgsl
-/// #import bevy_ui::ui_vertex_output UiVertexOutput
+/// #import bevy_ui::{
+///     ui_material_bindings::globals,
+///     ui_vertex_output::UiVertexOutput,
+/// }
 ///
 /// struct CustomMaterial {
 ///     color: vec4<f32>,
@@ -87,7 +93,9 @@ use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, Shader
 ///
 /// @fragment
 /// fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
-///
+///     // Pulse the color once a second
+///     let pulse = 0.75 + 0.25 * sin(globals.time * 6.2831855);
+///     return vec4<f32>(material.color.rgb * pulse, material.color.a);
 /// }
 /// ```
 pub trait UiMaterial: AsBindGroup + Asset + Clone + Sized {
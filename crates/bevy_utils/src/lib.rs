@@ -31,6 +31,8 @@ pub use bevy_utils_proc_macros::*;
 pub use cow_arc::*;
 pub use default::default;
 pub use hashbrown;
+#[doc(hidden)]
+pub use once::Throttle;
 pub use parallel_queue::*;
 pub use tracing;
 pub use web_time::{Duration, Instant, SystemTime, SystemTimeError, TryFromFloatSecsError};
@@ -60,3 +60,122 @@ macro_rules! error_once {
         $crate::once!($crate::tracing::error!($($arg)+))
     });
 }
+
+/// Tracks when a call site of [`throttled!`](crate::throttled) last fired.
+#[doc(hidden)]
+pub struct Throttle {
+    /// Nanoseconds since [`Throttle::epoch`] when the call site last fired, or `u64::MAX` if it
+    /// never fired.
+    last_fired: std::sync::atomic::AtomicU64,
+}
+
+impl Throttle {
+    #[doc(hidden)]
+    pub const fn new() -> Self {
+        Self {
+            last_fired: std::sync::atomic::AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// Returns `true` if at least `1 / hz` seconds passed since this last returned `true`.
+    #[doc(hidden)]
+    pub fn ready(&self, hz: f64) -> bool {
+        use std::sync::atomic::Ordering;
+
+        let now = Self::epoch().elapsed().as_nanos() as u64;
+        let last = self.last_fired.load(Ordering::Relaxed);
+        if last != u64::MAX && (now.saturating_sub(last) as f64) < 1e9 / hz {
+            return false;
+        }
+        // Another thread may fire from the same call site at the same time, only one of them wins
+        self.last_fired
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    fn epoch() -> crate::Instant {
+        static EPOCH: std::sync::OnceLock<crate::Instant> = std::sync::OnceLock::new();
+        *EPOCH.get_or_init(crate::Instant::now)
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Call some expression at most `hz` times per second per call site.
+///
+/// The first call always fires, and calls in between are skipped.
+#[macro_export]
+macro_rules! throttled {
+    ($hz:expr, $expression:expr) => {{
+        static THROTTLE: $crate::Throttle = $crate::Throttle::new();
+        if THROTTLE.ready($hz as f64) {
+            $expression;
+        }
+    }};
+}
+
+/// Call [`trace!`](crate::tracing::trace) at most `hz` times per second per call site.
+///
+/// Useful for logging within systems which are called every frame, when the message should keep
+/// being reported while the condition lasts.
+#[macro_export]
+macro_rules! trace_throttled {
+    ($hz:expr, $($arg:tt)+) => ({
+        $crate::throttled!($hz, $crate::tracing::trace!($($arg)+))
+    });
+}
+
+/// Call [`debug!`](crate::tracing::debug) at most `hz` times per second per call site.
+///
+/// Useful for logging within systems which are called every frame, when the message should keep
+/// being reported while the condition lasts.
+#[macro_export]
+macro_rules! debug_throttled {
+    ($hz:expr, $($arg:tt)+) => ({
+        $crate::throttled!($hz, $crate::tracing::debug!($($arg)+))
+    });
+}
+
+/// Call [`info!`](crate::tracing::info) at most `hz` times per second per call site.
+///
+/// Useful for logging within systems which are called every frame, when the message should keep
+/// being reported while the condition lasts.
+#[macro_export]
+macro_rules! info_throttled {
+    ($hz:expr, $($arg:tt)+) => ({
+        $crate::throttled!($hz, $crate::tracing::info!($($arg)+))
+    });
+}
+
+/// Call [`warn!`](crate::tracing::warn) at most `hz` times per second per call site.
+///
+/// Useful for logging within systems which are called every frame, when the message should keep
+/// being reported while the condition lasts.
+///
+/// ```
+/// # use bevy_utils::warn_throttled;
+/// # let missing_readbacks = 3;
+/// // Logged at most twice per second, even when called every frame
+/// warn_throttled!(2.0, "{missing_readbacks} readbacks were not ready in time");
+/// ```
+#[macro_export]
+macro_rules! warn_throttled {
+    ($hz:expr, $($arg:tt)+) => ({
+        $crate::throttled!($hz, $crate::tracing::warn!($($arg)+))
+    });
+}
+
+/// Call [`error!`](crate::tracing::error) at most `hz` times per second per call site.
+///
+/// Useful for logging within systems which are called every frame, when the message should keep
+/// being reported while the condition lasts.
+#[macro_export]
+macro_rules! error_throttled {
+    ($hz:expr, $($arg:tt)+) => ({
+        $crate::throttled!($hz, $crate::tracing::error!($($arg)+))
+    });
+}
@@ -2,9 +2,6 @@
 
 // Note: This example showcases the meshlet API, but is not the type of scene that would benefit from using meshlets.
 
-#[path = "../helpers/camera_controller.rs"]
-mod camera_controller;
-
 use bevy::{
     pbr::{
         experimental::meshlet::{MaterialMeshletMeshBundle, MeshletPlugin},
@@ -13,7 +10,6 @@ use bevy::{
     prelude::*,
     render::render_resource::AsBindGroup,
 };
-use camera_controller::{CameraController, CameraControllerPlugin};
 use std::{f32::consts::PI, path::Path, process::ExitCode};
 
 const ASSET_URL: &str = "https://raw.githubusercontent.com/JMS55/bevy_meshlet_asset/bd869887bc5c9c6e74e353f657d342bef84bacd8/bunny.meshlet_mesh";
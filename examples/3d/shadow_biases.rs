@@ -1,10 +1,6 @@
 //! Demonstrates how shadow biases affect shadows in a 3d scene.
 
-#[path = "../helpers/camera_controller.rs"]
-mod camera_controller;
-
 use bevy::{pbr::ShadowFilteringMethod, prelude::*};
-use camera_controller::{CameraController, CameraControllerPlugin};
 
 fn main() {
     App::new()
@@ -1,8 +1,5 @@
 //! Load a cubemap texture onto a cube like a skybox and cycle through different compressed texture formats
 
-#[path = "../helpers/camera_controller.rs"]
-mod camera_controller;
-
 use bevy::{
     asset::LoadState,
     core_pipeline::Skybox,
@@ -13,7 +10,6 @@ use bevy::{
         texture::CompressedImageFormats,
     },
 };
-use camera_controller::{CameraController, CameraControllerPlugin};
 use std::f32::consts::PI;
 
 const CUBEMAPS: &[(&str, CompressedImageFormats)] = &[
@@ -10,6 +10,10 @@
 //!
 //! Finally, after all that we can access the `LogEvent` event from our systems and use it.
 //! In this example we build a simple log viewer.
+//!
+//! If you only need the level, target and message of each record, setting
+//! [`LogPlugin::log_events`](bevy::log::LogPlugin::log_events) sends them as
+//! [`bevy::log::LogEvent`]s without a custom layer.
 
 use std::sync::mpsc;
 
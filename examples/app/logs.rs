@@ -1,6 +1,6 @@
 //! This example illustrates how to use logs in bevy.
 
-use bevy::log::once;
+use bevy::log::{once, throttled};
 use bevy::prelude::*;
 
 fn main() {
@@ -9,11 +9,16 @@ fn main() {
             // Uncomment this to override the default log settings:
             // level: bevy::log::Level::TRACE,
             // filter: "wgpu=warn,bevy_ecs=info".to_string(),
+            // Uncomment this to also write the logs to daily files in the `logs` directory:
+            // file: Some(bevy::log::FileLogSettings::new("logs")),
+            // Uncomment this to write the logs as newline-delimited JSON:
+            // format: bevy::log::LogFormat::Json,
             ..default()
         }))
         .add_systems(Startup, setup)
         .add_systems(Update, log_system)
         .add_systems(Update, log_once_system)
+        .add_systems(Update, log_throttled_system)
         .add_systems(Update, panic_on_p)
         .run();
 }
@@ -81,3 +86,19 @@ fn log_once_system() {
         info!("result of some expensive one time calculation: {}", a);
     });
 }
+
+fn log_throttled_system(time: Res<Time>) {
+    // The 'throttled' variants of each log level are useful when a system is called every frame,
+    // and the user should keep being informed, just not at the frame rate.
+    // The first argument is the maximum number of messages per second for the call site.
+
+    info_throttled!(
+        1.0,
+        "printed at most once per second: {:.1}s",
+        time.elapsed_seconds()
+    );
+    warn_throttled!(0.2, "some warning we wish to repeat every five seconds");
+
+    // you can also use the `throttled!` macro directly
+    throttled!(0.5, info!("doing something every other second"));
+}
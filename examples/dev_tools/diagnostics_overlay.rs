@@ -0,0 +1,84 @@
+//! Showcase how to use and configure the diagnostics overlay.
+
+use bevy::{
+    dev_tools::diagnostics_overlay::{DiagnosticsOverlayConfig, DiagnosticsOverlayPlugin},
+    diagnostic::SystemInformationDiagnosticsPlugin,
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            // Any diagnostic can be shown, as long as the plugin measuring it is added
+            SystemInformationDiagnosticsPlugin,
+            DiagnosticsOverlayPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (spawn_sprites, customize_config))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // We need to spawn a camera (2d or 3d) to see the overlay
+    commands.spawn(Camera2dBundle::default());
+
+    // Instruction text
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|c| {
+            c.spawn(TextBundle::from_section(
+                concat!(
+                    "Press F2 to toggle the overlay.\n",
+                    "Press 1 to show the CPU and memory usage.\n",
+                    "Press 2 to change the color of the plots.\n",
+                    "Hold Space to spawn entities."
+                ),
+                TextStyle {
+                    font_size: 25.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_sprites(mut commands: Commands, keyboard: Res<ButtonInput<KeyCode>>) {
+    if keyboard.pressed(KeyCode::Space) {
+        // The entity count plot grows while space is held
+        commands.spawn_batch((0..100).map(|_| SpatialBundle::default()));
+    }
+}
+
+fn customize_config(
+    input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DiagnosticsOverlayConfig>,
+) {
+    if input.just_pressed(KeyCode::Digit1) {
+        // Rows can be added and removed at any time
+        let cpu = SystemInformationDiagnosticsPlugin::CPU_USAGE;
+        let mem = SystemInformationDiagnosticsPlugin::MEM_USAGE;
+        if overlay.diagnostics.contains(&cpu) {
+            overlay
+                .diagnostics
+                .retain(|path| *path != cpu && *path != mem);
+        } else {
+            overlay.diagnostics.extend([cpu, mem]);
+        }
+    }
+    if input.just_pressed(KeyCode::Digit2) {
+        overlay.plot_color = if overlay.plot_color == Color::srgb(1.0, 0.6, 0.2) {
+            DiagnosticsOverlayConfig::default().plot_color
+        } else {
+            Color::srgb(1.0, 0.6, 0.2)
+        };
+    }
+}
@@ -0,0 +1,67 @@
+//! Showcase how to use the frame time breakdown overlay.
+//!
+//! Run with `--features bevy/trace` to also see the time taken by each system of the engine.
+
+use bevy::{
+    dev_tools::frame_time_overlay::FrameTimeOverlayPlugin, log::LogPlugin, prelude::*,
+    utils::Duration,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins.set(LogPlugin {
+                // Measure the time spent in each span for the overlay
+                span_timings: true,
+                ..default()
+            }),
+            FrameTimeOverlayPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (steady_work, occasional_spike))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // We need to spawn a camera (2d or 3d) to see the overlay
+    commands.spawn(Camera2dBundle::default());
+
+    // Instruction text
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|c| {
+            c.spawn(TextBundle::from_section(
+                "Press F3 to toggle the frame time overlay.",
+                TextStyle {
+                    font_size: 25.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn steady_work() {
+    // Any span shows up in the overlay, not only systems
+    let _span = info_span!("steady_work").entered();
+    std::thread::sleep(Duration::from_millis(2));
+}
+
+fn occasional_spike(time: Res<Time>, mut timer: Local<Timer>) {
+    if timer.duration().is_zero() {
+        *timer = Timer::from_seconds(2.0, TimerMode::Repeating);
+    }
+    if timer.tick(time.delta()).just_finished() {
+        // The peak stays visible for a second, while the smoothed time barely moves
+        let _span = info_span!("occasional_spike").entered();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
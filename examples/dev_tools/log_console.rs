@@ -0,0 +1,83 @@
+//! Showcase how to use the in-app log console.
+
+use bevy::{
+    dev_tools::log_console::{LogConsole, LogConsolePlugin},
+    log::LogPlugin,
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins.set(LogPlugin {
+                // The console shows the records kept here, so keep plenty to scroll back through
+                recent_records: 2000,
+                level: bevy::log::Level::DEBUG,
+                ..default()
+            }),
+            LogConsolePlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (log_system, filter_console))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // We need to spawn a camera (2d or 3d) to see the console
+    commands.spawn(Camera2dBundle::default());
+
+    // Instruction text
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|c| {
+            c.spawn(TextBundle::from_section(
+                concat!(
+                    "Press ` to open the log console.\n",
+                    "Type to search, press Tab to change the level shown\n",
+                    "and scroll with PageUp and PageDown.\n",
+                    "Press F1 to only show logs from this example."
+                ),
+                TextStyle {
+                    font_size: 25.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn log_system(time: Res<Time>, mut timer: Local<Timer>, mut count: Local<usize>) {
+    if timer.duration().is_zero() {
+        *timer = Timer::from_seconds(0.5, TimerMode::Repeating);
+    }
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    *count += 1;
+    match *count % 4 {
+        0 => error!("something failed"),
+        1 => warn!(count = *count, "something took longer than expected"),
+        2 => info!("helpful information that is worth printing by default"),
+        _ => debug!("helpful for debugging"),
+    }
+}
+
+fn filter_console(input: Res<ButtonInput<KeyCode>>, mut console: ResMut<LogConsole>) {
+    if input.just_pressed(KeyCode::F1) {
+        // Targets are module paths, so this also works for plugins and crates
+        console.target_filter = if console.target_filter.is_empty() {
+            "log_console".to_string()
+        } else {
+            String::new()
+        };
+    }
+}
@@ -68,6 +68,20 @@ fn draw_example_collection(
 
     gizmos.rect_2d(Vec2::ZERO, 0., Vec2::splat(650.), BLACK);
 
+    // Filled polygons may be concave, and are drawn behind the lines.
+    gizmos
+        .polygon_filled_2d(
+            [
+                Vec2::new(-60., -40.),
+                Vec2::new(0., -10.),
+                Vec2::new(60., -40.),
+                Vec2::new(40., 40.),
+                Vec2::new(-40., 40.),
+            ],
+            STEEL_BLUE.with_alpha(0.4),
+        )
+        .outline(STEEL_BLUE);
+
     my_gizmos
         .rounded_rect_2d(Vec2::ZERO, 0., Vec2::splat(630.), BLACK)
         .corner_radius((time.elapsed_seconds() / 3.).cos() * 100.);
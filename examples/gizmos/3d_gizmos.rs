@@ -55,10 +55,12 @@ fn setup(
         TextBundle::from_section(
             "Press 'D' to toggle drawing gizmos on top of everything else in the scene\n\
             Press 'P' to toggle perspective for line gizmos\n\
+            Press 'O' to toggle drawing occluded parts of gizmos dimmed\n\
             Hold 'Left' or 'Right' to change the line width of straight gizmos\n\
             Hold 'Up' or 'Down' to change the line width of round gizmos\n\
             Press '1' or '2' to toggle the visibility of straight gizmos or round gizmos\n\
             Press 'A' to show all AABB boxes\n\
+            Press 'B' to show the bounding spheres of AABB boxes\n\
             Press 'U' or 'I' to cycle through line styles for straight or round gizmos\n\
             Press 'J' or 'K' to cycle through line joins for straight or round gizmos",
             TextStyle::default(),
@@ -81,6 +83,7 @@ fn rotate_camera(mut query: Query<&mut Transform, With<Camera>>, time: Res<Time>
 fn draw_example_collection(
     mut gizmos: Gizmos,
     mut my_gizmos: Gizmos<MyRoundGizmos>,
+    camera: Query<&GlobalTransform, With<Camera>>,
     time: Res<Time>,
 ) {
     gizmos.grid(
@@ -115,12 +118,16 @@ fn draw_example_collection(
         .edge_radius(0.1)
         .arc_resolution(4);
 
-    for y in [0., 0.5, 1.] {
-        gizmos.ray(
-            Vec3::new(1., y, 0.),
-            Vec3::new(-3., (time.elapsed_seconds() * 3.).sin(), 0.),
-            BLUE,
-        );
+    // X-ray gizmos are drawn through other geometry.
+    {
+        let mut x_ray = gizmos.x_ray();
+        for y in [0., 0.5, 1.] {
+            x_ray.ray(
+                Vec3::new(1., y, 0.),
+                Vec3::new(-3., (time.elapsed_seconds() * 3.).sin(), 0.),
+                BLUE,
+            );
+        }
     }
 
     my_gizmos
@@ -143,13 +150,26 @@ fn draw_example_collection(
         .sphere(Vec3::ZERO, Quat::IDENTITY, 3.2, BLACK)
         .resolution(64);
 
-    gizmos.arrow(Vec3::ZERO, Vec3::ONE * 1.5, YELLOW);
+    // The line width can be overridden for individual gizmos.
+    gizmos
+        .with_line_width(8.)
+        .arrow(Vec3::ZERO, Vec3::ONE * 1.5, YELLOW);
 
     // You can create more complex arrows using the arrow builder.
     gizmos
         .arrow(Vec3::new(2., 0., 2.), Vec3::new(2., 2., 2.), ORANGE_RED)
         .with_double_end()
         .with_tip_length(0.5);
+
+    // Screen space gizmos are drawn in pixels, on top of everything else.
+    gizmos
+        .screen()
+        .rect_2d(Vec2::new(80., 80.), 0., Vec2::splat(100.), WHITE);
+
+    // Text labels can be billboarded to face the camera.
+    gizmos
+        .text(Vec3::new(2., 2.4, 2.), "Double arrow", WHITE)
+        .billboard(camera.single());
 }
 
 fn update_config(
@@ -170,6 +190,11 @@ fn update_config(
             config.line_width *= if config.line_perspective { 5. } else { 1. / 5. };
         }
     }
+    if keyboard.just_pressed(KeyCode::KeyO) {
+        for (_, config, _) in config_store.iter_mut() {
+            config.occluded_opacity = if config.occluded_opacity == 0. { 0.25 } else { 0. };
+        }
+    }
 
     let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
     if keyboard.pressed(KeyCode::ArrowRight) {
@@ -230,4 +255,11 @@ fn update_config(
         // We can change this behaviour in the configuration of AabbGizmoGroup
         config_store.config_mut::<AabbGizmoConfigGroup>().1.draw_all ^= true;
     }
+
+    if keyboard.just_pressed(KeyCode::KeyB) {
+        config_store
+            .config_mut::<AabbGizmoConfigGroup>()
+            .1
+            .draw_sphere_bounds ^= true;
+    }
 }
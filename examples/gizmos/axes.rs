@@ -107,7 +107,7 @@ fn setup(
 fn draw_axes(mut gizmos: Gizmos, query: Query<(&Transform, &Aabb), With<ShowAxes>>) {
     for (&transform, &aabb) in &query {
         let length = aabb.half_extents.length();
-        gizmos.axes(transform, length);
+        gizmos.axes(transform, length).labels();
     }
 }
 
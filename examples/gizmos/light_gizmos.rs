@@ -114,7 +114,8 @@ fn setup(
                 "Press 'D' to toggle drawing gizmos on top of everything else in the scene\n\
             Hold 'Left' or 'Right' to change the line width of the gizmos\n\
             Press 'A' to toggle drawing of the light gizmos\n\
-            Press 'C' to cycle between the light gizmos coloring modes",
+            Press 'C' to cycle between the light gizmos coloring modes\n\
+            Press 'F' to toggle drawing of the light falloff",
                 text_style.clone(),
             )
             .with_style(Style {
@@ -175,6 +176,9 @@ fn update_config(
     if keyboard.just_pressed(KeyCode::KeyA) {
         config.enabled ^= true;
     }
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        light_config.draw_falloff ^= true;
+    }
     if keyboard.just_pressed(KeyCode::KeyC) {
         light_config.color = match light_config.color {
             LightGizmoColor::Manual(_) => LightGizmoColor::Varied,
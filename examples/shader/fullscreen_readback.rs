@@ -0,0 +1,106 @@
+//! Computes the average luminance of the scene with a fullscreen shader, and reads it back to
+//! the CPU each frame.
+//!
+//! The shader in `assets/shaders/average_luminance.wgsl` runs over the camera's output after post
+//! processing, and writes its result to a single pixel texture, which is read back and sent to the
+//! main world as a [`FullscreenReadbackComplete`] event.
+
+use bevy::{
+    core_pipeline::fullscreen_readback::{FullscreenReadback, FullscreenReadbackComplete},
+    prelude::*,
+    render::render_resource::TextureFormat,
+};
+
+/// This example uses a shader source file from the assets subdirectory
+const SHADER_ASSET_PATH: &str = "shaders/average_luminance.wgsl";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_light, show_luminance))
+        .run();
+}
+
+#[derive(Component)]
+struct LuminanceText;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        FullscreenReadback {
+            shader: asset_server.load(SHADER_ASSET_PATH),
+            output_size: UVec2::ONE,
+            output_format: TextureFormat::R32Float,
+        },
+    ));
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(5.0, 5.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::default()),
+        material: materials.add(Color::srgb(0.8, 0.7, 0.6)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+
+    commands.spawn((
+        TextBundle::from_section("Average luminance: -", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        LuminanceText,
+    ));
+}
+
+/// Moves the light in and out of the scene, so the luminance changes
+fn move_light(time: Res<Time>, mut lights: Query<&mut Transform, With<PointLight>>) {
+    for mut transform in &mut lights {
+        transform.translation.y = 8.0 + 6.0 * time.elapsed_seconds().sin();
+    }
+}
+
+fn show_luminance(
+    mut readbacks: EventReader<FullscreenReadbackComplete>,
+    mut text: Query<&mut Text, With<LuminanceText>>,
+) {
+    // Only the latest readback is interesting
+    let Some(readback) = readbacks.read().last() else {
+        return;
+    };
+    // The output texture is a single `R32Float` pixel
+    let Some(bytes) = readback
+        .data
+        .get(..4)
+        .and_then(|bytes| bytes.try_into().ok())
+    else {
+        return;
+    };
+    let luminance = f32::from_ne_bytes(bytes);
+    text.single_mut().sections[0].value = format!("Average luminance: {luminance:.3}");
+}
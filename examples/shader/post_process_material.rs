@@ -0,0 +1,97 @@
+//! A post processing effect defined as a material, which runs over the output of a camera.
+//!
+//! Unlike the custom render pass of the `post_processing` example, this doesn't need a render
+//! graph node: implementing [`PostProcessMaterial`] and adding a [`PostProcessMaterialPlugin`] is
+//! enough.
+
+use bevy::{
+    core_pipeline::post_process_material::{PostProcessMaterial, PostProcessMaterialPlugin},
+    prelude::*,
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+/// This example uses a shader source file from the assets subdirectory
+const SHADER_ASSET_PATH: &str = "shaders/color_grading_material.wgsl";
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            PostProcessMaterialPlugin::<ColorGradingMaterial>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_saturation)
+        .run();
+}
+
+// Both fields are written to the same uniform, see `assets/shaders/color_grading_material.wgsl`
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+struct ColorGradingMaterial {
+    #[uniform(0)]
+    tint: LinearRgba,
+    #[uniform(0)]
+    saturation: f32,
+}
+
+impl PostProcessMaterial for ColorGradingMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SHADER_ASSET_PATH.into()
+    }
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut color_grading_materials: ResMut<Assets<ColorGradingMaterial>>,
+) {
+    // camera, with the post process material
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        color_grading_materials.add(ColorGradingMaterial {
+            tint: LinearRgba::new(1.0, 0.9, 0.75, 1.0),
+            saturation: 1.0,
+        }),
+    ));
+
+    // plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(5.0, 5.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::default()),
+        material: materials.add(Color::srgb(0.2, 0.4, 0.9)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+}
+
+/// Fades the scene in and out of grayscale
+fn update_saturation(
+    time: Res<Time>,
+    cameras: Query<&Handle<ColorGradingMaterial>>,
+    mut materials: ResMut<Assets<ColorGradingMaterial>>,
+) {
+    for handle in &cameras {
+        if let Some(material) = materials.get_mut(handle) {
+            material.saturation = time.elapsed_seconds().sin() * 0.5 + 0.5;
+        }
+    }
+}
@@ -13,15 +13,11 @@ use bevy::{
     render::primitives::{Aabb, Sphere},
 };
 
-#[path = "../../helpers/camera_controller.rs"]
-mod camera_controller;
-
 #[cfg(feature = "animation")]
 mod animation_plugin;
 mod morph_viewer_plugin;
 mod scene_viewer_plugin;
 
-use camera_controller::{CameraController, CameraControllerPlugin};
 use morph_viewer_plugin::MorphViewerPlugin;
 use scene_viewer_plugin::{SceneHandle, SceneViewerPlugin};
 
@@ -115,10 +111,8 @@ fn setup_scene_after_load(
         let mut projection = PerspectiveProjection::default();
         projection.far = projection.far.max(size * 10.0);
 
-        let walk_speed = size * 3.0;
         let camera_controller = CameraController {
-            walk_speed,
-            run_speed: 3.0 * walk_speed,
+            walk_speed: size * 3.0,
             ..default()
         };
 
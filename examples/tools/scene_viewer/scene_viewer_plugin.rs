@@ -11,8 +11,6 @@ use bevy::{
 use std::f32::consts::*;
 use std::fmt;
 
-use super::camera_controller::*;
-
 #[derive(Resource)]
 pub struct SceneHandle {
     pub gltf_handle: Handle<Gltf>,
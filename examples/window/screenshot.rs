@@ -1,14 +1,21 @@
 //! An example showing how to save screenshots to disk
 
 use bevy::prelude::*;
-use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::render::view::screenshot::{CameraScreenshot, ScreenshotCaptured, ScreenshotManager};
 use bevy::window::PrimaryWindow;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_systems(Startup, setup)
-        .add_systems(Update, screenshot_on_spacebar)
+        .add_systems(
+            Update,
+            (
+                screenshot_on_spacebar,
+                region_screenshot_on_r,
+                save_region_screenshots,
+            ),
+        )
         .run();
 }
 
@@ -27,6 +34,36 @@ fn screenshot_on_spacebar(
     }
 }
 
+fn region_screenshot_on_r(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    camera: Query<Entity, With<Camera>>,
+) {
+    if input.just_pressed(KeyCode::KeyR) {
+        // Capture the top left corner of the camera's viewport
+        commands.entity(camera.single()).insert(CameraScreenshot {
+            region: Some(URect::new(0, 0, 256, 256)),
+        });
+    }
+}
+
+fn save_region_screenshots(
+    mut screenshots: EventReader<ScreenshotCaptured>,
+    mut counter: Local<u32>,
+) {
+    for screenshot in screenshots.read() {
+        let path = format!("./region-screenshot-{}.png", *counter);
+        *counter += 1;
+        match screenshot.image.clone().try_into_dynamic() {
+            Ok(image) => match image.to_rgb8().save(&path) {
+                Ok(()) => info!("Region screenshot saved to {path}"),
+                Err(e) => error!("Cannot save region screenshot: {e}"),
+            },
+            Err(e) => error!("Cannot convert region screenshot: {e}"),
+        }
+    }
+}
+
 /// set up a simple 3D scene
 fn setup(
     mut commands: Commands,
@@ -63,7 +100,8 @@ fn setup(
 
     commands.spawn(
         TextBundle::from_section(
-            "Press <spacebar> to save a screenshot to disk",
+            "Press <spacebar> to save a screenshot to disk\n\
+             Press R to save the top left corner of the camera's view",
             TextStyle::default(),
         )
         .with_style(Style {
@@ -0,0 +1,90 @@
+//! A golden image test of a [`UiMaterial`], which renders it offscreen and compares it to
+//! `tests/golden_images/ui_material.png`.
+//! This is run in CI to ensure that UI materials keep rendering the same.
+//!
+//! Run it from the repository root with
+//! `cargo run --example golden_ui_material --features bevy_golden_image_testing`, and set
+//! `BEVY_UPDATE_GOLDEN_IMAGES` to update the reference image after an intended change.
+use bevy::{
+    dev_tools::golden_image::{GoldenImageCamera, GoldenImageTest},
+    prelude::*,
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins((
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            })
+            .disable::<WinitPlugin>(),
+        UiMaterialPlugin::<SplitMaterial>::default(),
+    ))
+    .insert_resource(Msaa::Off)
+    .add_systems(Startup, setup);
+
+    let test = GoldenImageTest {
+        // Leave time for the shader to load and its pipeline to compile
+        warmup_frames: 100,
+        ..GoldenImageTest::new("ui_material").with_size(UVec2::splat(64))
+    };
+    if let Err(err) = test.run(&mut app) {
+        error!("{err}");
+        std::process::exit(1);
+    }
+}
+
+fn setup(mut commands: Commands, mut ui_materials: ResMut<Assets<SplitMaterial>>) {
+    let camera = commands
+        .spawn((
+            Camera2dBundle {
+                camera: Camera {
+                    clear_color: ClearColorConfig::Custom(Color::BLACK),
+                    ..default()
+                },
+                ..default()
+            },
+            GoldenImageCamera,
+        ))
+        .id();
+
+    commands.spawn((
+        MaterialNodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(16.0),
+                top: Val::Px(24.0),
+                width: Val::Px(32.0),
+                height: Val::Px(16.0),
+                ..default()
+            },
+            material: ui_materials.add(SplitMaterial {
+                left: LinearRgba::RED,
+                right: LinearRgba::BLUE,
+            }),
+            ..default()
+        },
+        TargetCamera(camera),
+    ));
+}
+
+/// Colors the left and the right half of a node differently, to test the UVs as well.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+struct SplitMaterial {
+    #[uniform(0)]
+    left: LinearRgba,
+    #[uniform(1)]
+    right: LinearRgba,
+}
+
+impl UiMaterial for SplitMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/golden_ui_material.wgsl".into()
+    }
+}
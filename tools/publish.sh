@@ -43,6 +43,7 @@ crates=(
     bevy_a11y
     bevy_ui
     bevy_winit
+    bevy_camera_controller
     bevy_dev_tools
     bevy_internal
     bevy_dylib